@@ -1,28 +1,29 @@
 use serde_json::json;
-use std::fs::{create_dir_all, File};
+use std::fs::{File, create_dir_all};
 use std::io::Write;
 use std::path::Path;
 
 use symposium_booklet::parse_two_workbooks;
 
 fn main() {
-    // Require explicit file paths via cargo env vars; fail early if missing
-    let abstracts_path = option_env!("SYMPOSIUM_ABSTRACTS")
-        .expect("SYMPOSIUM_ABSTRACTS must be set in .cargo/config.toml");
-    let grouping_path = option_env!("SYMPOSIUM_GROUPING")
-        .expect("SYMPOSIUM_GROUPING must be set in .cargo/config.toml");
+    // Require explicit file paths in the environment; fail early if missing
+    let abstracts_path = std::env::var("SYMPOSIUM_ABSTRACTS")
+        .expect("SYMPOSIUM_ABSTRACTS must be set in the environment");
+    let grouping_path = std::env::var("SYMPOSIUM_GROUPING")
+        .expect("SYMPOSIUM_GROUPING must be set in the environment");
 
     // parse the two explicit workbooks
-    let (abstracts_map, sessions) = match parse_two_workbooks(abstracts_path, grouping_path) {
-        Ok(res) => res,
-        Err(e) => {
-            eprintln!("Error parsing workbooks: {}", e);
-            std::process::exit(1);
-        }
-    };
+    let (abstracts_map, sessions) =
+        match parse_two_workbooks(&abstracts_path, &grouping_path, &Default::default()) {
+            Ok(res) => res,
+            Err(e) => {
+                eprintln!("Error parsing workbooks: {}", e);
+                std::process::exit(1);
+            }
+        };
 
     // convert abstracts map to vec
-    let mut abstracts: Vec<_> = abstracts_map.into_iter().map(|(_k, v)| v).collect();
+    let mut abstracts: Vec<_> = abstracts_map.into_values().collect();
     // sort by id for deterministic output
     abstracts.sort_by(|a, b| a.id.cmp(&b.id));
 
@@ -1,4 +1,4 @@
-use symposium_booklet::{cli::BuildOpts, io, log};
+use symposium_booklet::{cli::BuildOpts, docx::DocxSplit, io, log};
 
 fn main() -> anyhow::Result<()> {
     log::init()?;
@@ -12,6 +12,8 @@ fn main() -> anyhow::Result<()> {
 
     let opts = BuildOpts {
         input: input.clone(),
+        source: Default::default(),
+        grouping: None,
         output: output.clone(),
         template: None,
         locales: locales.clone(),
@@ -19,6 +21,18 @@ fn main() -> anyhow::Result<()> {
         emit_parse_json: false,
         verbose: false,
         typst_bin: None,
+        no_pdf: false,
+        preview: Vec::new(),
+        jobs: None,
+        no_cache: false,
+        config: None,
+        only_session: Vec::new(),
+        only_id: Vec::new(),
+        keyword: Vec::new(),
+        no_linkify: false,
+        data_json: false,
+        format: Vec::new(),
+        docx_split: DocxSplit::Locale,
     };
 
     io::run_build(opts)?;
@@ -0,0 +1,596 @@
+use super::{
+    AbstractData, BookData, BookOptions, EventData, IndexEntry, Origin, SCHEMA_VERSION,
+    SectionData, SessionData, TagData, TagRefData,
+};
+use crate::collate;
+use crate::config::{ContentConfig, SessionOrder, Untranslated};
+use crate::model::{Abstract, Session, SourceRef};
+use crate::richtext as rich;
+use crate::typst::markup::{Block, Inline, text};
+use crate::typst::{links, locale, template};
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use toml::value::Table;
+
+// Building the editions from abstract records in the form `write_markdown`
+// writes them: the frontmatter and body of each abstract, grouped by session.
+// `build` makes them from the parsed model, `emit-typst` reads them back from
+// the markdown output.
+
+#[derive(Debug, Deserialize, Clone)]
+pub(crate) struct FrontMatter {
+    id: String,
+    title: String,
+    authors: Option<Vec<String>>,
+    affiliation: Option<String>,
+    order: Option<u32>,
+    locale: Option<String>,
+    keywords: Option<Vec<String>>,
+    take_home: Option<String>,
+    reference: Option<String>,
+    sections: Option<Vec<AbstractSection>>,
+    #[serde(default)]
+    markup: bool,
+    variants: Option<BTreeMap<String, VariantFrontMatter>>,
+    source: Option<SourceRef>,
+    /// Markdown file relative to the output directory.
+    #[serde(skip)]
+    pub(crate) file: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct VariantFrontMatter {
+    title: String,
+    sections: Option<Vec<AbstractSection>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct AbstractSection {
+    label: String,
+    text: String,
+}
+
+// Sessions in manifest order: (title, slug, abstracts in every language);
+// each booklet picks its texts.
+pub(crate) type Sessions = Vec<(String, String, Vec<(FrontMatter, String)>)>;
+
+// The edition for each booklet locale, with the input origin of every abstract
+// label.
+pub(crate) fn editions(
+    all_sessions: &Sessions,
+    event: &EventData,
+    partial: Option<&str>,
+    locales_csv: &str,
+    template: &Option<String>,
+    opts: &BookOptions,
+) -> Result<Vec<(BookData, HashMap<String, Origin>)>> {
+    let partial = partial.map(str::to_string);
+    let (template_dir, _) = template::template_location(template);
+    let locale_dir = locale::locale_dir(&template_dir);
+    let mut books = Vec::new();
+    for locale in crate::util::locales(locales_csv) {
+        let labels = load_labels(&locale_dir, locale)?;
+        books.push(book_data(
+            all_sessions,
+            event,
+            &partial,
+            locale,
+            labels,
+            opts,
+        ));
+    }
+    Ok(books)
+}
+
+// A booklet locale's labels, with a warning when they come from another locale.
+fn load_labels(locale_dir: &Path, locale: &str) -> Result<Table> {
+    let labels = locale::load_labels(locale_dir, locale)?;
+    if labels.found.first().map(String::as_str) != Some(locale) {
+        tracing::warn!(
+            "No locale file for {}; labels from {}",
+            locale,
+            labels
+                .found
+                .first()
+                .map(String::as_str)
+                .unwrap_or("built-in defaults")
+        );
+    }
+    Ok(labels.values)
+}
+
+// Sessions as `read_output` finds them in the markdown `build` writes, from the
+// parsed model.
+pub(crate) fn model_sessions(
+    abstracts: &HashMap<String, Abstract>,
+    sessions: &[Session],
+) -> Result<Sessions> {
+    let mut all_sessions: Sessions = Vec::new();
+    for crate::io::markdown::SessionFiles { session, files, .. } in
+        crate::io::markdown::markdown_files(abstracts, sessions)?
+    {
+        let items: Vec<(FrontMatter, String)> = files
+            .iter()
+            .map(|(path, abs, order)| front_matter(abs, path, *order))
+            .collect();
+        if items.is_empty() {
+            continue;
+        }
+        // sessions with the same title are merged
+        if let Some((_, _, v)) = all_sessions
+            .iter_mut()
+            .find(|(t, _, _)| t == &session.title)
+        {
+            v.extend(items);
+        } else {
+            all_sessions.push((
+                session.title.clone(),
+                crate::io::markdown::session_slug(session),
+                items,
+            ));
+        }
+    }
+    Ok(all_sessions)
+}
+
+// The frontmatter and body `write_markdown` writes for an abstract.
+fn front_matter(abs: &Abstract, file: &Path, order: u32) -> (FrontMatter, String) {
+    let sections = |sections: &[crate::model::AbstractSection]| {
+        (!sections.is_empty()).then(|| {
+            sections
+                .iter()
+                .map(|s| AbstractSection {
+                    label: s.label.clone(),
+                    text: s.text.clone(),
+                })
+                .collect()
+        })
+    };
+    let fm = FrontMatter {
+        id: abs.id.clone(),
+        title: abs.title.clone(),
+        authors: Some(abs.authors.clone()),
+        affiliation: abs.affiliation.clone(),
+        order: Some(order),
+        locale: Some(abs.locale.clone()),
+        keywords: (!abs.keywords.is_empty()).then(|| abs.keywords.clone()),
+        take_home: abs.take_home.clone(),
+        reference: abs.reference.clone(),
+        sections: sections(&abs.abstract_sections),
+        markup: abs.abstract_sections.iter().any(|s| s.rich.is_some()),
+        variants: (!abs.variants.is_empty()).then(|| {
+            abs.variants
+                .iter()
+                .map(|(locale, variant)| {
+                    (
+                        locale.clone(),
+                        VariantFrontMatter {
+                            title: variant.title.clone(),
+                            sections: sections(&variant.abstract_sections),
+                        },
+                    )
+                })
+                .collect()
+        }),
+        source: abs.source.clone(),
+        file: file.to_string_lossy().replace('\\', "/"),
+    };
+    (fm, abs.abstract_text.trim().to_string())
+}
+
+// The book for one locale, with the input origin of every abstract label.
+fn book_data(
+    all_sessions: &Sessions,
+    event: &EventData,
+    partial: &Option<String>,
+    locale: &str,
+    labels: Table,
+    opts: &BookOptions,
+) -> (BookData, HashMap<String, Origin>) {
+    // tag -> (title, label) of the abstracts carrying it
+    let mut keyword_map: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    let mut origins: HashMap<String, Origin> = HashMap::new();
+    let mut label_state = LabelState::default();
+    let mut sessions = Vec::new();
+    for (sess_title, sess_slug, abstracts) in all_sessions {
+        let mut abs_sorted: Vec<(FrontMatter, String, bool)> = abstracts
+            .iter()
+            .filter_map(|(fm, body)| select_text(fm, body, locale, &opts.content))
+            .collect();
+        if abs_sorted.is_empty() {
+            continue;
+        }
+        // sort by order if present
+        abs_sorted.sort_by_key(|(fm, _, _)| fm.order.unwrap_or(0));
+        let mut items = Vec::new();
+        for (fm, body, untranslated) in abs_sorted.iter() {
+            let abs_label = label_state.next(fm);
+            let mut abs = abstract_data(fm, body, abs_label, opts.linkify, *untranslated);
+            abs.tags = opts
+                .thesaurus
+                .tags(fm.keywords.as_deref().unwrap_or_default(), locale);
+            for tag in abs.tags.iter() {
+                keyword_map
+                    .entry(tag.clone())
+                    .or_default()
+                    .push((abs.title.clone(), abs.label.clone()));
+            }
+            origins.insert(
+                abs.label.clone(),
+                Origin {
+                    session: sess_title.clone(),
+                    markdown: fm.file.clone(),
+                    source: fm.source.clone(),
+                },
+            );
+            items.push(abs);
+        }
+        sessions.push(SessionData {
+            title: sess_title.clone(),
+            slug: sess_slug.clone(),
+            abstracts: items,
+        });
+    }
+    if opts.session_order == SessionOrder::Title {
+        sessions.sort_by(|a, b| collate::compare(&a.title, &b.title, locale));
+    }
+    let author_index = name_index(&sessions, locale, |abs| {
+        abs.authors.iter().map(|a| index_name(a)).collect()
+    });
+    let affiliation_index = if opts.index.affiliations {
+        name_index(&sessions, locale, |abs| abs.affiliations.clone())
+    } else {
+        Vec::new()
+    };
+
+    let book = BookData {
+        schema_version: SCHEMA_VERSION,
+        locale: locale.to_string(),
+        event: event.clone(),
+        labels,
+        partial: partial.clone(),
+        sessions,
+        tag_index: tag_index(keyword_map, locale),
+        author_index,
+        affiliation_index,
+        index_letters: opts.index.letters,
+    };
+    (book, origins)
+}
+
+// Same language: equal tags or equal primary subtags (`da-DK` and `da`).
+fn same_language(a: &str, b: &str) -> bool {
+    let norm = |s: &str| s.trim().to_lowercase().replace('_', "-");
+    let (a, b) = (norm(a), norm(b));
+    let primary = |s: &str| s.split('-').next().unwrap_or_default().to_string();
+    a == b || primary(&a) == primary(&b)
+}
+
+// The language an abstract is printed in for one booklet.
+enum Language<'a> {
+    // its own text, untranslated when `true`
+    Own(bool),
+    // the variant with this locale
+    Variant(&'a str),
+}
+
+// The first accepted language (`[content] sources`) the abstract has, its own or
+// a variant's, else its own text as `[content] untranslated` says.
+fn choose_language<'a>(
+    own: &str,
+    variants: impl IntoIterator<Item = &'a str>,
+    booklet: &str,
+    content: &ContentConfig,
+) -> Option<Language<'a>> {
+    let variants: Vec<&str> = variants.into_iter().collect();
+    let primary = booklet.split(['-', '_']).next().unwrap_or(booklet);
+    let accepted = content
+        .sources
+        .get(booklet)
+        .or_else(|| content.sources.get(primary))
+        .cloned()
+        .unwrap_or_else(|| vec![booklet.to_string()]);
+    for lang in &accepted {
+        if same_language(lang, own) {
+            return Some(Language::Own(false));
+        }
+        if let Some(variant) = variants.iter().find(|l| same_language(lang, l)) {
+            return Some(Language::Variant(variant));
+        }
+    }
+    match content.untranslated {
+        Untranslated::Omit => None,
+        Untranslated::Include => Some(Language::Own(false)),
+        Untranslated::Mark => Some(Language::Own(true)),
+    }
+}
+
+// The text of an abstract for one booklet (see `choose_language`). Returns the
+// frontmatter with that text, the body and whether it is untranslated.
+fn select_text(
+    fm: &FrontMatter,
+    body: &str,
+    booklet: &str,
+    content: &ContentConfig,
+) -> Option<(FrontMatter, String, bool)> {
+    let own = fm.locale.as_deref().unwrap_or("en");
+    let variants = fm.variants.iter().flatten().map(|(l, _)| l.as_str());
+    let variant_locale = match choose_language(own, variants, booklet, content)? {
+        Language::Own(untranslated) => return Some((fm.clone(), body.to_string(), untranslated)),
+        Language::Variant(locale) => locale,
+    };
+    let variant = &fm.variants.as_ref()?[variant_locale];
+    let mut chosen = fm.clone();
+    chosen.title = variant.title.clone();
+    chosen.sections = variant.sections.clone();
+    chosen.locale = Some(variant_locale.to_string());
+    chosen.variants = None;
+    // errors in the variant point at its own columns
+    if let Some(source) = chosen.source.as_mut() {
+        for field in ["title", "abstract"] {
+            let own_column = format!("{}_{}", field, variant_locale);
+            if let Some(column) = source.columns.get(&own_column).cloned() {
+                source.columns.insert(field.to_string(), column);
+            }
+        }
+    }
+    let body = variant
+        .sections
+        .iter()
+        .flatten()
+        .map(|s| s.text.trim())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Some((chosen, body, false))
+}
+
+fn abstract_data(
+    fm: &FrontMatter,
+    body: &str,
+    label: String,
+    linkify: bool,
+    untranslated: bool,
+) -> AbstractData {
+    let affiliations = match &fm.affiliation {
+        Some(aff) => {
+            let list = unique_list(aff);
+            if list.is_empty() {
+                vec![aff.clone()]
+            } else {
+                list
+            }
+        }
+        None => Vec::new(),
+    };
+    let mut sections: Vec<SectionData> = fm
+        .sections
+        .iter()
+        .flatten()
+        .map(|s| (s.label.trim(), s.text.trim()))
+        .filter(|(_, t)| !t.is_empty())
+        .map(|(label, section_text)| SectionData {
+            label: label.to_string(),
+            text: section_text.to_string(),
+            body: body_blocks(section_text, fm.markup, linkify),
+        })
+        .collect();
+    if sections.is_empty() && !body.trim().is_empty() {
+        sections.push(SectionData {
+            label: String::new(),
+            text: body.trim().to_string(),
+            body: body_blocks(body.trim(), fm.markup, linkify),
+        });
+    }
+    let inline = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| with_links(vec![Block::Paragraph(vec![text(v)])], linkify))
+    };
+    AbstractData {
+        id: fm.id.clone(),
+        label,
+        locale: fm.locale.clone().unwrap_or_else(|| "en".to_string()),
+        untranslated,
+        title: fm.title.clone(),
+        authors: fm.authors.clone().unwrap_or_default(),
+        affiliations,
+        sections,
+        take_home: inline(&fm.take_home),
+        reference: inline(&fm.reference),
+        tags: Vec::new(),
+    }
+}
+
+// Tag index entries: tag -> abstracts (one entry per abstract), tags and titles
+// collated by the booklet locale.
+fn tag_index(keyword_map: HashMap<String, Vec<(String, String)>>, locale: &str) -> Vec<TagData> {
+    let mut entries: Vec<TagData> = keyword_map
+        .into_iter()
+        .map(|(tag, titles)| TagData {
+            letter: collate::initial(&tag, locale),
+            tag,
+            abstracts: index_refs(titles, locale),
+        })
+        .collect();
+    entries.sort_by(|a, b| collate::compare(&a.tag, &b.tag, locale));
+    entries
+}
+
+// Abstract references of one index entry: collated by title, one per abstract.
+fn index_refs(mut titles: Vec<(String, String)>, locale: &str) -> Vec<TagRefData> {
+    titles.sort_by(|a, b| collate::compare(&a.0, &b.0, locale));
+    titles.dedup_by(|a, b| a.1 == b.1);
+    titles
+        .into_iter()
+        .map(|(title, label)| TagRefData { title, label })
+        .collect()
+}
+
+// Author form for the index: "Surname, Given names", without a trailing
+// ", title" from the presenter column.
+fn index_name(author: &str) -> String {
+    let name = author.split(',').next().unwrap_or("").trim();
+    let words: Vec<&str> = name.split_whitespace().collect();
+    match words.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{}, {}", last, rest.join(" ")),
+        _ => name.to_string(),
+    }
+}
+
+// Author or affiliation index: `names` lists an abstract's entries.
+fn name_index(
+    sessions: &[SessionData],
+    locale: &str,
+    names: impl Fn(&AbstractData) -> Vec<String>,
+) -> Vec<IndexEntry> {
+    // case-insensitive grouping; the first spelling seen is shown
+    let mut map: HashMap<String, (String, Vec<(String, String)>)> = HashMap::new();
+    for abs in sessions.iter().flat_map(|s| s.abstracts.iter()) {
+        for name in names(abs) {
+            if name.trim().is_empty() {
+                continue;
+            }
+            map.entry(name.to_lowercase())
+                .or_insert_with(|| (name.clone(), Vec::new()))
+                .1
+                .push((abs.title.clone(), abs.label.clone()));
+        }
+    }
+    let mut entries: Vec<IndexEntry> = map
+        .into_values()
+        .map(|(name, titles)| IndexEntry {
+            letter: collate::initial(&name, locale),
+            name,
+            abstracts: index_refs(titles, locale),
+        })
+        .collect();
+    entries.sort_by(|a, b| collate::compare(&a.name, &b.name, locale));
+    entries
+}
+
+fn rich_inlines(spans: &[rich::Inline]) -> Vec<Inline> {
+    spans
+        .iter()
+        .map(|span| match span {
+            rich::Inline::Text(t) => text(t),
+            rich::Inline::Strong(c) => Inline::Strong(rich_inlines(c)),
+            rich::Inline::Emph(c) => call("emph", rich_inlines(c)),
+            rich::Inline::Underline(c) => call("underline", rich_inlines(c)),
+            rich::Inline::Sub(c) => call("sub", rich_inlines(c)),
+            rich::Inline::Sup(c) => call("super", rich_inlines(c)),
+        })
+        .collect()
+}
+
+fn call(func: &str, body: Vec<Inline>) -> Inline {
+    Inline::Call {
+        func: func.to_string(),
+        body,
+    }
+}
+
+fn with_links(blocks: Vec<Block>, linkify: bool) -> Vec<Block> {
+    if linkify {
+        blocks.into_iter().map(links::linkify_block).collect()
+    } else {
+        blocks
+    }
+}
+
+// A body section, parsed as markup when the abstract was written with
+// `[text] markup` enabled.
+fn body_blocks(body: &str, markup: bool, linkify: bool) -> Vec<Block> {
+    if !markup {
+        return with_links(vec![Block::Paragraph(vec![text(body)])], linkify);
+    }
+    let (parsed, _) = rich::parse(body);
+    let blocks = parsed
+        .into_iter()
+        .map(|block| match block {
+            rich::Block::Paragraph(spans) => Block::Paragraph(rich_inlines(&spans)),
+            rich::Block::List(items) => {
+                Block::List(items.iter().map(|item| rich_inlines(item)).collect())
+            }
+        })
+        .collect();
+    with_links(blocks, linkify)
+}
+
+fn unique_list(input: &str) -> Vec<String> {
+    let mut parts: Vec<String> = input
+        .replace(" / ", ";")
+        .split(';')
+        .flat_map(|part| part.split('/'))
+        .map(|part| part.trim())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.to_string())
+        .collect();
+
+    if parts.len() <= 1 {
+        parts = input
+            .split(',')
+            .map(|part| part.trim())
+            .filter(|part| !part.is_empty())
+            .map(|part| part.to_string())
+            .collect();
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    let mut unique = Vec::new();
+    for part in parts {
+        if seen.insert(part.to_lowercase()) {
+            unique.push(part);
+        }
+    }
+    unique
+}
+
+fn label_for_abstract(fm: &FrontMatter) -> String {
+    let base = if !fm.id.is_empty() {
+        fm.id.clone()
+    } else {
+        fm.title.clone()
+    };
+    let mut label: String = base
+        .to_lowercase()
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '-' })
+        .collect();
+    while label.starts_with('-') {
+        label.remove(0);
+    }
+    while label.ends_with('-') {
+        label.pop();
+    }
+    if label.is_empty() {
+        label = "abstract".to_string();
+    }
+    format!("abs-{}", label)
+}
+
+#[derive(Default)]
+struct LabelState {
+    used: std::collections::HashSet<String>,
+    counter: u32,
+}
+
+impl LabelState {
+    fn next(&mut self, fm: &FrontMatter) -> String {
+        let base = label_for_abstract(fm);
+        if self.used.insert(base.clone()) {
+            return base;
+        }
+        loop {
+            self.counter += 1;
+            let candidate = format!("{}-{}", base, self.counter);
+            if self.used.insert(candidate.clone()) {
+                return candidate;
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+pub mod build;
+
+use crate::config::{ContentConfig, EventConfig, IndexConfig, SessionOrder};
+use crate::io::Selection;
+use crate::keywords::Thesaurus;
+use crate::model::SourceRef;
+use crate::typst::markup::Block;
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use toml::value::Table;
+
+// The book as every output format sees it: one edition per booklet locale with
+// the event, the sessions in order, their abstracts resolved for that locale
+// (language choice, markup, links, tags), the locale labels and the collated
+// indexes. Text is plain (unescaped); body content is a block tree (see
+// `typst::markup`), which each renderer turns into its own markup. An edition
+// as JSON is `typst/data_<locale>.json` (see spec/04 for the schema).
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+// All editions of a build, made once from the selected programme and shared by
+// the renderers (see `render::RenderContext::booklet`).
+pub struct Booklet {
+    pub editions: Vec<BookData>,
+    /// Input origin of every abstract label, per edition.
+    pub origins: Vec<HashMap<String, Origin>>,
+}
+
+impl Booklet {
+    pub fn new(
+        selection: &Selection,
+        event: &EventConfig,
+        partial: Option<&str>,
+        locales_csv: &str,
+        template: &Option<String>,
+        opts: &BookOptions,
+    ) -> Result<Self> {
+        let sessions = build::model_sessions(&selection.abstracts, &selection.sessions)?;
+        let event = EventData {
+            id: event.id.clone(),
+            title: event.title.clone(),
+            acronym: event.acronym.clone(),
+            start: event.start.clone(),
+            time_zone: event.time_zone.clone(),
+            url: event.url.clone(),
+        };
+        let (editions, origins) =
+            build::editions(&sessions, &event, partial, locales_csv, template, opts)?
+                .into_iter()
+                .unzip();
+        Ok(Booklet { editions, origins })
+    }
+}
+
+// What goes into the editions. The author index is always collated; each
+// renderer decides whether to print it.
+#[derive(Debug, Clone)]
+pub struct BookOptions {
+    /// URLs, DOIs and emails in body text, take-home and reference become links.
+    pub linkify: bool,
+    /// Which abstract languages go into which booklet.
+    pub content: ContentConfig,
+    pub index: IndexConfig,
+    pub session_order: SessionOrder,
+    /// Keyword synonyms and translations for tags and the tag index.
+    pub thesaurus: Thesaurus,
+}
+
+// Where an abstract came from, for source maps and proofreading copies.
+#[derive(Debug, Clone)]
+pub struct Origin {
+    pub session: String,
+    /// Markdown file relative to the output directory.
+    pub markdown: String,
+    pub source: Option<SourceRef>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BookData {
+    pub schema_version: u32,
+    pub locale: String,
+    pub event: EventData,
+    /// Locale labels after the fallback chain; tables nest.
+    pub labels: Table,
+    /// Filter label of a partial (subset) build.
+    pub partial: Option<String>,
+    pub sessions: Vec<SessionData>,
+    pub tag_index: Vec<TagData>,
+    /// Printed by the renderers that want it (see `render`).
+    pub author_index: Vec<IndexEntry>,
+    /// Only with `[index] affiliations`.
+    pub affiliation_index: Vec<IndexEntry>,
+    /// Templates print group letter headings (`[index] letters`).
+    pub index_letters: bool,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct EventData {
+    pub id: String,
+    pub title: String,
+    pub acronym: String,
+    pub start: String,
+    pub time_zone: String,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionData {
+    pub title: String,
+    pub slug: String,
+    pub abstracts: Vec<AbstractData>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AbstractData {
+    pub id: String,
+    /// Anchor name, unique within the book (`abs-<id>`); the Typst label.
+    pub label: String,
+    /// Language of the printed text.
+    pub locale: String,
+    /// Printed in its own language in another language's booklet
+    /// (`[content] untranslated = "mark"`).
+    pub untranslated: bool,
+    pub title: String,
+    pub authors: Vec<String>,
+    pub affiliations: Vec<String>,
+    pub sections: Vec<SectionData>,
+    pub take_home: Option<Vec<Block>>,
+    pub reference: Option<Vec<Block>>,
+    /// Display form, e.g. `#patient_safety`.
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SectionData {
+    pub label: String,
+    /// Section text as written (canonical markup when `markup` is set).
+    pub text: String,
+    pub body: Vec<Block>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TagData {
+    pub tag: String,
+    /// Group letter in the booklet locale's alphabet, e.g. `Æ`.
+    pub letter: String,
+    pub abstracts: Vec<TagRefData>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexEntry {
+    pub name: String,
+    pub letter: String,
+    pub abstracts: Vec<TagRefData>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TagRefData {
+    pub title: String,
+    pub label: String,
+}
@@ -1,3 +1,6 @@
+use crate::docx::DocxSplit;
+use crate::io::ingest::SourceKind;
+use crate::typst::PreviewFormat;
 use clap::{Parser, Subcommand};
 
 #[derive(Parser)]
@@ -12,6 +15,10 @@ pub struct Cli {
 pub enum Commands {
     /// Build markdown files and optionally render PDFs
     Build(BuildOpts),
+    /// Build, then rebuild whenever the input, config or template changes
+    Watch(WatchOpts),
+    /// Watch and rebuild like `watch`, and serve a live-reloading HTML preview
+    Serve(ServeOpts),
     /// Emit typst files from an existing output directory (manifest + markdowns)
     EmitTypst {
         /// Output directory containing manifest.json and per-session markdowns
@@ -29,11 +36,59 @@ pub enum Commands {
         /// Path to typst binary
         #[arg(long)]
         typst_bin: Option<String>,
+
+        /// Write the typst files only; do not compile PDFs
+        #[arg(long)]
+        no_pdf: bool,
+
+        /// Also render page previews into `output/preview/<locale>/` (png, svg; comma separated)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        preview: Vec<PreviewFormat>,
+
+        /// Locales compiled at the same time (default: number of CPUs)
+        #[arg(long)]
+        jobs: Option<usize>,
+
+        /// Ignore `output/.cache/`: regenerate and recompile everything
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Keep URLs, DOIs and emails as plain text (print-only editions)
+        #[arg(long)]
+        no_linkify: bool,
+
+        /// Also write `typst/data_<locale>.json` for Typst-native templates
+        #[arg(long)]
+        data_json: bool,
+
+        /// Build configuration file (TOML); `[text]` and `[content]` apply
+        #[arg(long)]
+        config: Option<String>,
     },
     /// Validate input files without writing output
     Validate {
         /// Input workbook or directory
         input: String,
+
+        /// Input format
+        #[arg(long, value_enum, default_value_t = SourceKind::Workbook)]
+        source: SourceKind,
+
+        /// Grouping workbook with the session sheet (for exports without sessions)
+        #[arg(long)]
+        grouping: Option<String>,
+
+        /// Build configuration file (TOML)
+        #[arg(long)]
+        config: Option<String>,
+
+        /// Locales whose labels are checked against the template (default en,da)
+        #[arg(long, default_value = "en,da")]
+        locales: String,
+
+        /// Template directory whose `labels.*` references are checked
+        #[arg(long)]
+        template: Option<String>,
     },
 }
 
@@ -43,6 +98,14 @@ pub struct BuildOpts {
     #[arg(long)]
     pub input: String,
 
+    /// Input format: our workbook, or an EasyChair/ConfTool/Pretalx export
+    #[arg(long, value_enum, default_value_t = SourceKind::Workbook)]
+    pub source: SourceKind,
+
+    /// Grouping workbook with the session sheet; without it, exports get one session per track
+    #[arg(long)]
+    pub grouping: Option<String>,
+
     /// Output directory
     #[arg(long)]
     pub output: String,
@@ -70,4 +133,84 @@ pub struct BuildOpts {
     /// Path to typst binary
     #[arg(long)]
     pub typst_bin: Option<String>,
+
+    /// Write the typst files only; do not compile PDFs
+    #[arg(long)]
+    pub no_pdf: bool,
+
+    /// Also render page previews into `output/preview/<locale>/` (png, svg; comma separated)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub preview: Vec<PreviewFormat>,
+
+    /// Locales compiled at the same time (default: number of CPUs)
+    #[arg(long)]
+    pub jobs: Option<usize>,
+
+    /// Ignore `output/.cache/`: regenerate and recompile everything
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Build configuration file (TOML)
+    #[arg(long)]
+    pub config: Option<String>,
+
+    /// Partial build: only these sessions (id, title or slug; comma separated)
+    #[arg(long, value_delimiter = ',')]
+    pub only_session: Vec<String>,
+
+    /// Partial build: only these abstract ids (comma separated)
+    #[arg(long, value_delimiter = ',')]
+    pub only_id: Vec<String>,
+
+    /// Partial build: only abstracts tagged with one of these keywords (comma separated)
+    #[arg(long, value_delimiter = ',')]
+    pub keyword: Vec<String>,
+
+    /// Keep URLs, DOIs and emails as plain text (print-only editions)
+    #[arg(long)]
+    pub no_linkify: bool,
+
+    /// Also write `typst/data_<locale>.json` for Typst-native templates
+    #[arg(long)]
+    pub data_json: bool,
+
+    /// Output formats (markdown, frab, typst, latex, html, epub, docx; comma separated; default `[output] formats`, else markdown,typst)
+    #[arg(long, value_delimiter = ',')]
+    pub format: Vec<String>,
+
+    /// Word documents (`--format docx`) per locale or per session
+    #[arg(long, value_enum, default_value = "locale")]
+    pub docx_split: DocxSplit,
+}
+
+#[derive(clap::Args, Clone)]
+pub struct WatchOpts {
+    #[command(flatten)]
+    pub build: BuildOpts,
+
+    /// Quiet time after the last change before rebuilding, in milliseconds
+    #[arg(long, default_value_t = 500)]
+    pub debounce: u64,
+
+    /// How often to look for changes, in milliseconds
+    #[arg(long, default_value_t = 250)]
+    pub poll: u64,
+
+    /// Stop after this many builds (scripts and tests)
+    #[arg(long)]
+    pub max_builds: Option<usize>,
+}
+
+#[derive(clap::Args, Clone)]
+pub struct ServeOpts {
+    #[command(flatten)]
+    pub watch: WatchOpts,
+
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    pub bind: String,
+
+    /// Port to listen on (0 picks a free one)
+    #[arg(long, default_value_t = 8000)]
+    pub port: u16,
 }
@@ -0,0 +1,119 @@
+use std::cmp::Ordering;
+
+// Locale-aware string collation. Byte order puts Æ/Ø/Å and accented letters after
+// "z" (and uppercase before lowercase); this builds primary sort keys from a
+// per-locale alphabet instead, with accents folded onto their base letter.
+
+// Letters after z, in alphabet order, for locales that have them.
+fn extra_letters(locale: &str) -> &'static [char] {
+    match language(locale).as_str() {
+        "da" | "nb" | "nn" | "no" => &['æ', 'ø', 'å'],
+        "sv" | "fi" => &['å', 'ä', 'ö'],
+        _ => &[],
+    }
+}
+
+fn language(locale: &str) -> String {
+    locale.split(['-', '_']).next().unwrap_or("").to_lowercase()
+}
+
+// Fold a lowercase character onto the letters of the locale's alphabet. Returns
+// one or two letters (e.g. "ae" for æ in English).
+fn fold(ch: char, locale: &str) -> Vec<char> {
+    let lang = language(locale);
+    let nordic = matches!(lang.as_str(), "da" | "nb" | "nn" | "no");
+    let swedish = matches!(lang.as_str(), "sv" | "fi");
+    match ch {
+        'æ' | 'ä' if nordic => vec!['æ'],
+        'ø' | 'ö' if nordic => vec!['ø'],
+        'å' if nordic || swedish => vec!['å'],
+        'ü' if nordic || swedish => vec!['y'],
+        'æ' | 'ä' if swedish => vec!['ä'],
+        'ø' | 'ö' if swedish => vec!['ö'],
+        'æ' => vec!['a', 'e'],
+        'ø' => vec!['o'],
+        'œ' => vec!['o', 'e'],
+        'ß' => vec!['s', 's'],
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => vec!['a'],
+        'ç' | 'č' | 'ć' => vec!['c'],
+        'ð' | 'ď' => vec!['d'],
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ě' => vec!['e'],
+        'ì' | 'í' | 'î' | 'ï' => vec!['i'],
+        'ñ' | 'ń' | 'ň' => vec!['n'],
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ō' => vec!['o'],
+        'ř' => vec!['r'],
+        'š' | 'ś' => vec!['s'],
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' => vec!['u'],
+        'ý' | 'ÿ' => vec!['y'],
+        'ž' | 'ź' | 'ż' => vec!['z'],
+        'þ' => vec!['t', 'h'],
+        _ => vec![ch],
+    }
+}
+
+// Primary weights: whitespace < digits < letters (alphabet order); punctuation
+// is ignored.
+fn weight(ch: char, locale: &str) -> Option<u32> {
+    if ch.is_whitespace() {
+        return Some(1);
+    }
+    if let Some(d) = ch.to_digit(10) {
+        return Some(10 + d);
+    }
+    if ch.is_ascii_lowercase() {
+        return Some(100 + (ch as u32 - 'a' as u32));
+    }
+    if let Some(pos) = extra_letters(locale).iter().position(|c| *c == ch) {
+        return Some(126 + pos as u32);
+    }
+    if ch.is_alphabetic() {
+        // letters outside the alphabet sort after it, by code point
+        return Some(1000 + ch as u32);
+    }
+    None
+}
+
+pub fn sort_key(input: &str, locale: &str) -> Vec<u32> {
+    let mut key = Vec::new();
+    let mut last_space = true;
+    for ch in input.trim().chars().flat_map(|c| c.to_lowercase()) {
+        for folded in fold(ch, locale) {
+            match weight(folded, locale) {
+                // collapse whitespace runs
+                Some(1) if last_space => {}
+                Some(w) => {
+                    last_space = w == 1;
+                    key.push(w);
+                }
+                None => {}
+            }
+        }
+    }
+    key
+}
+
+// Compare by primary key, then by lowercase text, then by raw text, so the order
+// is total and stable.
+pub fn compare(a: &str, b: &str, locale: &str) -> Ordering {
+    sort_key(a, locale)
+        .cmp(&sort_key(b, locale))
+        .then_with(|| a.to_lowercase().cmp(&b.to_lowercase()))
+        .then_with(|| a.cmp(b))
+}
+
+// Index group letter for a term: its first letter in the locale's alphabet,
+// uppercase (`Æble` is `Æ` in Danish, `A` in English). Terms starting with a
+// digit group under `0-9`; terms without letters or digits under `#`.
+pub fn initial(input: &str, locale: &str) -> String {
+    for ch in input.chars().flat_map(|c| c.to_lowercase()) {
+        let Some(first) = fold(ch, locale).into_iter().next() else {
+            continue;
+        };
+        match weight(first, locale) {
+            Some(w) if (10..20).contains(&w) => return "0-9".to_string(),
+            Some(w) if w >= 100 => return first.to_uppercase().collect(),
+            _ => {}
+        }
+    }
+    "#".to_string()
+}
@@ -0,0 +1,278 @@
+use anyhow::{Result, anyhow};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::path::Path;
+
+// Optional build configuration loaded from a TOML file passed with `--config`.
+// Every field has a default so an absent file behaves like today's build.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Config {
+    pub event: EventConfig,
+    pub status: StatusConfig,
+    pub ordering: OrderingConfig,
+    pub text: TextConfig,
+    pub content: ContentConfig,
+    pub index: IndexConfig,
+    pub keywords: KeywordsConfig,
+    pub epub: EpubConfig,
+    pub latex: LatexConfig,
+    pub output: OutputConfig,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct OutputConfig {
+    /// Formats typeset from the book data when `--format` is not given
+    /// (default `["typst"]`).
+    pub formats: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct LatexConfig {
+    /// Document class name, or a `.cls` file copied next to the book
+    pub class: String,
+    pub class_options: String,
+    /// File with the partner's own packages and macros, read in after ours
+    pub preamble: Option<String>,
+}
+
+impl Default for LatexConfig {
+    fn default() -> Self {
+        LatexConfig {
+            class: "book".to_string(),
+            class_options: "a5paper,11pt".to_string(),
+            preamble: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct EpubConfig {
+    /// Embed the template's Mari fonts (only where the font licence allows it)
+    pub embed_fonts: bool,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct KeywordsConfig {
+    /// Thesaurus TOML: canonical terms with aliases and per-locale translations.
+    pub thesaurus: Option<String>,
+}
+
+// Back-of-book indexes. The tag index is always built when abstracts have
+// keywords; entries collate by the booklet locale.
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct IndexConfig {
+    /// Author index (surname first)
+    pub authors: bool,
+    /// Affiliation index
+    pub affiliations: bool,
+    /// Group letter headings (A, B, ..., Æ, Ø, Å) in every index
+    pub letters: bool,
+}
+
+// What goes into an abstract's booklet when it has no text in that booklet's
+// language.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Untranslated {
+    /// Leave it out
+    #[default]
+    Omit,
+    /// Print its own text
+    Include,
+    /// Print its own text with an `untranslated_label` note
+    Mark,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ContentConfig {
+    /// Abstract languages accepted per booklet locale, in order of preference
+    /// (e.g. `en = ["en", "da"]`). A booklet without an entry takes only its own.
+    pub sources: HashMap<String, Vec<String>>,
+    pub untranslated: Untranslated,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct TextConfig {
+    /// Parse `*emph*`, `**strong**`, `~sub~`, `^sup^` and `- ` lists in abstract text.
+    pub markup: bool,
+    /// Keep bold/italic/underline/super-/subscript runs of xlsx abstract cells
+    /// (as markup; implies `markup`).
+    pub rich_text: bool,
+    /// Turn URLs, DOIs and email addresses into links in the PDF (off for
+    /// print-only editions).
+    pub linkify: bool,
+}
+
+impl Default for TextConfig {
+    fn default() -> Self {
+        TextConfig {
+            markup: false,
+            rich_text: false,
+            linkify: true,
+        }
+    }
+}
+
+impl TextConfig {
+    pub fn parses_markup(&self) -> bool {
+        self.markup || self.rich_text
+    }
+}
+
+// How sessions are ordered in the booklet.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionOrder {
+    /// Grouping-sheet order
+    #[default]
+    Sheet,
+    /// Alphabetical by title, collated per booklet locale
+    Title,
+}
+
+// How abstracts are ordered within a session.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortMode {
+    /// Grouping-sheet row order
+    #[default]
+    Sheet,
+    Title,
+    /// Presenting (first) author's surname
+    Author,
+    Id,
+    /// The id list in `[ordering.explicit]`; unlisted items follow in sheet order
+    Explicit,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct OrderingConfig {
+    pub default: SortMode,
+    /// Collation locale for `title` and `author` sorting.
+    pub locale: String,
+    /// Per-session sort mode keyed by session id or slug.
+    pub sessions: HashMap<String, SortMode>,
+    /// Explicit id order keyed by session id or slug.
+    pub explicit: HashMap<String, Vec<String>>,
+    /// Order of the sessions themselves in each booklet.
+    pub session_order: SessionOrder,
+}
+
+impl Default for OrderingConfig {
+    fn default() -> Self {
+        OrderingConfig {
+            default: SortMode::Sheet,
+            locale: "da".to_string(),
+            sessions: HashMap::new(),
+            explicit: HashMap::new(),
+            session_order: SessionOrder::Sheet,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct StatusConfig {
+    /// Submission statuses that go into the booklet. Abstracts without a status
+    /// (no status column) are always included.
+    pub include: Vec<String>,
+}
+
+impl Default for StatusConfig {
+    fn default() -> Self {
+        StatusConfig {
+            include: vec!["accepted".to_string()],
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct EventConfig {
+    pub id: String,
+    pub title: String,
+    pub acronym: String,
+    /// First conference day as `YYYY-MM-DD`.
+    pub start: String,
+    /// Local time of the first slot on each day (`HH:MM`).
+    pub day_start: String,
+    pub utc_offset: String,
+    pub time_zone: String,
+    pub slot_minutes: u32,
+    pub default_room: String,
+    pub url: Option<String>,
+    /// Per-session scheduling overrides keyed by session id or slug.
+    pub sessions: HashMap<String, SessionSlot>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SessionSlot {
+    pub day: Option<u32>,
+    pub room: Option<String>,
+    pub start: Option<String>,
+    pub slot_minutes: Option<u32>,
+}
+
+impl Default for EventConfig {
+    fn default() -> Self {
+        EventConfig {
+            id: "symposium-2026".to_string(),
+            title: "Interprofessional Education Symposium 2026".to_string(),
+            acronym: "symposium-2026".to_string(),
+            start: "2026-03-13".to_string(),
+            day_start: "09:00".to_string(),
+            utc_offset: "+01:00".to_string(),
+            time_zone: "Europe/Copenhagen".to_string(),
+            slot_minutes: 10,
+            default_room: "Main hall".to_string(),
+            url: None,
+            sessions: HashMap::new(),
+        }
+    }
+}
+
+pub fn load_config(path: Option<&str>) -> Result<Config> {
+    let Some(path) = path else {
+        return Ok(Config::default());
+    };
+    if !Path::new(path).exists() {
+        return Err(anyhow!("config file not found: {}", path));
+    }
+    let txt = read_to_string(path)?;
+    let mut config: Config =
+        toml::from_str(&txt).map_err(|e| anyhow!("Failed to parse config {}: {}", path, e))?;
+    config.resolve_paths(Path::new(path).parent().unwrap_or(Path::new("")));
+    Ok(config)
+}
+
+impl Config {
+    // Files the config names are relative to the config file, not to where the
+    // build runs.
+    fn resolve_paths(&mut self, dir: &Path) {
+        let resolve = |file: &mut String| {
+            if Path::new(file.as_str()).is_relative() {
+                *file = dir.join(file.as_str()).to_string_lossy().into_owned();
+            }
+        };
+        if self.latex.class.ends_with(".cls") {
+            resolve(&mut self.latex.class);
+        }
+        if let Some(preamble) = self.latex.preamble.as_mut() {
+            resolve(preamble);
+        }
+        if let Some(thesaurus) = self.keywords.thesaurus.as_mut() {
+            resolve(thesaurus);
+        }
+    }
+}
@@ -0,0 +1,429 @@
+use crate::booklet::{AbstractData, BookData, Booklet, Origin, SessionData};
+use crate::typst::markup::{Block, Inline};
+use crate::util::xml_escape;
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, create_dir_all, read_to_string};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use toml::value::Table;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+// Word documents for proofreading (`--format docx`) from the booklet, so the
+// committee reads the texts each locale's booklet prints, in its order, with
+// the sections found by `split_abstract_sections`. Sessions and abstracts use
+// Word's built-in heading styles (navigation pane, outline view); section
+// labels are bold runs. Every abstract carries a bookmark named after its id
+// and a line with its source row, so comments can be traced back.
+
+pub const DOCX_DIR: &str = "docx";
+
+// One document per booklet locale, or one per session and locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum DocxSplit {
+    Locale,
+    Session,
+}
+
+// The fixed package parts; `styles.xml` has a `{lang}` placeholder.
+pub const DOCX_TEMPLATE_DIR: &str = "templates/docx";
+
+pub fn write_docx(booklet: &Booklet, outdir: &str, split: DocxSplit) -> Result<()> {
+    for (book, origins) in booklet.editions.iter().zip(&booklet.origins) {
+        let mut documents: Vec<(PathBuf, String)> = Vec::new();
+        match split {
+            DocxSplit::Locale => {
+                let mut body = paragraph(
+                    Some("Title"),
+                    &[run(label(&book.labels, "title"), &Props::default())],
+                );
+                let mut bookmarks = Bookmarks::default();
+                for session in &book.sessions {
+                    body.push_str(&session_xml(session, &book.labels, origins, &mut bookmarks));
+                }
+                documents.push((book_path(outdir, book), body));
+            }
+            DocxSplit::Session => {
+                for (i, session) in book.sessions.iter().enumerate() {
+                    let mut bookmarks = Bookmarks::default();
+                    let body = session_xml(session, &book.labels, origins, &mut bookmarks);
+                    documents.push((session_path(outdir, &book.locale, i, session), body));
+                }
+            }
+        }
+        for (path, body) in documents {
+            write_package(&path, &book.locale, &body)
+                .with_context(|| format!("writing {}", path.display()))?;
+            tracing::info!("Wrote {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+pub fn write_docx_plan(
+    booklet: &Booklet,
+    outdir: &str,
+    split: DocxSplit,
+    plan: &mut crate::io::plan::Plan,
+) -> Result<()> {
+    use crate::io::plan::PlanAction;
+    for book in &booklet.editions {
+        match split {
+            DocxSplit::Locale => plan.push(PlanAction::WriteFile {
+                path: book_path(outdir, book),
+                summary: format!("Word document for proofreading ({})", book.locale),
+            }),
+            DocxSplit::Session => {
+                for (i, session) in book.sessions.iter().enumerate() {
+                    plan.push(PlanAction::WriteFile {
+                        path: session_path(outdir, &book.locale, i, session),
+                        summary: format!(
+                            "Word document for proofreading session '{}' ({})",
+                            session.title, book.locale
+                        ),
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// First output file of an edition, for the build record.
+pub fn first_docx_path(outdir: &str, book: &BookData, split: DocxSplit) -> Option<PathBuf> {
+    match split {
+        DocxSplit::Locale => Some(book_path(outdir, book)),
+        DocxSplit::Session => book
+            .sessions
+            .first()
+            .map(|session| session_path(outdir, &book.locale, 0, session)),
+    }
+}
+
+fn book_path(outdir: &str, book: &BookData) -> PathBuf {
+    Path::new(outdir)
+        .join(DOCX_DIR)
+        .join(crate::typst::book_file_name(
+            &book.locale,
+            book.partial.as_deref(),
+            "docx",
+        ))
+}
+
+fn session_path(outdir: &str, locale: &str, index: usize, session: &SessionData) -> PathBuf {
+    Path::new(outdir).join(DOCX_DIR).join(locale).join(format!(
+        "{:02}-{}.docx",
+        index + 1,
+        session.slug
+    ))
+}
+
+fn label<'a>(labels: &'a Table, key: &'a str) -> &'a str {
+    labels.get(key).and_then(|v| v.as_str()).unwrap_or(key)
+}
+
+fn session_xml(
+    session: &SessionData,
+    labels: &Table,
+    origins: &HashMap<String, Origin>,
+    bookmarks: &mut Bookmarks,
+) -> String {
+    let mut out = paragraph(Some("Heading1"), &[run(&session.title, &Props::default())]);
+    for abs in &session.abstracts {
+        out.push_str(&abstract_xml(
+            abs,
+            origins.get(&abs.label),
+            labels,
+            bookmarks,
+        ));
+    }
+    out
+}
+
+fn abstract_xml(
+    abs: &AbstractData,
+    origin: Option<&Origin>,
+    labels: &Table,
+    bookmarks: &mut Bookmarks,
+) -> String {
+    let (id, name) = bookmarks.next(&abs.id);
+    let mut out = format!(
+        "<w:p><w:pPr><w:pStyle w:val=\"Heading2\"/></w:pPr><w:bookmarkStart w:id=\"{}\" w:name=\"{}\"/>{}<w:bookmarkEnd w:id=\"{}\"/></w:p>",
+        id,
+        name,
+        run(&abs.title, &Props::lang(&abs.locale)),
+        id
+    );
+    // where the abstract came from, for tracing comments back to the workbook
+    let source = match origin.and_then(|o| o.source.as_ref()) {
+        Some(source) => format!("{} · {} row {}", abs.id, source.sheet, source.row),
+        None => abs.id.clone(),
+    };
+    out.push_str(&paragraph(
+        Some("SourceRef"),
+        &[run(&source, &Props::default())],
+    ));
+    if abs.untranslated {
+        out.push_str(&paragraph(
+            Some("SourceRef"),
+            &[run(
+                label(labels, "untranslated_label"),
+                &Props {
+                    italic: true,
+                    ..Props::default()
+                },
+            )],
+        ));
+    }
+    if !abs.authors.is_empty() {
+        out.push_str(&labelled(
+            labels,
+            "authors_label",
+            &[run(&abs.authors.join(", "), &Props::default())],
+        ));
+    }
+    if !abs.affiliations.is_empty() {
+        out.push_str(&labelled(
+            labels,
+            "affiliation_label",
+            &[run(&abs.affiliations.join("; "), &Props::default())],
+        ));
+    }
+    let text_lang = Props::lang(&abs.locale);
+    for section in &abs.sections {
+        out.push_str(&section_xml(&section.label, &section.body, &text_lang));
+    }
+    for (key, blocks) in [
+        ("take_home_label", &abs.take_home),
+        ("reference_label", &abs.reference),
+    ] {
+        if let Some(blocks) = blocks {
+            let mut runs = Vec::new();
+            for block in blocks {
+                match block {
+                    Block::Paragraph(items) => inlines(items, &text_lang, &mut runs),
+                    Block::List(items) => {
+                        for item in items {
+                            inlines(item, &text_lang, &mut runs);
+                        }
+                    }
+                }
+            }
+            out.push_str(&labelled(labels, key, &runs));
+        }
+    }
+    if !abs.tags.is_empty() {
+        out.push_str(&labelled(
+            labels,
+            "tags_label",
+            &[run(&abs.tags.join(", "), &Props::default())],
+        ));
+    }
+    out
+}
+
+// `Label: text` with the label in bold.
+fn labelled(labels: &Table, key: &str, runs: &[String]) -> String {
+    let mut all = vec![run(&format!("{}: ", label(labels, key)), &Props::bold())];
+    all.extend_from_slice(runs);
+    paragraph(None, &all)
+}
+
+// A section's paragraphs; the label leads the first one in bold.
+fn section_xml(label: &str, blocks: &[Block], props: &Props) -> String {
+    let mut lead = (!label.is_empty()).then(|| run(&format!("{}: ", label), &Props::bold()));
+    let mut out = String::new();
+    for block in blocks {
+        match block {
+            Block::Paragraph(items) => {
+                let mut runs: Vec<String> = lead.take().into_iter().collect();
+                inlines(items, props, &mut runs);
+                out.push_str(&paragraph(None, &runs));
+            }
+            Block::List(items) => {
+                if let Some(lead) = lead.take() {
+                    out.push_str(&paragraph(None, &[lead]));
+                }
+                for item in items {
+                    let mut runs = Vec::new();
+                    inlines(item, props, &mut runs);
+                    out.push_str(&format!(
+                        "<w:p><w:pPr><w:pStyle w:val=\"ListBullet\"/><w:numPr><w:ilvl w:val=\"0\"/><w:numId w:val=\"1\"/></w:numPr></w:pPr>{}</w:p>",
+                        runs.concat()
+                    ));
+                }
+            }
+        }
+    }
+    if let Some(lead) = lead {
+        out.push_str(&paragraph(None, &[lead]));
+    }
+    out
+}
+
+// The body tree's emphasis functions as run properties; links keep their text.
+fn inlines(items: &[Inline], props: &Props, out: &mut Vec<String>) {
+    for item in items {
+        let mut inner = props.clone();
+        let body = match item {
+            Inline::Text(text) => {
+                out.push(run(text, props));
+                continue;
+            }
+            Inline::Strong(body) => {
+                inner.bold = true;
+                body
+            }
+            Inline::Link { body, .. } => body,
+            Inline::Call { func, body } => {
+                match func.as_str() {
+                    "emph" => inner.italic = true,
+                    "underline" => inner.underline = true,
+                    "sub" => inner.vert_align = Some("subscript"),
+                    "super" => inner.vert_align = Some("superscript"),
+                    _ => {}
+                }
+                body
+            }
+        };
+        inlines(body, &inner, out);
+    }
+}
+
+// Run properties, written in the order the schema requires (Word rejects
+// documents with `w:rPr` children out of order).
+#[derive(Debug, Clone, Default)]
+struct Props {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    vert_align: Option<&'static str>,
+    lang: Option<String>,
+}
+
+impl Props {
+    fn bold() -> Self {
+        Props {
+            bold: true,
+            ..Props::default()
+        }
+    }
+
+    fn lang(locale: &str) -> Self {
+        Props {
+            lang: Some(locale.to_string()),
+            ..Props::default()
+        }
+    }
+
+    fn xml(&self) -> String {
+        let mut out = String::new();
+        if self.bold {
+            out.push_str("<w:b/>");
+        }
+        if self.italic {
+            out.push_str("<w:i/>");
+        }
+        if self.underline {
+            out.push_str("<w:u w:val=\"single\"/>");
+        }
+        if let Some(align) = self.vert_align {
+            out.push_str(&format!("<w:vertAlign w:val=\"{}\"/>", align));
+        }
+        if let Some(lang) = &self.lang {
+            out.push_str(&format!("<w:lang w:val=\"{}\"/>", xml_escape(lang)));
+        }
+        out
+    }
+}
+
+fn paragraph(style: Option<&str>, runs: &[String]) -> String {
+    match style {
+        Some(style) => format!(
+            "<w:p><w:pPr><w:pStyle w:val=\"{}\"/></w:pPr>{}</w:p>",
+            style,
+            runs.concat()
+        ),
+        None => format!("<w:p>{}</w:p>", runs.concat()),
+    }
+}
+
+// A run of text; line breaks become `<w:br/>`.
+fn run(text: &str, props: &Props) -> String {
+    let mut out = String::from("<w:r>");
+    let rpr = props.xml();
+    if !rpr.is_empty() {
+        out.push_str(&format!("<w:rPr>{}</w:rPr>", rpr));
+    }
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            out.push_str("<w:br/>");
+        }
+        out.push_str(&format!(
+            "<w:t xml:space=\"preserve\">{}</w:t>",
+            xml_escape(line.trim_end_matches('\r'))
+        ));
+    }
+    out.push_str("</w:r>");
+    out
+}
+
+// Bookmark ids and names. Word allows letters, digits and `_`, starting with a
+// letter, at most 40 characters; names stay unique within a document.
+#[derive(Default)]
+struct Bookmarks {
+    next_id: usize,
+    taken: HashSet<String>,
+}
+
+impl Bookmarks {
+    fn next(&mut self, abstract_id: &str) -> (usize, String) {
+        let clean: String = abstract_id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let base: String = format!("abs_{}", clean).chars().take(36).collect();
+        let mut name = base.clone();
+        let mut n = 1;
+        while !self.taken.insert(name.clone()) {
+            n += 1;
+            name = format!("{}_{}", base, n);
+        }
+        self.next_id += 1;
+        (self.next_id, name)
+    }
+}
+
+fn write_package(path: &Path, booklet: &str, body: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        create_dir_all(dir)?;
+    }
+    let document = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<w:document xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\"><w:body>{}<w:sectPr><w:pgSz w:w=\"11906\" w:h=\"16838\"/><w:pgMar w:top=\"1440\" w:right=\"1440\" w:bottom=\"1440\" w:left=\"1440\" w:header=\"708\" w:footer=\"708\" w:gutter=\"0\"/></w:sectPr></w:body></w:document>\n",
+        body
+    );
+    let part = |name: &str| {
+        let file = Path::new(DOCX_TEMPLATE_DIR).join(name);
+        read_to_string(&file).with_context(|| format!("reading {}", file.display()))
+    };
+    let options = SimpleFileOptions::default();
+    let mut zip = ZipWriter::new(File::create(path)?);
+    for (name, content) in [
+        ("[Content_Types].xml", part("content_types.xml")?),
+        ("_rels/.rels", part("rels.xml")?),
+        ("word/_rels/document.xml.rels", part("document_rels.xml")?),
+        // spelling and grammar in the booklet's language
+        (
+            "word/styles.xml",
+            part("styles.xml")?.replace("{lang}", &xml_escape(booklet)),
+        ),
+        ("word/numbering.xml", part("numbering.xml")?),
+        ("word/document.xml", document),
+    ] {
+        zip.start_file(name, options)?;
+        zip.write_all(content.as_bytes())?;
+    }
+    zip.finish()?;
+    Ok(())
+}
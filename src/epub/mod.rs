@@ -0,0 +1,430 @@
+use crate::booklet::{AbstractData, BookData, IndexEntry, TagRefData};
+use crate::config::EpubConfig;
+use crate::html::{anchor, markup};
+use crate::typst::template;
+use crate::typst::template::Markup;
+use anyhow::{Context, Result, anyhow};
+use minijinja::value::{Serde, Value};
+use minijinja::{Environment, context, path_loader};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use toml::value::Table;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+// EPUB 3 booklet per locale (`symposium-2026_<locale>.epub`) for phones and
+// e-readers, from the same book data as the Typst booklet (see
+// `booklet::Booklet`). One XHTML document per session, a navigation document
+// with the outline of the PDF (sessions, then their abstracts) and the
+// indexes, which link to the abstracts and back. The pages are Jinja templates
+// in `templates/epub/`, like the HTML site's; the cover image comes from the
+// booklet template's `images/`, and the Mari fonts are embedded only with
+// `[epub] embed_fonts` since their licence may not allow it.
+
+pub const EPUB_TEMPLATE_DIR: &str = "templates/epub";
+// Copied into the package as it is.
+const CONTAINER: &str = "container.xml";
+// Candidate cover images in the template's `images/`, with their media type.
+const COVERS: [(&str, &str); 3] = [
+    ("cover.jpg", "image/jpeg"),
+    ("cover.jpeg", "image/jpeg"),
+    ("cover.png", "image/png"),
+];
+// Brand font files taken from the template's font directory.
+const FONTS: [&str; 2] = ["Mari Book.otf", "Mari Bold.otf"];
+
+#[derive(Debug, Serialize)]
+struct EpubContext<'a> {
+    locale: &'a str,
+    title: String,
+    identifier: String,
+    modified: String,
+    labels: &'a Table,
+    partial: Option<&'a str>,
+    cover: Option<Resource>,
+    fonts: Vec<Resource>,
+    sessions: Vec<SessionDoc>,
+    tag_index: Vec<IndexGroup>,
+    author_index: Vec<IndexGroup>,
+}
+
+// A file in the package other than the pages.
+#[derive(Debug, Serialize)]
+struct Resource {
+    href: String,
+    media_type: &'static str,
+    #[serde(skip)]
+    source: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+struct SessionDoc {
+    title: String,
+    file: String,
+    abstracts: Vec<AbstractDoc>,
+}
+
+#[derive(Debug, Serialize)]
+struct AbstractDoc {
+    /// XHTML id, the Typst label (`abs-a1`).
+    id: String,
+    locale: String,
+    untranslated: bool,
+    title: String,
+    authors: Vec<String>,
+    affiliations: Vec<String>,
+    sections: Vec<SectionDoc>,
+    take_home: Option<Markup>,
+    reference: Option<Markup>,
+    tags: Vec<IndexLink>,
+}
+
+#[derive(Debug, Serialize)]
+struct SectionDoc {
+    label: String,
+    body: Markup,
+}
+
+#[derive(Debug, Serialize)]
+struct IndexLink {
+    name: String,
+    href: String,
+}
+
+// Index entries under one letter heading (no heading without `[index] letters`).
+#[derive(Debug, Serialize)]
+struct IndexGroup {
+    letter: String,
+    entries: Vec<IndexDoc>,
+}
+
+#[derive(Debug, Serialize)]
+struct IndexDoc {
+    name: String,
+    id: String,
+    abstracts: Vec<IndexLink>,
+}
+
+// The author index only with `[index] authors` (`authors`).
+pub fn write_epubs(
+    outdir: &str,
+    books: &[BookData],
+    template: &Option<String>,
+    authors: bool,
+    config: &EpubConfig,
+) -> Result<()> {
+    let env = environment()?;
+    let modified = modified()?;
+    for book in books {
+        let ctx = epub_context(book, template, authors, config, &modified);
+        let path = Path::new(outdir).join(crate::typst::book_file_name(
+            &book.locale,
+            book.partial.as_deref(),
+            "epub",
+        ));
+        write_package(&env, &ctx, &path).with_context(|| format!("writing {}", path.display()))?;
+        tracing::info!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+// Dry-run plan entries for the EPUB files.
+pub fn write_epub_plan(
+    outdir: &str,
+    locales_csv: &str,
+    partial: Option<&str>,
+    plan: &mut crate::io::plan::Plan,
+) -> Result<()> {
+    for locale in crate::util::locales(locales_csv) {
+        plan.push(crate::io::plan::PlanAction::WriteFile {
+            path: Path::new(outdir).join(crate::typst::book_file_name(locale, partial, "epub")),
+            summary: format!("EPUB booklet for {}", locale),
+        });
+    }
+    Ok(())
+}
+
+fn environment() -> Result<Environment<'static>> {
+    let mut env = Environment::new();
+    crate::html::configure(&mut env)?;
+    env.set_loader(path_loader(EPUB_TEMPLATE_DIR));
+    Ok(env)
+}
+
+fn render(env: &Environment, name: &str, ctx: Value) -> Result<String> {
+    env.get_template(name)
+        .map_err(|e| anyhow!("template {}/{}: {:#}", EPUB_TEMPLATE_DIR, name, e))?
+        .render(ctx)
+        .map_err(|e| anyhow!("rendering {}/{}: {:#}", EPUB_TEMPLATE_DIR, name, e))
+}
+
+// `dcterms:modified`: `SOURCE_DATE_EPOCH` for reproducible files, else now.
+fn modified() -> Result<String> {
+    let secs = match std::env::var("SOURCE_DATE_EPOCH") {
+        Ok(value) => value
+            .trim()
+            .parse::<i64>()
+            .map_err(|_| anyhow!("SOURCE_DATE_EPOCH is not a number: {}", value))?,
+        Err(_) => std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_secs() as i64,
+    };
+    let (y, m, d) = crate::util::civil_from_days(secs.div_euclid(86400));
+    let time = secs.rem_euclid(86400);
+    Ok(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        y,
+        m,
+        d,
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    ))
+}
+
+fn epub_context<'a>(
+    book: &'a BookData,
+    template: &Option<String>,
+    authors: bool,
+    config: &EpubConfig,
+    modified: &str,
+) -> EpubContext<'a> {
+    let title = book
+        .labels
+        .get("title")
+        .and_then(|v| v.as_str())
+        .filter(|t| !t.is_empty())
+        .unwrap_or(&book.event.title)
+        .to_string();
+    let images = template::template_location(template).0.join("images");
+    let cover = COVERS
+        .iter()
+        .find(|(name, _)| images.join(name).is_file())
+        .map(|(name, media_type)| Resource {
+            href: format!("images/{}", name),
+            media_type,
+            source: images.join(name),
+        });
+    let font_dir = crate::typst::font_dir(template).join("TTF");
+    let fonts = if config.embed_fonts {
+        FONTS
+            .iter()
+            .filter(|font| font_dir.join(font).is_file())
+            .map(|font| Resource {
+                // no spaces in package paths
+                href: format!("fonts/{}", font.replace(' ', "-")),
+                media_type: "font/otf",
+                source: font_dir.join(font),
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let tag_ids = index_ids("tag", book.tag_index.iter().map(|t| t.tag.as_str()));
+    let author_ids = index_ids("author", book.author_index.iter().map(|e| e.name.as_str()));
+
+    // Typst label -> position in the book, for the index entries
+    let mut files: HashMap<&str, String> = HashMap::new();
+    let sessions = book
+        .sessions
+        .iter()
+        .enumerate()
+        .map(|(i, session)| {
+            let file = format!("session-{:02}.xhtml", i + 1);
+            for abs in &session.abstracts {
+                files.insert(&abs.label, format!("{}#{}", file, abs.label));
+            }
+            SessionDoc {
+                title: session.title.clone(),
+                abstracts: session
+                    .abstracts
+                    .iter()
+                    .map(|abs| abstract_doc(abs, &tag_ids))
+                    .collect(),
+                file,
+            }
+        })
+        .collect();
+    let links = |refs: &[TagRefData]| {
+        refs.iter()
+            .map(|r| IndexLink {
+                name: r.title.clone(),
+                href: files.get(r.label.as_str()).cloned().unwrap_or_default(),
+            })
+            .collect()
+    };
+    let tags: Vec<(String, String, Vec<IndexLink>)> = book
+        .tag_index
+        .iter()
+        .map(|t| (t.tag.clone(), t.letter.clone(), links(&t.abstracts)))
+        .collect();
+    let names = |entries: &[IndexEntry]| {
+        entries
+            .iter()
+            .map(|e| (e.name.clone(), e.letter.clone(), links(&e.abstracts)))
+            .collect::<Vec<_>>()
+    };
+    EpubContext {
+        locale: &book.locale,
+        identifier: format!(
+            "urn:uuid:{}",
+            crate::util::stable_guid(&[
+                "epub",
+                &book.event.id,
+                &book.locale,
+                book.partial.as_deref().unwrap_or_default(),
+            ])
+        ),
+        title,
+        modified: modified.to_string(),
+        labels: &book.labels,
+        partial: book.partial.as_deref(),
+        cover,
+        fonts,
+        sessions,
+        tag_index: index_groups(tags, &tag_ids, book.index_letters),
+        author_index: if authors {
+            index_groups(names(&book.author_index), &author_ids, book.index_letters)
+        } else {
+            Vec::new()
+        },
+    }
+}
+
+fn abstract_doc(abs: &AbstractData, tag_ids: &HashMap<String, String>) -> AbstractDoc {
+    AbstractDoc {
+        id: abs.label.clone(),
+        locale: abs.locale.clone(),
+        untranslated: abs.untranslated,
+        title: if abs.title.is_empty() {
+            abs.id.clone()
+        } else {
+            abs.title.clone()
+        },
+        authors: abs.authors.clone(),
+        affiliations: abs.affiliations.clone(),
+        sections: abs
+            .sections
+            .iter()
+            .map(|s| SectionDoc {
+                label: s.label.clone(),
+                body: Markup::new(markup::blocks(&s.body)),
+            })
+            .collect(),
+        take_home: abs
+            .take_home
+            .as_deref()
+            .map(|b| Markup::new(markup::inline_blocks(b))),
+        reference: abs
+            .reference
+            .as_deref()
+            .map(|b| Markup::new(markup::inline_blocks(b))),
+        // back to the tag index entries
+        tags: abs
+            .tags
+            .iter()
+            .filter_map(|tag| {
+                Some(IndexLink {
+                    name: tag.clone(),
+                    href: format!("tags.xhtml#{}", tag_ids.get(tag)?),
+                })
+            })
+            .collect(),
+    }
+}
+
+// Entry name -> XHTML id. XML ids must not start with a digit, so every id gets
+// a prefix; names that slug the same get a number.
+fn index_ids<'a>(kind: &str, names: impl Iterator<Item = &'a str>) -> HashMap<String, String> {
+    let mut ids = HashMap::new();
+    let mut taken = HashSet::new();
+    for name in names {
+        let base = format!("{}-{}", kind, anchor(name));
+        let mut id = base.clone();
+        let mut n = 1;
+        while !taken.insert(id.clone()) {
+            n += 1;
+            id = format!("{}-{}", base, n);
+        }
+        ids.insert(name.to_string(), id);
+    }
+    ids
+}
+
+fn index_groups(
+    entries: Vec<(String, String, Vec<IndexLink>)>,
+    ids: &HashMap<String, String>,
+    letters: bool,
+) -> Vec<IndexGroup> {
+    let mut groups: Vec<IndexGroup> = Vec::new();
+    for (name, letter, abstracts) in entries {
+        let letter = if letters { letter } else { String::new() };
+        if groups.last().is_none_or(|g| g.letter != letter) {
+            groups.push(IndexGroup {
+                letter,
+                entries: Vec::new(),
+            });
+        }
+        groups.last_mut().unwrap().entries.push(IndexDoc {
+            id: ids.get(&name).cloned().unwrap_or_default(),
+            name,
+            abstracts,
+        });
+    }
+    groups
+}
+
+// The OCF container: `mimetype` first and stored, then the package.
+fn write_package(env: &Environment, ctx: &EpubContext, path: &Path) -> Result<()> {
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(File::create(path)?);
+    zip.start_file("mimetype", stored)?;
+    zip.write_all(b"application/epub+zip")?;
+    let container = Path::new(EPUB_TEMPLATE_DIR).join(CONTAINER);
+    zip.start_file("META-INF/container.xml", deflated)?;
+    zip.write_all(
+        &fs::read(&container).with_context(|| format!("reading {}", container.display()))?,
+    )?;
+
+    let book = Value::from(Serde(ctx));
+    let mut add = |name: &str, text: String| -> Result<()> {
+        zip.start_file(format!("OEBPS/{}", name), deflated)?;
+        zip.write_all(text.as_bytes())?;
+        Ok(())
+    };
+    add("content.opf", render(env, "content.opf", book.clone())?)?;
+    add("nav.xhtml", render(env, "nav.xhtml", book.clone())?)?;
+    add("title.xhtml", render(env, "title.xhtml", book.clone())?)?;
+    add("style.css", render(env, "style.css", book.clone())?)?;
+    for session in &ctx.sessions {
+        let page = context! { session => Value::from(Serde(session)), ..book.clone() };
+        add(&session.file, render(env, "session.xhtml", page)?)?;
+    }
+    for (file, heading, groups) in [
+        ("tags.xhtml", "tag_index_label", &ctx.tag_index),
+        ("authors.xhtml", "author_index_label", &ctx.author_index),
+    ] {
+        if groups.is_empty() {
+            continue;
+        }
+        let page = context! {
+            heading => ctx.labels.get(heading).and_then(|v| v.as_str()),
+            groups => Value::from(Serde(groups)),
+            ..book.clone()
+        };
+        add(file, render(env, "index.xhtml", page)?)?;
+    }
+
+    for resource in ctx.cover.iter().chain(&ctx.fonts) {
+        // images and fonts are compressed already
+        zip.start_file(format!("OEBPS/{}", resource.href), stored)?;
+        zip.write_all(&fs::read(&resource.source)?)?;
+    }
+    zip.finish()?;
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use crate::io::markdown::session_slug;
+use crate::keywords;
+use crate::model::{Abstract, Session};
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use slug::slugify;
+use std::collections::{HashMap, HashSet};
+
+// Canonical submission statuses. Source values (English, Danish, and the names
+// used by EasyChair/ConfTool/Pretalx) are mapped onto these; anything else is
+// kept lowercased so it can still be listed in the config.
+pub const STATUS_ACCEPTED: &str = "accepted";
+pub const STATUS_REJECTED: &str = "rejected";
+pub const STATUS_WITHDRAWN: &str = "withdrawn";
+pub const STATUS_PENDING: &str = "pending";
+
+pub fn normalize_status(raw: &str) -> String {
+    let low = raw.trim().to_lowercase();
+    let starts = |prefixes: &[&str]| prefixes.iter().any(|p| low.starts_with(p));
+    // check negated forms before their positive stems ("ikke optaget")
+    if starts(&[
+        "reject",
+        "afvis",
+        "afvist",
+        "ikke optaget",
+        "ikke accepteret",
+        "declin",
+    ]) {
+        STATUS_REJECTED.to_string()
+    } else if starts(&[
+        "withdraw",
+        "trukket",
+        "tilbagetrukket",
+        "annuller",
+        "cancel",
+        "deleted",
+        "slettet",
+    ]) {
+        STATUS_WITHDRAWN.to_string()
+    } else if starts(&["accept", "godkend", "optaget", "confirm", "bekræft"]) {
+        STATUS_ACCEPTED.to_string()
+    } else if starts(&[
+        "pending",
+        "submitted",
+        "afvent",
+        "indsendt",
+        "under review",
+        "til bedømmelse",
+    ]) {
+        STATUS_PENDING.to_string()
+    } else {
+        low
+    }
+}
+
+pub fn normalize_statuses(abstracts: &mut HashMap<String, Abstract>) {
+    for a in abstracts.values_mut() {
+        a.status = a
+            .status
+            .as_deref()
+            .map(normalize_status)
+            .filter(|s| !s.is_empty());
+    }
+}
+
+// Abstracts without a status are always included; the others must have one of
+// the configured statuses.
+pub fn status_included(abs: &Abstract, include: &[String]) -> bool {
+    match &abs.status {
+        None => true,
+        Some(status) => include.iter().any(|i| normalize_status(i) == *status),
+    }
+}
+
+// Drop abstracts whose status is not included, along with the session items
+// that reference them. Sessions left without items are removed.
+pub fn apply_status_filter(
+    abstracts: &mut HashMap<String, Abstract>,
+    sessions: &mut Vec<Session>,
+    include: &[String],
+) -> usize {
+    let before = abstracts.len();
+    abstracts.retain(|_, a| status_included(a, include));
+    for s in sessions.iter_mut() {
+        s.items.retain(|i| abstracts.contains_key(&i.id));
+    }
+    sessions.retain(|s| !s.items.is_empty());
+    before - abstracts.len()
+}
+
+// A proofing subset of the booklet: only the named sessions, ids and/or keywords.
+// All given criteria must hold. An empty subset is the full booklet.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Subset {
+    pub sessions: Vec<String>,
+    pub ids: Vec<String>,
+    pub keywords: Vec<String>,
+}
+
+impl Subset {
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty() && self.ids.is_empty() && self.keywords.is_empty()
+    }
+
+    // Short file-name friendly description, e.g. `session-posters_ids-a1-a2`.
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        for (name, values) in [
+            ("session", &self.sessions),
+            ("ids", &self.ids),
+            ("keyword", &self.keywords),
+        ] {
+            if !values.is_empty() {
+                parts.push(format!("{}-{}", name, slugify(values.join("-"))));
+            }
+        }
+        parts.join("_")
+    }
+
+    fn session_matches(&self, session: &Session) -> bool {
+        if self.sessions.is_empty() {
+            return true;
+        }
+        let slug = session_slug(session);
+        self.sessions.iter().any(|want| {
+            let want = want.trim();
+            want == session.id || want.eq_ignore_ascii_case(&session.title) || want == slug
+        })
+    }
+
+    fn abstract_matches(&self, abs: &Abstract) -> bool {
+        if !self.ids.is_empty() && !self.ids.iter().any(|id| id.trim() == abs.id) {
+            return false;
+        }
+        if self.keywords.is_empty() {
+            return true;
+        }
+        let keys: Vec<String> = abs
+            .keywords
+            .iter()
+            .flat_map(|k| keywords::split(k))
+            .map(|k| keywords::key(&k))
+            .collect();
+        self.keywords
+            .iter()
+            .any(|k| keys.contains(&keywords::key(k)))
+    }
+
+    // Reduce sessions (and the abstracts they reference) to the subset. Fails when
+    // nothing is left, so a typo never produces an empty "booklet".
+    pub fn apply(
+        &self,
+        abstracts: &mut HashMap<String, Abstract>,
+        sessions: &mut Vec<Session>,
+    ) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        sessions.retain(|s| self.session_matches(s));
+        for s in sessions.iter_mut() {
+            s.items.retain(|i| {
+                abstracts
+                    .get(&i.id)
+                    .map(|a| self.abstract_matches(a))
+                    .unwrap_or(false)
+            });
+        }
+        sessions.retain(|s| !s.items.is_empty());
+        if sessions.is_empty() {
+            return Err(anyhow!(
+                "No abstracts match the subset filter ({})",
+                self.label()
+            ));
+        }
+        let keep: HashSet<&str> = sessions
+            .iter()
+            .flat_map(|s| s.items.iter().map(|i| i.id.as_str()))
+            .collect();
+        abstracts.retain(|id, _| keep.contains(id.as_str()));
+        Ok(())
+    }
+}
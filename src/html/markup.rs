@@ -0,0 +1,128 @@
+use crate::typst::markup::{Block, Inline, Target};
+
+// The generated body tree (see `typst::markup`) as HTML. Text is escaped; the
+// Typst functions the tree uses map to their HTML elements.
+
+pub fn escape(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for ch in input.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+pub fn blocks(blocks: &[Block]) -> String {
+    let mut out = String::new();
+    for block in blocks {
+        match block {
+            Block::Paragraph(body) => {
+                if body.is_empty() {
+                    continue;
+                }
+                out.push_str("<p>");
+                inlines(body, &mut out);
+                out.push_str("</p>\n");
+            }
+            Block::List(items) => {
+                out.push_str("<ul>\n");
+                for item in items {
+                    out.push_str("<li>");
+                    inlines(item, &mut out);
+                    out.push_str("</li>\n");
+                }
+                out.push_str("</ul>\n");
+            }
+        }
+    }
+    out
+}
+
+// Inline content only, for fields printed after a label (take-home, reference).
+pub fn inline_blocks(blocks: &[Block]) -> String {
+    let mut out = String::new();
+    for (i, block) in blocks.iter().enumerate() {
+        if i > 0 {
+            out.push_str("<br />");
+        }
+        match block {
+            Block::Paragraph(body) => inlines(body, &mut out),
+            Block::List(items) => {
+                for (j, item) in items.iter().enumerate() {
+                    if j > 0 {
+                        out.push_str("<br />");
+                    }
+                    out.push_str("– ");
+                    inlines(item, &mut out);
+                }
+            }
+        }
+    }
+    out
+}
+
+// Text only, for the search index; without the break hints of long links.
+pub fn plain(blocks: &[Block]) -> String {
+    fn text(items: &[Inline], out: &mut String) {
+        for item in items {
+            match item {
+                Inline::Text(s) => out.push_str(&s.replace('\u{200B}', "")),
+                Inline::Strong(body) | Inline::Link { body, .. } | Inline::Call { body, .. } => {
+                    text(body, out)
+                }
+            }
+        }
+    }
+    let mut parts = Vec::new();
+    for block in blocks {
+        match block {
+            Block::Paragraph(body) => {
+                let mut out = String::new();
+                text(body, &mut out);
+                parts.push(out);
+            }
+            Block::List(items) => {
+                for item in items {
+                    let mut out = String::new();
+                    text(item, &mut out);
+                    parts.push(out);
+                }
+            }
+        }
+    }
+    parts.join("\n")
+}
+
+fn inlines(items: &[Inline], out: &mut String) {
+    for item in items {
+        match item {
+            Inline::Text(s) => out.push_str(&escape(s).replace('\n', "<br />")),
+            Inline::Strong(body) => wrap("strong", body, out),
+            Inline::Link { target, body } => {
+                let Target::Url(url) = target;
+                out.push_str(&format!("<a href=\"{}\">", escape(url)));
+                inlines(body, out);
+                out.push_str("</a>");
+            }
+            Inline::Call { func, body } => match func.as_str() {
+                "emph" => wrap("em", body, out),
+                "underline" => wrap("u", body, out),
+                "sub" => wrap("sub", body, out),
+                "super" => wrap("sup", body, out),
+                _ => inlines(body, out),
+            },
+        }
+    }
+}
+
+fn wrap(tag: &str, body: &[Inline], out: &mut String) {
+    out.push_str(&format!("<{}>", tag));
+    inlines(body, out);
+    out.push_str(&format!("</{}>", tag));
+}
@@ -0,0 +1,384 @@
+use crate::booklet::{AbstractData, BookData, EventData, IndexEntry, TagRefData};
+use crate::typst::locale;
+use crate::typst::template::Markup;
+use anyhow::{Result, anyhow};
+use minijinja::syntax::SyntaxConfig;
+use minijinja::value::{Serde, Value, merge_maps};
+use minijinja::{Environment, context, path_loader};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::{self, create_dir_all};
+use std::path::{Path, PathBuf};
+use toml::value::Table;
+
+pub mod markup;
+
+// Static HTML site of the programme in `output/html/`: per locale an index of
+// the sessions, one page per abstract, tag and author index pages and
+// `search.json` for the client-side search. Built from the same book data as
+// the Typst booklet (see `booklet::Booklet`), so both show the same texts, order
+// and tags. Pages are Jinja templates in `templates/html` with HTML
+// auto-escaping; its `assets/` (style with the Region H design tokens, search
+// script) and the brand fonts are copied next to the pages, so the site needs
+// nothing from the network.
+
+pub const SITE_TEMPLATE_DIR: &str = "templates/html";
+pub const SITE_DIR: &str = "html";
+const ASSETS: &str = "assets";
+// Brand font files copied from the template's font directory when present.
+const FONTS: [&str; 2] = ["Mari Book.otf", "Mari Bold.otf"];
+
+#[derive(Debug, Serialize)]
+struct SiteContext<'a> {
+    locale: &'a str,
+    /// Every locale of the site, for the language links.
+    locales: &'a [String],
+    event: &'a EventData,
+    labels: &'a Table,
+    partial: Option<&'a str>,
+    sessions: Vec<SessionPage>,
+    tag_index: Vec<IndexPage>,
+    author_index: Vec<IndexPage>,
+}
+
+#[derive(Debug, Serialize)]
+struct SessionPage {
+    title: String,
+    slug: String,
+    abstracts: Vec<AbstractPage>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AbstractPage {
+    id: String,
+    /// Page relative to the locale directory, e.g. `abstracts/a1.html`.
+    href: String,
+    locale: String,
+    untranslated: bool,
+    title: String,
+    authors: Vec<String>,
+    affiliations: Vec<String>,
+    sections: Vec<SectionPage>,
+    take_home: Option<Markup>,
+    reference: Option<Markup>,
+    tags: Vec<TagLink>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SectionPage {
+    label: String,
+    body: Markup,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TagLink {
+    tag: String,
+    /// Anchor on the tag index page.
+    anchor: String,
+}
+
+#[derive(Debug, Serialize)]
+struct IndexPage {
+    name: String,
+    letter: String,
+    anchor: String,
+    abstracts: Vec<Link>,
+}
+
+#[derive(Debug, Serialize)]
+struct Link {
+    title: String,
+    href: String,
+}
+
+// One entry of `search.json`.
+#[derive(Debug, Serialize)]
+struct SearchEntry<'a> {
+    title: &'a str,
+    authors: &'a [String],
+    session: &'a str,
+    tags: &'a [String],
+    text: String,
+    href: &'a str,
+}
+
+// The site always has an author index, whatever `[index] authors` says.
+pub fn write_site(outdir: &str, books: &[BookData], template: &Option<String>) -> Result<()> {
+    let locales: Vec<String> = books.iter().map(|b| b.locale.clone()).collect();
+
+    let site_dir = Path::new(outdir).join(SITE_DIR);
+    create_dir_all(&site_dir)?;
+    copy_tree(
+        &Path::new(SITE_TEMPLATE_DIR).join(ASSETS),
+        &site_dir.join(ASSETS),
+    )?;
+    let font_dir = crate::typst::font_dir(template).join("TTF");
+    for font in FONTS {
+        if font_dir.join(font).is_file() {
+            create_dir_all(site_dir.join(ASSETS).join("fonts"))?;
+            fs::copy(
+                font_dir.join(font),
+                site_dir.join(ASSETS).join("fonts").join(font),
+            )?;
+        }
+    }
+
+    let env = environment()?;
+    render(
+        &env,
+        "languages.html",
+        context! { locales => &locales, labels => books.first().map(|b| Value::from(Serde(&b.labels))), assets => "assets/" },
+        &site_dir.join("index.html"),
+    )?;
+    for book in books {
+        let ctx = site_context(book, &locales);
+        let dir = site_dir.join(&book.locale);
+        create_dir_all(dir.join("abstracts"))?;
+        let page = |root: &str| {
+            merge_maps([
+                context! { root => root, assets => format!("{}../{}/", root, ASSETS) },
+                Value::from(Serde(&ctx)),
+            ])
+        };
+        render(&env, "index.html", page(""), &dir.join("index.html"))?;
+        render(&env, "tags.html", page(""), &dir.join("tags.html"))?;
+        render(&env, "authors.html", page(""), &dir.join("authors.html"))?;
+        for session in &ctx.sessions {
+            for abs in &session.abstracts {
+                let values = merge_maps([
+                    context! {
+                        abstract => Value::from(Serde(abs)),
+                        session => context! { title => &session.title, slug => &session.slug },
+                    },
+                    page("../"),
+                ]);
+                render(&env, "abstract.html", values, &dir.join(&abs.href))?;
+            }
+        }
+        write_search_index(book, &ctx, &dir)?;
+    }
+    Ok(())
+}
+
+// Dry-run plan entries for the site.
+pub fn write_site_plan(
+    outdir: &str,
+    locales_csv: &str,
+    plan: &mut crate::io::plan::Plan,
+) -> Result<()> {
+    use crate::io::plan::PlanAction;
+    let site_dir = Path::new(outdir).join(SITE_DIR);
+    plan.push(PlanAction::CreateDir {
+        path: site_dir.clone(),
+    });
+    for locale in crate::util::locales(locales_csv) {
+        plan.push(PlanAction::WriteFile {
+            path: site_dir.join(locale).join("index.html"),
+            summary: format!(
+                "HTML site for {} (abstract, tag and author pages, search.json)",
+                locale
+            ),
+        });
+    }
+    Ok(())
+}
+
+fn environment() -> Result<Environment<'static>> {
+    let mut env = Environment::new();
+    configure(&mut env)?;
+    env.set_loader(path_loader(SITE_TEMPLATE_DIR));
+    Ok(env)
+}
+
+// Block trimming and escaping shared with the EPUB pages.
+pub fn configure(env: &mut Environment) -> Result<()> {
+    env.set_syntax(
+        SyntaxConfig::builder()
+            .trim_blocks(true)
+            .lstrip_blocks(true)
+            .build()
+            .map_err(|e| anyhow!("template syntax: {}", e))?,
+    );
+    // like minijinja's HTML escaping, but URLs keep their slashes
+    env.set_formatter(|out, _state, value| {
+        if value.is_undefined() || value.is_none() {
+            return Ok(());
+        }
+        if value.is_safe() {
+            return out
+                .write_str(value.as_str().unwrap_or_default())
+                .map_err(Into::into);
+        }
+        out.write_str(&markup::escape(&value.to_string()))
+            .map_err(Into::into)
+    });
+    Ok(())
+}
+
+fn render(env: &Environment, name: &str, ctx: Value, path: &Path) -> Result<()> {
+    let tmpl = env
+        .get_template(name)
+        .map_err(|e| anyhow!("template {}/{}: {:#}", SITE_TEMPLATE_DIR, name, e))?;
+    let html = tmpl
+        .render(ctx)
+        .map_err(|e| anyhow!("rendering {}/{}: {:#}", SITE_TEMPLATE_DIR, name, e))?;
+    fs::write(path, html)?;
+    Ok(())
+}
+
+// Anchor for a tag or index name: `#patient_safety` -> `patient-safety`.
+pub fn anchor(name: &str) -> String {
+    let slug = slug::slugify(name);
+    if slug.is_empty() {
+        "entry".to_string()
+    } else {
+        slug
+    }
+}
+
+fn site_context<'a>(book: &'a BookData, locales: &'a [String]) -> SiteContext<'a> {
+    // Typst label -> page, for the index entries
+    let mut pages: HashMap<&str, String> = HashMap::new();
+    let sessions = book
+        .sessions
+        .iter()
+        .map(|session| SessionPage {
+            title: session.title.clone(),
+            slug: session.slug.clone(),
+            abstracts: session
+                .abstracts
+                .iter()
+                .map(|abs| {
+                    let page = abstract_page(abs);
+                    pages.insert(&abs.label, page.href.clone());
+                    page
+                })
+                .collect(),
+        })
+        .collect();
+    let links = |refs: &[TagRefData]| {
+        refs.iter()
+            .map(|r| Link {
+                title: r.title.clone(),
+                href: pages.get(r.label.as_str()).cloned().unwrap_or_default(),
+            })
+            .collect()
+    };
+    let tag_index = book
+        .tag_index
+        .iter()
+        .map(|entry| IndexPage {
+            anchor: anchor(&entry.tag),
+            name: entry.tag.clone(),
+            letter: entry.letter.clone(),
+            abstracts: links(&entry.abstracts),
+        })
+        .collect();
+    let names = |entries: &[IndexEntry]| {
+        entries
+            .iter()
+            .map(|entry| IndexPage {
+                anchor: anchor(&entry.name),
+                name: entry.name.clone(),
+                letter: entry.letter.clone(),
+                abstracts: links(&entry.abstracts),
+            })
+            .collect()
+    };
+    SiteContext {
+        locale: &book.locale,
+        locales,
+        event: &book.event,
+        labels: &book.labels,
+        partial: book.partial.as_deref(),
+        sessions,
+        tag_index,
+        author_index: names(&book.author_index),
+    }
+}
+
+fn abstract_page(abs: &AbstractData) -> AbstractPage {
+    let name = abs.label.strip_prefix("abs-").unwrap_or(&abs.label);
+    AbstractPage {
+        id: abs.id.clone(),
+        href: format!("abstracts/{}.html", name),
+        locale: abs.locale.clone(),
+        untranslated: abs.untranslated,
+        title: abs.title.clone(),
+        authors: abs.authors.clone(),
+        affiliations: abs.affiliations.clone(),
+        sections: abs
+            .sections
+            .iter()
+            .map(|s| SectionPage {
+                label: s.label.clone(),
+                body: Markup::new(markup::blocks(&s.body)),
+            })
+            .collect(),
+        take_home: abs
+            .take_home
+            .as_deref()
+            .map(|b| Markup::new(markup::inline_blocks(b))),
+        reference: abs
+            .reference
+            .as_deref()
+            .map(|b| Markup::new(markup::inline_blocks(b))),
+        tags: abs
+            .tags
+            .iter()
+            .map(|tag| TagLink {
+                tag: tag.clone(),
+                anchor: anchor(tag),
+            })
+            .collect(),
+    }
+}
+
+// `search.json`: one entry per abstract with its plain text.
+fn write_search_index(book: &BookData, ctx: &SiteContext, dir: &Path) -> Result<()> {
+    let mut entries = Vec::new();
+    for (session, pages) in book.sessions.iter().zip(&ctx.sessions) {
+        for (abs, page) in session.abstracts.iter().zip(&pages.abstracts) {
+            entries.push(SearchEntry {
+                title: &abs.title,
+                authors: &abs.authors,
+                session: &session.title,
+                tags: &abs.tags,
+                text: abs
+                    .sections
+                    .iter()
+                    .map(|s| markup::plain(&s.body))
+                    .collect::<Vec<_>>()
+                    .join("\n\n"),
+                href: &page.href,
+            });
+        }
+    }
+    fs::write(dir.join("search.json"), serde_json::to_string(&entries)?)?;
+    Ok(())
+}
+
+fn copy_tree(from: &Path, to: &Path) -> Result<()> {
+    if !from.is_dir() {
+        return Err(anyhow!("site assets not found: {}", from.display()));
+    }
+    create_dir_all(to)?;
+    for entry in fs::read_dir(from)?.filter_map(|e| e.ok()) {
+        let path: PathBuf = entry.path();
+        if path.is_dir() {
+            copy_tree(&path, &to.join(entry.file_name()))?;
+        } else {
+            fs::copy(&path, to.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
+// Label keys used by the site templates, for `validate`'s label report.
+pub fn label_keys() -> Result<std::collections::BTreeSet<String>> {
+    let dir = Path::new(SITE_TEMPLATE_DIR);
+    if !dir.is_dir() {
+        return Ok(Default::default());
+    }
+    locale::template_label_keys(dir)
+}
@@ -0,0 +1,210 @@
+use crate::cli::BuildOpts;
+use crate::config::Config;
+use crate::io::Selection;
+use crate::render::Renderer;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::Hasher;
+use std::path::{Path, PathBuf};
+
+// Caches under `<output>/.cache/`, which `build` keeps when it wipes the output:
+// - `build.json`: key of the last complete build (size and modification time of
+//   the inputs, config and template, and the options), the programme it was
+//   made from and what it wrote; an identical rebuild returns before the input
+//   is parsed.
+// - `sessions/<locale>/<hash>.typ`: rendered session include files.
+// - `pdf/<locale>/<hash>/`: a locale's PDF and previews, reused while its typst
+//   sources, data and template hash the same.
+// `--no-cache` bypasses all of them.
+
+pub const CACHE_DIR: &str = ".cache";
+const BUILD_RECORD: &str = "build.json";
+
+pub fn cache_dir(outdir: &str) -> PathBuf {
+    Path::new(outdir).join(CACHE_DIR)
+}
+
+// Hash over strings and files (by content, or by size and modification time).
+// Stable for a given toolchain, which is all a cache inside the output
+// directory needs.
+#[derive(Default)]
+pub struct Fingerprint(DefaultHasher);
+
+impl Fingerprint {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn str(&mut self, s: &str) -> &mut Self {
+        self.0.write_usize(s.len());
+        self.0.write(s.as_bytes());
+        self
+    }
+
+    pub fn bytes(&mut self, b: &[u8]) -> &mut Self {
+        self.0.write_usize(b.len());
+        self.0.write(b);
+        self
+    }
+
+    // A file, or every file below a directory (sorted), by relative name and
+    // content. A missing path hashes as absent.
+    pub fn path(&mut self, path: &Path) -> Result<&mut Self> {
+        self.path_where(path, &|_| true)
+    }
+
+    pub fn path_where(&mut self, path: &Path, keep: &dyn Fn(&Path) -> bool) -> Result<&mut Self> {
+        if path.is_dir() {
+            let mut entries: Vec<PathBuf> = fs::read_dir(path)?
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .collect();
+            entries.sort();
+            for entry in entries {
+                if entry.is_dir() || keep(&entry) {
+                    self.str(&entry.file_name().unwrap_or_default().to_string_lossy());
+                    self.path_where(&entry, keep)?;
+                }
+            }
+        } else if path.is_file() {
+            self.bytes(&fs::read(path)?);
+        } else {
+            self.str("<absent>");
+        }
+        Ok(self)
+    }
+
+    // Like `path`, but a file by its size and modification time, so large
+    // inputs are not read.
+    pub fn stat(&mut self, path: &Path) -> Result<&mut Self> {
+        if path.is_dir() {
+            let mut entries: Vec<PathBuf> = fs::read_dir(path)?
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .collect();
+            entries.sort();
+            for entry in entries {
+                self.str(&entry.file_name().unwrap_or_default().to_string_lossy());
+                self.stat(&entry)?;
+            }
+        } else if path.is_file() {
+            let meta = fs::metadata(path)?;
+            let modified = meta
+                .modified()?
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default();
+            self.0.write_u64(meta.len());
+            self.0.write_u128(modified.as_nanos());
+        } else {
+            self.str("<absent>");
+        }
+        Ok(self)
+    }
+
+    pub fn finish(&self) -> String {
+        format!("{:016x}", self.0.finish())
+    }
+}
+
+// `S` is the `Selection`, borrowed when recording.
+#[derive(Debug, Serialize, Deserialize)]
+struct BuildRecord<S> {
+    key: String,
+    outputs: Vec<PathBuf>,
+    selection: S,
+}
+
+// Everything a build reads: input files, config, thesaurus, template and fonts
+// (by size and modification time), plus the options that change what it
+// writes. Known before the input is parsed.
+pub fn build_key(
+    opts: &BuildOpts,
+    config: &Config,
+    renderers: &[(&str, &dyn Renderer)],
+) -> Result<String> {
+    let formats: Vec<&str> = renderers.iter().map(|(name, _)| *name).collect();
+    let mut fp = Fingerprint::new();
+    fp.str(env!("CARGO_PKG_VERSION")).str(&format!(
+        "{:?}",
+        (
+            opts.source,
+            &opts.template,
+            &opts.locales,
+            &opts.typst_bin,
+            opts.no_pdf,
+            &opts.preview,
+            (&opts.only_session, &opts.only_id, &opts.keyword),
+            opts.no_linkify,
+            opts.data_json,
+            (&formats, opts.docx_split),
+        )
+    ));
+    fp.stat(Path::new(&opts.input))?;
+    for file in [
+        opts.grouping.as_deref(),
+        opts.config.as_deref(),
+        config.keywords.thesaurus.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        fp.str(file).stat(Path::new(file))?;
+    }
+    fp.stat(&crate::typst::template::template_location(&opts.template).0)?
+        .stat(&crate::typst::font_dir(&opts.template))?;
+    for (_, renderer) in renderers {
+        for path in renderer.inputs(config) {
+            fp.str(&path.to_string_lossy()).stat(&path)?;
+        }
+    }
+    Ok(fp.finish())
+}
+
+// The programme of the last complete build, if it had this key and its outputs
+// are still there.
+pub fn cached_build(outdir: &str, key: &str) -> Option<Selection> {
+    let txt = fs::read_to_string(cache_dir(outdir).join(BUILD_RECORD)).ok()?;
+    let record = serde_json::from_str::<BuildRecord<Selection>>(&txt).ok()?;
+    (record.key == key && record.outputs.iter().all(|p| p.exists())).then_some(record.selection)
+}
+
+pub fn record_build(
+    outdir: &str,
+    key: &str,
+    outputs: Vec<PathBuf>,
+    selection: &Selection,
+) -> Result<()> {
+    let dir = cache_dir(outdir);
+    fs::create_dir_all(&dir)?;
+    let record = BuildRecord {
+        key: key.to_string(),
+        outputs,
+        selection,
+    };
+    fs::write(
+        dir.join(BUILD_RECORD),
+        serde_json::to_string_pretty(&record)?,
+    )?;
+    Ok(())
+}
+
+// Empty the output directory except for `.cache/`, and forget the last build
+// so a failure halfway is not mistaken for a finished one.
+pub fn clear_output(outdir: &Path) -> Result<()> {
+    for entry in fs::read_dir(outdir)? {
+        let path = entry?.path();
+        if path.file_name().is_some_and(|n| n == CACHE_DIR) {
+            continue;
+        }
+        if path.is_dir() {
+            fs::remove_dir_all(&path)?;
+        } else {
+            fs::remove_file(&path)?;
+        }
+    }
+    let record = outdir.join(CACHE_DIR).join(BUILD_RECORD);
+    if record.exists() {
+        fs::remove_file(record)?;
+    }
+    Ok(())
+}
@@ -1,9 +1,13 @@
-use crate::model::{Abstract, AbstractSection, ItemRef, Session};
+use crate::config::TextConfig;
+use crate::io::xlsx_rich;
+use crate::model::{Abstract, AbstractSection, AbstractVariant, ItemRef, Session, SourceRef};
 use anyhow::{Result, anyhow};
-use calamine::{Data, Reader, open_workbook_auto};
-use std::collections::{HashMap, HashSet};
+use calamine::{Data, Range, Reader, open_workbook_auto};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
+use std::sync::OnceLock;
 
 fn as_str(cell: Option<&Data>) -> String {
     match cell {
@@ -229,7 +233,7 @@ fn capitalize_label(input: &str) -> String {
 
 fn normalize_section_label(input: &str) -> String {
     let trimmed = input.trim();
-    let trimmed = trimmed.trim_end_matches(|c: char| c == ',' || c == '.' || c == ':');
+    let trimmed = trimmed.trim_end_matches([',', '.', ':']);
     let trimmed = trimmed.trim();
     capitalize_label(trimmed)
 }
@@ -333,12 +337,14 @@ fn split_abstract_sections(input: &str, locale: &str) -> Vec<AbstractSection> {
                     sections.push(AbstractSection {
                         label: prev_label,
                         text: pre_text,
+                        ..Default::default()
                     });
                 }
             } else if !pre_text.is_empty() {
                 sections.push(AbstractSection {
                     label: default_label.clone(),
                     text: pre_text,
+                    ..Default::default()
                 });
             }
             current_label = Some(label);
@@ -358,12 +364,17 @@ fn split_abstract_sections(input: &str, locale: &str) -> Vec<AbstractSection> {
     let tail = tail.trim().to_string();
     if let Some(label) = current_label.take() {
         if !tail.is_empty() {
-            sections.push(AbstractSection { label, text: tail });
+            sections.push(AbstractSection {
+                label,
+                text: tail,
+                ..Default::default()
+            });
         }
     } else if !tail.is_empty() {
         sections.push(AbstractSection {
             label: default_label,
             text: tail,
+            ..Default::default()
         });
     }
 
@@ -372,7 +383,12 @@ fn split_abstract_sections(input: &str, locale: &str) -> Vec<AbstractSection> {
 
 fn sanitize_abstract_text(input: &str) -> String {
     let mut out = input.to_string();
-    for needle in ["Introduktion /", "introduktion /", "Introduktion", "introduktion"] {
+    for needle in [
+        "Introduktion /",
+        "introduktion /",
+        "Introduktion",
+        "introduktion",
+    ] {
         out = out.replace(needle, "");
     }
     out.trim().to_string()
@@ -391,6 +407,207 @@ fn join_section_texts(sections: &[AbstractSection], fallback: &str) -> String {
     }
 }
 
+fn find_column(lower_row: &[String], subs: &[&str]) -> Option<usize> {
+    for (j, cell) in lower_row.iter().enumerate() {
+        for &s in subs {
+            if cell.contains(&s.to_lowercase()) {
+                return Some(j);
+            }
+        }
+    }
+    None
+}
+
+// Language variant columns: `title_en`, `abstract_en` or `Abstract (en)`.
+// Returns (column, is_title, locale).
+fn variant_columns(lower_row: &[String]) -> Vec<(usize, bool, String)> {
+    static VARIANT: OnceLock<Regex> = OnceLock::new();
+    let re = VARIANT.get_or_init(|| {
+        Regex::new(
+            r"^(title|titel|abstract|resum[eé])(?:_([a-z]{2}(?:-[a-z]{2})?)|\s*\(([a-z]{2}(?:-[a-z]{2})?)\))$",
+        )
+        .unwrap()
+    });
+    lower_row
+        .iter()
+        .enumerate()
+        .filter_map(|(j, cell)| {
+            let caps = re.captures(cell.trim())?;
+            let locale = caps.get(2).or_else(|| caps.get(3))?.as_str().to_string();
+            Some((j, matches!(&caps[1], "title" | "titel"), locale))
+        })
+        .collect()
+}
+
+// Lowercased header row with variant columns blanked, so `title`/`abstract`
+// lookups find the main columns.
+fn base_header(header_row: &[String]) -> Vec<String> {
+    let mut lower_row: Vec<String> = header_row.iter().map(|s| s.to_lowercase()).collect();
+    for (j, _, _) in variant_columns(&lower_row) {
+        lower_row[j].clear();
+    }
+    lower_row
+}
+
+// The id, title and abstract columns of a base header row. The parser and the
+// rich-text reader must agree on them.
+fn text_columns(lower_row: &[String]) -> Option<(usize, usize, usize)> {
+    let col_id = find_column(lower_row, &["id"])?;
+    let col_title = find_column(lower_row, &["title", "titel"]).unwrap_or(col_id + 1);
+    let col_abstract =
+        find_column(lower_row, &["abstract", "resum", "resumé"]).unwrap_or(col_title + 2);
+    Some((col_id, col_title, col_abstract))
+}
+
+// Rows of an abstracts sheet, with the abstract cells prepared for `[text]`:
+// with `rich_text`, cells that carry formatting runs are replaced by their
+// markup form, and without `markup` the other cells are escaped so their text
+// stays literal. Typed `__` in cells read as markup is reported.
+fn abstract_rows(
+    path: &str,
+    sheet: &str,
+    range: &Range<Data>,
+    text: &TextConfig,
+) -> Result<Vec<Vec<String>>> {
+    let mut rows: Vec<Vec<String>> = range
+        .rows()
+        .map(|r| r.iter().map(|c| as_str(Some(c))).collect())
+        .collect();
+    if !text.parses_markup() {
+        return Ok(rows);
+    }
+    let Some(header_idx) = find_header_row(&rows, &[]) else {
+        return Ok(rows);
+    };
+    let lower_row: Vec<String> = rows[header_idx].iter().map(|s| s.to_lowercase()).collect();
+    let Some((col_id, _, col)) = text_columns(&base_header(&rows[header_idx])) else {
+        return Ok(rows);
+    };
+    let mut body_cols = vec![col];
+    body_cols.extend(
+        variant_columns(&lower_row)
+            .into_iter()
+            .filter(|(_, is_title, _)| !is_title)
+            .map(|(j, _, _)| j),
+    );
+
+    let rich = if text.rich_text {
+        // calamine ranges start at the first used cell, not at A1
+        let (row0, col0) = range.start().unwrap_or((0, 0));
+        xlsx_rich::rich_cells(path, sheet)?
+            .into_iter()
+            .filter_map(|((r, c), markup)| {
+                let r = r.checked_sub(row0)? as usize;
+                let c = c.checked_sub(col0)? as usize;
+                (c == col && r > header_idx).then_some((r, markup))
+            })
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    for (r, row) in rows.iter_mut().enumerate().skip(header_idx + 1) {
+        let id = row
+            .get(col_id)
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default();
+        for &j in &body_cols {
+            let rich_cell = if j == col { rich.get(&r) } else { None };
+            let Some(cell) = row.get_mut(j) else {
+                continue;
+            };
+            if let Some(markup) = rich_cell {
+                *cell = markup.clone();
+            } else if !text.markup {
+                *cell = crate::richtext::escape_inline(cell);
+            } else if let Some(warning) = crate::richtext::underscore_warning(cell) {
+                tracing::warn!("Abstract {}: {}", id, warning);
+            }
+        }
+    }
+    Ok(rows)
+}
+
+// Other-language versions of one row. A variant needs a title or an abstract;
+// the missing half is taken from the main columns.
+fn row_variants(
+    row: &[String],
+    cols: &[(usize, bool, String)],
+    locale: &str,
+    title: &str,
+    sections: &[AbstractSection],
+) -> BTreeMap<String, AbstractVariant> {
+    let mut parts: BTreeMap<String, (String, String)> = BTreeMap::new();
+    for (j, is_title, loc) in cols {
+        let value = row
+            .get(*j)
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default();
+        let entry = parts.entry(loc.clone()).or_default();
+        if *is_title {
+            entry.0 = value;
+        } else {
+            entry.1 = value;
+        }
+    }
+    let mut variants = BTreeMap::new();
+    for (loc, (v_title, v_text)) in parts {
+        if loc.eq_ignore_ascii_case(locale) || (v_title.is_empty() && v_text.is_empty()) {
+            continue;
+        }
+        let (abstract_sections, abstract_text) = if v_text.is_empty() {
+            (sections.to_vec(), join_section_texts(sections, ""))
+        } else {
+            abstract_body(&v_text, &loc)
+        };
+        variants.insert(
+            loc,
+            AbstractVariant {
+                title: if v_title.is_empty() {
+                    title.to_string()
+                } else {
+                    v_title
+                },
+                abstract_text,
+                abstract_sections,
+            },
+        );
+    }
+    variants
+}
+
+// Header text of a column, or its letter (`E`) when the header cell is empty.
+fn column_name(header_row: &[String], j: usize) -> String {
+    match header_row
+        .get(j)
+        .map(|h| h.trim())
+        .filter(|h| !h.is_empty())
+    {
+        Some(h) => h.to_string(),
+        None => column_letter(j),
+    }
+}
+
+fn column_letter(mut j: usize) -> String {
+    let mut out = String::new();
+    loop {
+        out.insert(0, (b'A' + (j % 26) as u8) as char);
+        if j < 26 {
+            return out;
+        }
+        j = j / 26 - 1;
+    }
+}
+
+// Sheet name and absolute row numbers for abstracts parsed from `range` rows.
+fn set_sheet(abstracts: &mut HashMap<String, Abstract>, sheet: &str, range: &Range<Data>) {
+    // calamine ranges start at the first used cell, not at A1
+    let row0 = range.start().map(|(r, _)| r as usize).unwrap_or(0);
+    for source in abstracts.values_mut().filter_map(|a| a.source.as_mut()) {
+        source.sheet = sheet.to_string();
+        source.row += row0;
+    }
+}
+
 // Extract parsing of abstracts from a rows buffer into a helper so tests can exercise
 // duplicate-id handling and header-detection without needing actual workbook files.
 pub fn parse_abstracts_from_rows(
@@ -398,27 +615,23 @@ pub fn parse_abstracts_from_rows(
     header_idx: usize,
 ) -> Result<HashMap<String, Abstract>> {
     let header_row = &rows_a[header_idx];
-    let lower_row: Vec<String> = header_row.iter().map(|s| s.to_lowercase()).collect();
-    let find_col = |subs: &[&str]| -> Option<usize> {
-        for (j, cell) in lower_row.iter().enumerate() {
-            for &s in subs {
-                if cell.contains(&s.to_lowercase()) {
-                    return Some(j);
-                }
-            }
-        }
-        None
-    };
+    let lower_row = base_header(header_row);
+    let find_col = |subs: &[&str]| find_column(&lower_row, subs);
+    let variant_cols = variant_columns(
+        &header_row
+            .iter()
+            .map(|s| s.to_lowercase())
+            .collect::<Vec<_>>(),
+    );
 
-    let col_id = find_col(&["id"]).ok_or_else(|| anyhow!("id column not found in abstracts"))?;
-    let col_title = find_col(&["title", "titel"]).unwrap_or(col_id + 1);
+    let (col_id, col_title, col_abstract) =
+        text_columns(&lower_row).ok_or_else(|| anyhow!("id column not found in abstracts"))?;
     let col_presenter = find_col(&[
         "hvem præsenterer projektet",
         "præsenterer projektet",
         "navn, titel",
     ]);
     let col_authors = find_col(&["authors", "author", "forfatter"]).unwrap_or(col_title + 1);
-    let col_abstract = find_col(&["abstract", "resum", "resumé"]).unwrap_or(col_title + 2);
     let col_keywords = find_col(&["keyword", "keywords", "nøgle", "emne ord", "emneord"])
         .unwrap_or(col_abstract + 1);
     let col_takehome = find_col(&["take home", "take-home", "takehome", "take home messages"])
@@ -428,6 +641,24 @@ pub fn parse_abstracts_from_rows(
         .unwrap_or(col_reference + 1);
     let col_center = find_col(&["center", "centre", "center/centre"]).unwrap_or(col_authors + 1);
     let col_contact = find_col(&["email", "kontakt", "contact"]).unwrap_or(col_authors + 2);
+    let col_status = find_col(&["status", "decision", "beslutning", "afgørelse"]);
+
+    // field -> column header, for error messages that point back at the sheet
+    let mut columns: BTreeMap<String, String> = [
+        ("title", col_title),
+        ("authors", col_presenter.unwrap_or(col_authors)),
+        ("abstract", col_abstract),
+        ("keywords", col_keywords),
+        ("take_home", col_takehome),
+        ("reference", col_reference),
+    ]
+    .into_iter()
+    .map(|(field, j)| (field.to_string(), column_name(header_row, j)))
+    .collect();
+    for (j, is_title, loc) in &variant_cols {
+        let field = if *is_title { "title" } else { "abstract" };
+        columns.insert(format!("{}_{}", field, loc), column_name(header_row, *j));
+    }
 
     let mut abstracts: Vec<Abstract> = Vec::new();
     let mut seen: HashMap<String, usize> = HashMap::new();
@@ -484,6 +715,10 @@ pub fn parse_abstracts_from_rows(
             .get(col_contact)
             .map(|s| s.trim().to_string())
             .filter(|s| !s.is_empty());
+        let status = col_status
+            .and_then(|idx| row.get(idx))
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
 
         if aid.is_empty() && title.is_empty() && abstract_text.is_empty() {
             continue;
@@ -505,11 +740,8 @@ pub fn parse_abstracts_from_rows(
         } else {
             parse_authors_and_affiliation(&authors_raw)
         };
-        let keywords_vec = keywords
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
+        let variants = row_variants(row, &variant_cols, &locale_val, &title, &abstract_sections);
+        let keywords_vec = crate::keywords::split(&keywords);
         abstracts.push(Abstract {
             id: aid.clone(),
             title: title.clone(),
@@ -524,6 +756,14 @@ pub fn parse_abstracts_from_rows(
             reference,
             literature,
             locale: locale_val,
+            track: None,
+            status,
+            variants,
+            source: Some(SourceRef {
+                sheet: String::new(),
+                row: ridx + 1,
+                columns: columns.clone(),
+            }),
         });
     }
 
@@ -538,6 +778,113 @@ pub fn parse_abstracts_from_rows(
     Ok(abstract_map)
 }
 
+// `parse_abstracts_from_rows` with the abstract text parsed as markup when
+// `[text] markup` or `rich_text` is on.
+fn abstracts_from_rows(
+    rows: &[Vec<String>],
+    header_idx: usize,
+    text: &TextConfig,
+) -> Result<HashMap<String, Abstract>> {
+    let mut abstracts = parse_abstracts_from_rows(rows, header_idx)?;
+    if text.parses_markup() {
+        abstracts
+            .values_mut()
+            .for_each(crate::richtext::apply_markup);
+    }
+    Ok(abstracts)
+}
+
+// Parse a grouping sheet: rows without known abstract ids are session headers,
+// rows with ids are items of the current session (in row order).
+pub fn parse_sessions_from_rows(
+    rows_b: &[Vec<String>],
+    abstract_map: &HashMap<String, Abstract>,
+) -> Result<Vec<Session>> {
+    let mut sessions: Vec<Session> = Vec::new();
+    let mut seen_session_ids: HashMap<String, u32> = HashMap::new();
+    let mut current_session_title = None::<String>;
+    let mut current_items: Vec<ItemRef> = Vec::new();
+    let mut item_counter = 1u32;
+
+    // helper to flush current session
+    let flush_session = |sessions: &mut Vec<Session>,
+                         seen: &mut HashMap<String, u32>,
+                         title: Option<String>,
+                         items: &mut Vec<ItemRef>|
+     -> Result<()> {
+        let title = title.unwrap_or_else(|| "(unnamed)".to_string());
+        push_session(sessions, seen, title, items)
+    };
+
+    for row in rows_b.iter() {
+        if row.iter().all(|c| c.trim().is_empty()) {
+            continue;
+        }
+        // try to find any token that looks like an abstract id present in abstract_map
+        let mut found_ids: Vec<String> = Vec::new();
+        for c in row.iter() {
+            if c.trim().is_empty() {
+                continue;
+            }
+            let token = c.trim();
+            if abstract_map.contains_key(token) {
+                found_ids.push(token.to_string());
+                continue;
+            }
+            for part in token.replace(';', ",").split(',').map(|s| s.trim()) {
+                if abstract_map.contains_key(part) {
+                    found_ids.push(part.to_string());
+                }
+            }
+        }
+
+        if !found_ids.is_empty() {
+            // this row contains item(s)
+            if current_session_title.is_none() {
+                current_session_title = Some("(unnamed)".to_string());
+            }
+            for fid in found_ids.into_iter() {
+                current_items.push(ItemRef {
+                    id: fid,
+                    order: item_counter,
+                });
+                item_counter += 1;
+            }
+        } else {
+            // treat as session header
+            // flush previous
+            flush_session(
+                &mut sessions,
+                &mut seen_session_ids,
+                current_session_title.take(),
+                &mut current_items,
+            )?;
+            // set new title
+            let textcells: Vec<String> = row
+                .iter()
+                .filter(|c| !c.trim().is_empty())
+                .cloned()
+                .collect();
+            let title = textcells.join(" ").trim().to_string();
+            current_session_title = Some(if title.is_empty() {
+                "(unnamed)".to_string()
+            } else {
+                title
+            });
+            item_counter = 1;
+        }
+    }
+    // flush last
+    flush_session(
+        &mut sessions,
+        &mut seen_session_ids,
+        current_session_title.take(),
+        &mut current_items,
+    )?;
+
+    Ok(sessions)
+}
+
 fn find_sheet_by_substr(path: &str, subs: &[&str]) -> Result<String> {
     let wb = open_workbook_auto(path).map_err(|e| anyhow!("open failed: {}", e))?;
     for name in wb.sheet_names() {
@@ -555,7 +902,10 @@ fn find_sheet_by_substr(path: &str, subs: &[&str]) -> Result<String> {
         .ok_or_else(|| anyhow!("no sheets in workbook {}", path))
 }
 
-pub fn parse_workbook(path: &str) -> Result<(HashMap<String, Abstract>, Vec<Session>)> {
+pub fn parse_workbook(
+    path: &str,
+    text: &TextConfig,
+) -> Result<(HashMap<String, Abstract>, Vec<Session>)> {
     // if `path` is a directory, find two xlsx files and parse accordingly
     if Path::new(path).is_dir() {
         let mut xls = Vec::new();
@@ -604,7 +954,7 @@ pub fn parse_workbook(path: &str) -> Result<(HashMap<String, Abstract>, Vec<Sess
         let file_b = file_b.ok_or_else(|| anyhow!("failed to choose grouping file"))?;
 
         // now parse abstracts from file_a and sessions from file_b
-        return parse_two_workbooks(&file_a, &file_b);
+        return parse_two_workbooks(&file_a, &file_b, text);
     }
 
     // existing single-workbook logic (both sheets in one workbook)
@@ -651,15 +1001,13 @@ pub fn parse_workbook(path: &str) -> Result<(HashMap<String, Abstract>, Vec<Sess
     let range = wb
         .worksheet_range(&abstracts_sheet)
         .map_err(|e| anyhow!("Failed to get range for sheet {}: {}", abstracts_sheet, e))?;
-    let mut rows_a: Vec<Vec<String>> = Vec::new();
-    for r in range.rows() {
-        rows_a.push(r.iter().map(|c| as_str(Some(c))).collect());
-    }
+    let rows_a = abstract_rows(path, &abstracts_sheet, &range, text)?;
 
     // detect header row
     let header_idx = find_header_row(&rows_a, &[])
         .ok_or_else(|| anyhow!("Could not detect header row in abstracts sheet"))?;
-    let abstract_map = parse_abstracts_from_rows(&rows_a, header_idx)?;
+    let mut abstract_map = abstracts_from_rows(&rows_a, header_idx, text)?;
+    set_sheet(&mut abstract_map, &abstracts_sheet, &range);
 
     // parse sessions sheet using flexible heuristics (header rows vs item rows)
     tracing::info!("Parsing sessions sheet: {}", sessions_sheet);
@@ -671,96 +1019,7 @@ pub fn parse_workbook(path: &str) -> Result<(HashMap<String, Abstract>, Vec<Sess
         rows_b.push(r.iter().map(|c| as_str(Some(c))).collect());
     }
 
-    // try to detect a header row (first non-empty row with 'id' or 'abstract')
-    let mut sessions: Vec<Session> = Vec::new();
-    let mut seen_session_ids: HashMap<String, u32> = HashMap::new();
-    let mut current_session_title = None::<String>;
-    let mut current_items: Vec<ItemRef> = Vec::new();
-    let mut item_counter = 1u32;
-
-    // helper to flush current session
-    let flush_session = |sessions: &mut Vec<Session>,
-                         seen: &mut HashMap<String, u32>,
-                         title: Option<String>,
-                         items: &mut Vec<ItemRef>|
-     -> Result<()> {
-        let title = title.unwrap_or_else(|| "(unnamed)".to_string());
-        push_session(sessions, seen, title, items)
-    };
-
-    for row in rows_b.iter() {
-        if row.iter().all(|c| c.trim().is_empty()) {
-            continue;
-        }
-        // try to find any token that looks like an abstract id present in abstract_map
-        let mut found_ids: Vec<String> = Vec::new();
-        for c in row.iter() {
-            if c.trim().is_empty() {
-                continue;
-            }
-            let token = c.trim();
-            if abstract_map.contains_key(token) {
-                found_ids.push(token.to_string());
-                continue;
-            }
-            for part in token.replace(';', ",").split(',').map(|s| s.trim()) {
-                if abstract_map.contains_key(part) {
-                    found_ids.push(part.to_string());
-                }
-            }
-        }
-
-        if !found_ids.is_empty() {
-            // this row contains item(s)
-            if current_session_title.is_none() {
-                current_session_title = Some("(unnamed)".to_string());
-            }
-            for fid in found_ids.into_iter() {
-                current_items.push(ItemRef {
-                    id: fid,
-                    order: item_counter,
-                });
-                item_counter += 1;
-            }
-        } else {
-            // treat as session header
-            // flush previous
-            flush_session(
-                &mut sessions,
-                &mut seen_session_ids,
-                current_session_title.take(),
-                &mut current_items,
-            )?;
-            // set new title
-            let textcells: Vec<String> = row
-                .iter()
-                .filter(|c| !c.trim().is_empty())
-                .cloned()
-                .collect();
-            let title = textcells.join(" ").trim().to_string();
-            current_session_title = Some(if title.is_empty() {
-                "(unnamed)".to_string()
-            } else {
-                title
-            });
-            item_counter = 1;
-        }
-    }
-    // flush last
-    flush_session(
-        &mut sessions,
-        &mut seen_session_ids,
-        current_session_title.take(),
-        &mut current_items,
-    )?;
-
-    // determine referenced set
-    let mut referenced: HashSet<String> = HashSet::new();
-    for s in &sessions {
-        for it in &s.items {
-            referenced.insert(it.id.clone());
-        }
-    }
+    let sessions = parse_sessions_from_rows(&rows_b, &abstract_map)?;
 
     // Unreferenced abstracts are not added to an automatic session.
 
@@ -770,6 +1029,7 @@ pub fn parse_workbook(path: &str) -> Result<(HashMap<String, Abstract>, Vec<Sess
 pub fn parse_two_workbooks(
     file_a: &str,
     file_b: &str,
+    text: &TextConfig,
 ) -> Result<(HashMap<String, Abstract>, Vec<Session>)> {
     tracing::info!(
         "Parsing abstracts from {} and sessions from {}",
@@ -781,16 +1041,26 @@ pub fn parse_two_workbooks(
     let range_a = open_workbook_auto(file_a)?
         .worksheet_range(&sheet_a)
         .map_err(|e| anyhow!("Failed to read sheet {} from {}: {}", sheet_a, file_a, e))?;
-    let mut rows_a: Vec<Vec<String>> = Vec::new();
-    for r in range_a.rows() {
-        rows_a.push(r.iter().map(|c| as_str(Some(c))).collect());
-    }
+    let rows_a = abstract_rows(file_a, &sheet_a, &range_a, text)?;
 
     let header_idx = find_header_row(&rows_a, &[])
         .ok_or_else(|| anyhow!("Could not detect header row in abstracts sheet"))?;
-    let abstract_map = parse_abstracts_from_rows(&rows_a, header_idx)?;
+    let mut abstract_map = abstracts_from_rows(&rows_a, header_idx, text)?;
+    set_sheet(&mut abstract_map, &sheet_a, &range_a);
 
     // load rows B
+    let rows_b = load_grouping_rows(file_b)?;
+
+    // parse sessions from rows_b (same heuristics as single workbook case)
+    let sessions = parse_sessions_from_rows(&rows_b, &abstract_map)?;
+
+    // Unreferenced abstracts are not added to an automatic session.
+
+    Ok((abstract_map, sessions))
+}
+
+// Rows of the grouping sheet in `file_b` (by name heuristics, else the first sheet).
+pub(crate) fn load_grouping_rows(file_b: &str) -> Result<Vec<Vec<String>>> {
     let sheet_b = match find_sheet_by_substr(file_b, &["gruppering", "grupper", "poster"]) {
         Ok(s) => s,
         Err(_) => match open_workbook_auto(file_b) {
@@ -802,98 +1072,30 @@ pub fn parse_two_workbooks(
             Err(_) => "Sheet1".to_string(),
         },
     };
-    let range_b = open_workbook_auto(file_b)?
-        .worksheet_range(&sheet_b)
-        .map_err(|e| anyhow!("Failed to read sheet {} from {}: {}", sheet_b, file_b, e))?;
-    let mut rows_b: Vec<Vec<String>> = Vec::new();
-    for r in range_b.rows() {
-        rows_b.push(r.iter().map(|c| as_str(Some(c))).collect());
-    }
-
-    // parse sessions from rows_b (same heuristics as single workbook case)
-    let mut sessions: Vec<Session> = Vec::new();
-    let mut seen_session_ids: HashMap<String, u32> = HashMap::new();
-    let mut current_session_title = None::<String>;
-    let mut current_items: Vec<ItemRef> = Vec::new();
-    let mut item_counter = 1u32;
-
-    let flush_session = |sessions: &mut Vec<Session>,
-                         seen: &mut HashMap<String, u32>,
-                         title: Option<String>,
-                         items: &mut Vec<ItemRef>|
-     -> Result<()> {
-        let title = title.unwrap_or_else(|| "(unnamed)".to_string());
-        push_session(sessions, seen, title, items)
-    };
-
-    for row in rows_b.iter() {
-        if row.iter().all(|c| c.trim().is_empty()) {
-            continue;
-        }
-        let mut found_ids: Vec<String> = Vec::new();
-        for c in row.iter() {
-            if c.trim().is_empty() {
-                continue;
-            }
-            let token = c.trim();
-            if abstract_map.contains_key(token) {
-                found_ids.push(token.to_string());
-                continue;
-            }
-            for part in token.replace(';', ",").split(',').map(|s| s.trim()) {
-                if abstract_map.contains_key(part) {
-                    found_ids.push(part.to_string());
-                }
-            }
-        }
+    read_sheet_rows(file_b, &sheet_b)
+}
 
-        if !found_ids.is_empty() {
-            if current_session_title.is_none() {
-                current_session_title = Some("(unnamed)".to_string());
-            }
-            for fid in found_ids.into_iter() {
-                current_items.push(ItemRef {
-                    id: fid,
-                    order: item_counter,
-                });
-                item_counter += 1;
-            }
-        } else {
-            flush_session(
-                &mut sessions,
-                &mut seen_session_ids,
-                current_session_title.take(),
-                &mut current_items,
-            )?;
-            let textcells: Vec<String> = row
-                .iter()
-                .filter(|c| !c.trim().is_empty())
-                .cloned()
-                .collect();
-            let title = textcells.join(" ").trim().to_string();
-            current_session_title = Some(if title.is_empty() {
-                "(unnamed)".to_string()
-            } else {
-                title
-            });
-            item_counter = 1;
-        }
+pub(crate) fn read_sheet_rows(path: &str, sheet: &str) -> Result<Vec<Vec<String>>> {
+    let range = open_workbook_auto(path)?
+        .worksheet_range(sheet)
+        .map_err(|e| anyhow!("Failed to read sheet {} from {}: {}", sheet, path, e))?;
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for r in range.rows() {
+        rows.push(r.iter().map(|c| as_str(Some(c))).collect());
     }
-    flush_session(
-        &mut sessions,
-        &mut seen_session_ids,
-        current_session_title.take(),
-        &mut current_items,
-    )?;
+    Ok(rows)
+}
 
-    // determine referenced set and add Unassigned for unreferenced
-    let mut referenced: HashSet<String> = HashSet::new();
-    for s in &sessions {
-        for it in &s.items {
-            referenced.insert(it.id.clone());
-        }
-    }
-    // Unreferenced abstracts are not added to an automatic session.
+pub(crate) fn sheet_names(path: &str) -> Result<Vec<String>> {
+    let wb = open_workbook_auto(path).map_err(|e| anyhow!("open failed: {}", e))?;
+    Ok(wb.sheet_names().to_owned())
+}
 
-    Ok((abstract_map, sessions))
+// Sanitize raw abstract text and split it into labelled sections; returns the
+// sections and the joined body text, exactly as the workbook parser does.
+pub(crate) fn abstract_body(raw: &str, locale: &str) -> (Vec<AbstractSection>, String) {
+    let sanitized = sanitize_abstract_text(raw.trim());
+    let sections = split_abstract_sections(&sanitized, locale);
+    let text = join_section_texts(&sections, &sanitized);
+    (sections, text)
 }
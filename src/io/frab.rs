@@ -0,0 +1,328 @@
+use crate::config::{EventConfig, SessionSlot};
+use crate::io::markdown::session_slug;
+use crate::model::{Abstract, Session};
+use crate::util::{civil_from_days, days_from_civil, days_in_month, stable_guid, xml_escape};
+use anyhow::{Result, anyhow};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{File, create_dir_all};
+use std::io::Write;
+use std::path::Path;
+
+pub const SCHEDULE_FILE: &str = "schedule.xml";
+
+fn parse_hhmm(input: &str) -> Result<u32> {
+    let (h, m) = input
+        .trim()
+        .split_once(':')
+        .ok_or_else(|| anyhow!("invalid time '{}', expected HH:MM", input))?;
+    let h: u32 = h
+        .parse()
+        .map_err(|_| anyhow!("invalid hour in time '{}'", input))?;
+    let m: u32 = m
+        .parse()
+        .map_err(|_| anyhow!("invalid minute in time '{}'", input))?;
+    if h > 23 || m > 59 {
+        return Err(anyhow!("time out of range: '{}'", input));
+    }
+    Ok(h * 60 + m)
+}
+
+fn format_hhmm(minutes: u32) -> String {
+    format!("{:02}:{:02}", (minutes / 60) % 24, minutes % 60)
+}
+
+// Date string of conference day `day` (1-based) counted from `start`.
+fn day_date(start: &str, day: u32) -> Result<String> {
+    let parts: Vec<&str> = start.trim().split('-').collect();
+    if parts.len() != 3 {
+        return Err(anyhow!(
+            "invalid event start date '{}', expected YYYY-MM-DD",
+            start
+        ));
+    }
+    let y: i64 = parts[0]
+        .parse()
+        .map_err(|_| anyhow!("invalid year in '{}'", start))?;
+    let m: u32 = parts[1]
+        .parse()
+        .map_err(|_| anyhow!("invalid month in '{}'", start))?;
+    let d: u32 = parts[2]
+        .parse()
+        .map_err(|_| anyhow!("invalid day in '{}'", start))?;
+    if !(1..=12).contains(&m) || d == 0 || d > days_in_month(y, m) {
+        return Err(anyhow!("event start date out of range: '{}'", start));
+    }
+    let (y, m, d) = civil_from_days(days_from_civil(y, m, d) + day.saturating_sub(1) as i64);
+    Ok(format!("{:04}-{:02}-{:02}", y, m, d))
+}
+
+fn session_slot<'a>(event: &'a EventConfig, session: &Session) -> Option<&'a SessionSlot> {
+    event
+        .sessions
+        .get(&session.id)
+        .or_else(|| event.sessions.get(&session_slug(session)))
+}
+
+struct ScheduledEvent<'a> {
+    abs: &'a Abstract,
+    session: &'a Session,
+    start: u32,
+    duration: u32,
+}
+
+// Lay out sessions on days and rooms. Each (day, room) pair keeps its own clock
+// so sessions in different rooms run in parallel.
+fn schedule<'a>(
+    abstracts: &'a HashMap<String, Abstract>,
+    sessions: &'a [Session],
+    event: &EventConfig,
+) -> Result<BTreeMap<u32, BTreeMap<String, Vec<ScheduledEvent<'a>>>>> {
+    let day_start = parse_hhmm(&event.day_start)?;
+    let mut days: BTreeMap<u32, BTreeMap<String, Vec<ScheduledEvent<'a>>>> = BTreeMap::new();
+    let mut clocks: HashMap<(u32, String), u32> = HashMap::new();
+
+    for session in sessions.iter() {
+        let slot = session_slot(event, session);
+        let day = slot.and_then(|s| s.day).unwrap_or(1).max(1);
+        let room = slot
+            .and_then(|s| s.room.clone())
+            .unwrap_or_else(|| event.default_room.clone());
+        let duration = slot
+            .and_then(|s| s.slot_minutes)
+            .unwrap_or(event.slot_minutes)
+            .max(1);
+        let clock = clocks.entry((day, room.clone())).or_insert(day_start);
+        if let Some(start) = slot.and_then(|s| s.start.as_deref()) {
+            *clock = parse_hhmm(start)?;
+        }
+
+        let mut items = session.items.clone();
+        items.sort_by_key(|i| i.order);
+        for item in items.iter() {
+            let abs = abstracts
+                .get(&item.id)
+                .ok_or_else(|| anyhow!("Referenced abstract {} not found", item.id))?;
+            days.entry(day)
+                .or_default()
+                .entry(room.clone())
+                .or_default()
+                .push(ScheduledEvent {
+                    abs,
+                    session,
+                    start: *clock,
+                    duration,
+                });
+            *clock += duration;
+        }
+    }
+    Ok(days)
+}
+
+// Render the frab/Pentabarf `schedule.xml` used by Giggity, Fahrplan, ConfClerk etc.
+pub fn render_frab(
+    abstracts: &HashMap<String, Abstract>,
+    sessions: &[Session],
+    event: &EventConfig,
+) -> Result<String> {
+    let days = schedule(abstracts, sessions, event)?;
+    let num_days = days.keys().max().copied().unwrap_or(1);
+    let offset = event.utc_offset.trim();
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<schedule>\n");
+    out.push_str(&format!(
+        "  <generator name=\"symposium-booklet\" version=\"{}\"/>\n",
+        env!("CARGO_PKG_VERSION")
+    ));
+    out.push_str(&format!("  <version>{}</version>\n", xml_escape(&event.id)));
+    out.push_str("  <conference>\n");
+    out.push_str(&format!(
+        "    <acronym>{}</acronym>\n",
+        xml_escape(&event.acronym)
+    ));
+    out.push_str(&format!(
+        "    <title>{}</title>\n",
+        xml_escape(&event.title)
+    ));
+    out.push_str(&format!(
+        "    <start>{}</start>\n",
+        day_date(&event.start, 1)?
+    ));
+    out.push_str(&format!(
+        "    <end>{}</end>\n",
+        day_date(&event.start, num_days)?
+    ));
+    out.push_str(&format!("    <days>{}</days>\n", num_days));
+    out.push_str(&format!(
+        "    <timeslot_duration>{}</timeslot_duration>\n",
+        format_hhmm(event.slot_minutes)
+    ));
+    if let Some(url) = &event.url {
+        out.push_str(&format!("    <base_url>{}</base_url>\n", xml_escape(url)));
+    }
+    out.push_str(&format!(
+        "    <time_zone_name>{}</time_zone_name>\n",
+        xml_escape(&event.time_zone)
+    ));
+    out.push_str("  </conference>\n");
+
+    let mut event_id = 0u32;
+    let mut person_ids: HashMap<String, usize> = HashMap::new();
+    for day in 1..=num_days {
+        let date = day_date(&event.start, day)?;
+        let rooms = days.get(&day);
+        let (day_begin, day_end) = rooms
+            .map(|rooms| {
+                let begin = rooms
+                    .values()
+                    .flat_map(|evs| evs.iter().map(|e| e.start))
+                    .min()
+                    .unwrap_or(0);
+                let end = rooms
+                    .values()
+                    .flat_map(|evs| evs.iter().map(|e| e.start + e.duration))
+                    .max()
+                    .unwrap_or(0);
+                (begin, end)
+            })
+            .unwrap_or((0, 0));
+        out.push_str(&format!(
+            "  <day index=\"{}\" date=\"{}\" start=\"{}T{}:00{}\" end=\"{}T{}:00{}\">\n",
+            day,
+            date,
+            date,
+            format_hhmm(day_begin),
+            offset,
+            date,
+            format_hhmm(day_end),
+            offset
+        ));
+        for (room, events) in rooms.into_iter().flatten() {
+            out.push_str(&format!(
+                "    <room name=\"{}\" guid=\"{}\">\n",
+                xml_escape(room),
+                stable_guid(&[&event.id, "room", room])
+            ));
+            for ev in events.iter() {
+                event_id += 1;
+                let abs = ev.abs;
+                let slug = format!(
+                    "{}-{}-{}",
+                    event.acronym,
+                    event_id,
+                    slug::slugify(&abs.title)
+                );
+                out.push_str(&format!(
+                    "      <event guid=\"{}\" id=\"{}\">\n",
+                    stable_guid(&[&event.id, "event", &abs.id]),
+                    event_id
+                ));
+                out.push_str(&format!(
+                    "        <date>{}T{}:00{}</date>\n",
+                    date,
+                    format_hhmm(ev.start),
+                    offset
+                ));
+                out.push_str(&format!(
+                    "        <start>{}</start>\n",
+                    format_hhmm(ev.start)
+                ));
+                out.push_str(&format!(
+                    "        <duration>{}</duration>\n",
+                    format_hhmm(ev.duration)
+                ));
+                out.push_str(&format!("        <room>{}</room>\n", xml_escape(room)));
+                out.push_str(&format!("        <slug>{}</slug>\n", xml_escape(&slug)));
+                out.push_str(&format!(
+                    "        <title>{}</title>\n",
+                    xml_escape(&abs.title)
+                ));
+                out.push_str("        <subtitle/>\n");
+                out.push_str(&format!(
+                    "        <track>{}</track>\n",
+                    xml_escape(&ev.session.title)
+                ));
+                out.push_str("        <type>Talk</type>\n");
+                out.push_str(&format!(
+                    "        <language>{}</language>\n",
+                    xml_escape(&abs.locale)
+                ));
+                out.push_str(&format!(
+                    "        <abstract>{}</abstract>\n",
+                    xml_escape(&crate::richtext::plain_body(
+                        &abs.abstract_text,
+                        &abs.abstract_sections
+                    ))
+                ));
+                match &abs.take_home {
+                    Some(take_home) => out.push_str(&format!(
+                        "        <description>{}</description>\n",
+                        xml_escape(take_home)
+                    )),
+                    None => out.push_str("        <description/>\n"),
+                }
+                out.push_str("        <persons>\n");
+                for author in abs.authors.iter() {
+                    let next_id = person_ids.len() + 1;
+                    let pid = *person_ids.entry(author.clone()).or_insert(next_id);
+                    out.push_str(&format!(
+                        "          <person id=\"{}\">{}</person>\n",
+                        pid,
+                        xml_escape(author)
+                    ));
+                }
+                out.push_str("        </persons>\n");
+                out.push_str("        <links/>\n");
+                out.push_str("        <attachments/>\n");
+                out.push_str("      </event>\n");
+            }
+            out.push_str("    </room>\n");
+        }
+        out.push_str("  </day>\n");
+    }
+    out.push_str("</schedule>\n");
+    Ok(out)
+}
+
+pub fn write_frab(
+    abstracts: &HashMap<String, Abstract>,
+    sessions: &[Session],
+    outdir: &str,
+    event: &EventConfig,
+) -> Result<()> {
+    create_dir_all(outdir)?;
+    let xml = render_frab(abstracts, sessions, event)?;
+    let path = Path::new(outdir).join(SCHEDULE_FILE);
+    let mut f = File::create(&path)
+        .map_err(|e| anyhow!("Failed to create file {}: {}", path.display(), e))?;
+    write!(f, "{}", xml)?;
+    tracing::info!("Wrote frab schedule to {}", path.display());
+    Ok(())
+}
+
+pub fn write_frab_plan(
+    abstracts: &HashMap<String, Abstract>,
+    sessions: &[Session],
+    outdir: &str,
+    event: &EventConfig,
+    plan: &mut crate::io::plan::Plan,
+) -> Result<()> {
+    use crate::io::plan::PlanAction;
+
+    let days = schedule(abstracts, sessions, event)?;
+    let num_events: usize = days
+        .values()
+        .flat_map(|rooms| rooms.values())
+        .map(|evs| evs.len())
+        .sum();
+    plan.push(PlanAction::WriteFile {
+        path: Path::new(outdir).join(SCHEDULE_FILE),
+        summary: format!(
+            "frab schedule — {} day(s), {} event(s)",
+            days.len().max(1),
+            num_events
+        ),
+    });
+    Ok(())
+}
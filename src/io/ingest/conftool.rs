@@ -0,0 +1,134 @@
+// ConfTool "Export Submissions" CSV. Authors usually carry numeric references
+// into the organisations column ("Jane Doe (1); John Smith (2)" with
+// "1: University A; 2: University B"); plain parenthesised affiliations work too.
+use super::{Table, build_abstract, file_name, read_csv};
+use crate::model::Abstract;
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+
+pub fn read_submissions(input: &str) -> Result<Vec<Abstract>> {
+    let table = Table::new(&file_name(input), read_csv(input)?)?;
+    let col_id = table
+        .col(&["paperid", "paper id", "id"])
+        .ok_or_else(|| anyhow!("ConfTool export has no paperID column"))?;
+    let col_title = table.col(&["title"]);
+    let col_authors = table.col(&["authors"]);
+    let col_orgs = table.col(&["organisations", "organizations", "organisation"]);
+    let col_abstract = table.col(&["abstract"]);
+    let col_keywords = table.col(&["keywords"]);
+    let col_track = table.col(&["track", "session_short", "contribution_type"]);
+    let col_status = table.col(&["acceptance_status_name", "acceptance_status", "status"]);
+    let col_email = table.col(&["submitting_author_email", "email"]);
+    let col_language = table.col(&["language"]);
+
+    let mut out = Vec::new();
+    for (idx, row) in table.rows.iter().enumerate() {
+        let id = table.get(row, Some(col_id)).unwrap_or_default();
+        if id.is_empty() {
+            continue;
+        }
+        let orgs = table
+            .get(row, col_orgs)
+            .map(|o| parse_organisations(&o))
+            .unwrap_or_default();
+        let (authors, mut affiliations) = table
+            .get(row, col_authors)
+            .map(|a| parse_authors(&a, &orgs))
+            .unwrap_or_default();
+        if affiliations.is_empty() {
+            affiliations = orgs.values().cloned().collect();
+            affiliations.sort();
+        }
+        let locale = table
+            .get(row, col_language)
+            .map(|l| language_code(&l))
+            .unwrap_or_else(|| "da".to_string());
+        let text = table.get(row, col_abstract).unwrap_or_default();
+        let mut abs = build_abstract(
+            id,
+            table.get(row, col_title).unwrap_or_default(),
+            authors,
+            &affiliations,
+            &text,
+            locale,
+        );
+        abs.contact_email = table.get(row, col_email);
+        abs.keywords = table
+            .get(row, col_keywords)
+            .map(|k| crate::keywords::split(&k))
+            .unwrap_or_default();
+        abs.track = table.get(row, col_track);
+        abs.status = table.get(row, col_status);
+        abs.source = Some(table.source(
+            idx,
+            &[
+                ("title", col_title),
+                ("authors", col_authors),
+                ("abstract", col_abstract),
+                ("keywords", col_keywords),
+            ],
+        ));
+        out.push(abs);
+    }
+    Ok(out)
+}
+
+// "1: University A; 2: University B" -> {"1": "University A", ...}. Unnumbered
+// lists are keyed by position.
+fn parse_organisations(input: &str) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    for (idx, part) in input.split(';').map(|p| p.trim()).enumerate() {
+        if part.is_empty() {
+            continue;
+        }
+        match part.split_once(':') {
+            Some((key, name)) if key.trim().chars().all(|c| c.is_ascii_digit()) => {
+                map.insert(key.trim().to_string(), name.trim().to_string());
+            }
+            _ => {
+                map.insert((idx + 1).to_string(), part.to_string());
+            }
+        }
+    }
+    map
+}
+
+fn parse_authors(input: &str, orgs: &HashMap<String, String>) -> (Vec<String>, Vec<String>) {
+    let separator = if input.contains(';') { ';' } else { ',' };
+    let mut authors = Vec::new();
+    let mut affiliations = Vec::new();
+    for entry in input.split(separator).map(|e| e.trim()) {
+        if entry.is_empty() {
+            continue;
+        }
+        let (name, refs) = match (entry.find('('), entry.rfind(')')) {
+            (Some(open), Some(close)) if close > open => {
+                (entry[..open].trim(), Some(&entry[open + 1..close]))
+            }
+            _ => (entry, None),
+        };
+        authors.push(name.to_string());
+        if let Some(refs) = refs {
+            let keys: Vec<&str> = refs.split(',').map(|r| r.trim()).collect();
+            if keys.iter().all(|k| k.chars().all(|c| c.is_ascii_digit())) {
+                for key in keys {
+                    if let Some(org) = orgs.get(key) {
+                        affiliations.push(org.clone());
+                    }
+                }
+            } else {
+                affiliations.push(refs.trim().to_string());
+            }
+        }
+    }
+    (authors, affiliations)
+}
+
+fn language_code(input: &str) -> String {
+    let low = input.trim().to_lowercase();
+    match low.as_str() {
+        "english" | "engelsk" => "en".to_string(),
+        "danish" | "dansk" => "da".to_string(),
+        _ => low,
+    }
+}
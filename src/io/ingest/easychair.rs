@@ -0,0 +1,210 @@
+// EasyChair exports: the "Download data in Excel" workbook (sheets `Submissions`
+// and `Authors`) or the `submission.csv` / `author.csv` pair.
+use super::{Table, build_abstract, file_name, read_csv};
+use crate::io::excel;
+use crate::model::Abstract;
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+struct Author {
+    name: String,
+    affiliation: Option<String>,
+    email: Option<String>,
+    corresponding: bool,
+}
+
+pub fn read_submissions(input: &str) -> Result<Vec<Abstract>> {
+    let (submissions, authors) = load_tables(input)?;
+    let authors = authors
+        .map(|t| authors_by_submission(&t))
+        .unwrap_or_default();
+
+    let col_id = submissions
+        .col(&["#", "submission #", "id"])
+        .ok_or_else(|| anyhow!("EasyChair export has no '#' column"))?;
+    let col_title = submissions.col(&["title"]);
+    let col_authors = submissions.col(&["authors"]);
+    let col_abstract = submissions.col(&["abstract"]);
+    let col_keywords = submissions.col(&["keywords"]);
+    let col_track = submissions.col(&["track name", "track"]);
+    let col_decision = submissions.col(&["decision"]);
+    let col_deleted = submissions.col(&["deleted?", "deleted"]);
+
+    let mut out = Vec::new();
+    for (idx, row) in submissions.rows.iter().enumerate() {
+        let deleted = submissions
+            .get(row, col_deleted)
+            .map(|d| d.eq_ignore_ascii_case("yes"))
+            .unwrap_or(false);
+        if deleted {
+            continue;
+        }
+        let id = submissions.get(row, Some(col_id)).unwrap_or_default();
+        if id.is_empty() {
+            continue;
+        }
+        let (names, affiliations, email) = match authors.get(&id) {
+            Some(list) => {
+                let names = list.iter().map(|a| a.name.clone()).collect();
+                let affiliations: Vec<String> =
+                    list.iter().filter_map(|a| a.affiliation.clone()).collect();
+                let email = list
+                    .iter()
+                    .find(|a| a.corresponding && a.email.is_some())
+                    .or_else(|| list.iter().find(|a| a.email.is_some()))
+                    .and_then(|a| a.email.clone());
+                (names, affiliations, email)
+            }
+            None => (
+                submissions
+                    .get(row, col_authors)
+                    .map(|a| split_author_list(&a))
+                    .unwrap_or_default(),
+                Vec::new(),
+                None,
+            ),
+        };
+        let text = submissions.get(row, col_abstract).unwrap_or_default();
+        let mut abs = build_abstract(
+            id,
+            submissions.get(row, col_title).unwrap_or_default(),
+            names,
+            &affiliations,
+            &text,
+            "da".to_string(),
+        );
+        abs.contact_email = email;
+        abs.keywords = submissions
+            .get(row, col_keywords)
+            .map(|k| crate::keywords::split(&k))
+            .unwrap_or_default();
+        abs.track = submissions.get(row, col_track);
+        abs.status = submissions.get(row, col_decision);
+        abs.source = Some(submissions.source(
+            idx,
+            &[
+                ("title", col_title),
+                ("authors", col_authors),
+                ("abstract", col_abstract),
+                ("keywords", col_keywords),
+            ],
+        ));
+        out.push(abs);
+    }
+    Ok(out)
+}
+
+// EasyChair's author column reads "A, B and C".
+fn split_author_list(input: &str) -> Vec<String> {
+    input
+        .replace(" and ", ", ")
+        .split(',')
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect()
+}
+
+fn authors_by_submission(table: &Table) -> HashMap<String, Vec<Author>> {
+    let col_sub = table.col(&["submission #", "submission"]);
+    let col_first = table.col(&["first name"]);
+    let col_last = table.col(&["last name"]);
+    let col_aff = table.col(&["affiliation", "organization"]);
+    let col_email = table.col(&["email"]);
+    let col_corr = table.col(&["corresponding?", "corresponding"]);
+    let mut map: HashMap<String, Vec<Author>> = HashMap::new();
+    for row in table.rows.iter() {
+        let Some(sub) = table.get(row, col_sub) else {
+            continue;
+        };
+        let name = [table.get(row, col_first), table.get(row, col_last)]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if name.is_empty() {
+            continue;
+        }
+        map.entry(sub).or_default().push(Author {
+            name,
+            affiliation: table.get(row, col_aff),
+            email: table.get(row, col_email),
+            corresponding: table
+                .get(row, col_corr)
+                .map(|c| c.eq_ignore_ascii_case("yes") || c == "✔")
+                .unwrap_or(false),
+        });
+    }
+    map
+}
+
+fn load_tables(input: &str) -> Result<(Table, Option<Table>)> {
+    let path = Path::new(input);
+    if path.is_dir() {
+        let submission = find_csv(path, "submission")
+            .ok_or_else(|| anyhow!("No submission*.csv found in {}", input))?;
+        let author = find_csv(path, "author");
+        return csv_tables(&submission, author.as_deref());
+    }
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    if ext == "csv" {
+        let author = path.parent().and_then(|dir| find_csv(dir, "author"));
+        return csv_tables(path, author.as_deref());
+    }
+
+    let names = excel::sheet_names(input)?;
+    let find = |sub: &str| {
+        names
+            .iter()
+            .find(|n| n.to_lowercase().contains(sub))
+            .cloned()
+    };
+    let submissions_sheet = find("submission")
+        .or_else(|| names.first().cloned())
+        .ok_or_else(|| anyhow!("Workbook {} has no sheets", input))?;
+    let submissions = Table::new(
+        &submissions_sheet,
+        excel::read_sheet_rows(input, &submissions_sheet)?,
+    )?;
+    let authors = match find("author") {
+        Some(sheet) => Some(Table::new(&sheet, excel::read_sheet_rows(input, &sheet)?)?),
+        None => None,
+    };
+    Ok((submissions, authors))
+}
+
+fn csv_tables(submission: &Path, author: Option<&Path>) -> Result<(Table, Option<Table>)> {
+    let submission = submission.to_string_lossy();
+    let submissions = Table::new(&file_name(&submission), read_csv(&submission)?)?;
+    let authors = match author {
+        Some(p) => {
+            let p = p.to_string_lossy();
+            Some(Table::new(&file_name(&p), read_csv(&p)?)?)
+        }
+        None => None,
+    };
+    Ok((submissions, authors))
+}
+
+fn find_csv(dir: &Path, prefix: &str) -> Option<PathBuf> {
+    let mut found: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            let name = p
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            name.starts_with(prefix) && name.ends_with(".csv")
+        })
+        .collect();
+    found.sort();
+    found.into_iter().next()
+}
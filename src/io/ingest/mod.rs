@@ -0,0 +1,306 @@
+// Ingestors for the native export formats of the abstract submission systems we
+// have used over the years. Each maps its own columns onto `model::Abstract`, so
+// the workbook heuristics in `excel::parse_abstracts_from_rows` stay untouched.
+pub mod conftool;
+pub mod easychair;
+pub mod pretalx;
+
+use crate::config::TextConfig;
+use crate::io::excel;
+use crate::model::{Abstract, ItemRef, Session, SourceRef};
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::read_to_string;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceKind {
+    /// Our own abstracts + grouping workbook(s)
+    #[default]
+    Workbook,
+    /// EasyChair "Download data in Excel" (.xlsx) or submission/author CSV files
+    Easychair,
+    /// ConfTool submissions CSV export
+    Conftool,
+    /// Pretalx JSON submissions export
+    Pretalx,
+}
+
+// Where abstracts and sessions come from. The built-in sources are the
+// `SourceKind`s of `--source`; library users can pass their own to
+// `io::run_build_with`.
+pub trait Ingestor {
+    /// Abstracts by id and the sessions referring to them, in programme order.
+    fn ingest(
+        &self,
+        input: &str,
+        grouping: Option<&str>,
+        text: &TextConfig,
+    ) -> Result<(HashMap<String, Abstract>, Vec<Session>)>;
+}
+
+impl Ingestor for SourceKind {
+    fn ingest(
+        &self,
+        input: &str,
+        grouping: Option<&str>,
+        text: &TextConfig,
+    ) -> Result<(HashMap<String, Abstract>, Vec<Session>)> {
+        parse_raw(*self, input, grouping, text)
+    }
+}
+
+// Parse `input` according to `kind`. Sessions come from the grouping workbook when
+// one is given; exports without a grouping file get one session per track.
+// Submission statuses are normalized (see `filter::normalize_status`).
+pub fn parse_source(
+    kind: SourceKind,
+    input: &str,
+    grouping: Option<&str>,
+    text: &TextConfig,
+) -> Result<(HashMap<String, Abstract>, Vec<Session>)> {
+    ingest(&kind, input, grouping, text)
+}
+
+// `parse_source` with any ingestor.
+pub fn ingest(
+    ingestor: &dyn Ingestor,
+    input: &str,
+    grouping: Option<&str>,
+    text: &TextConfig,
+) -> Result<(HashMap<String, Abstract>, Vec<Session>)> {
+    let (mut abstracts, sessions) = ingestor.ingest(input, grouping, text)?;
+    crate::filter::normalize_statuses(&mut abstracts);
+    Ok((abstracts, sessions))
+}
+
+fn parse_raw(
+    kind: SourceKind,
+    input: &str,
+    grouping: Option<&str>,
+    text: &TextConfig,
+) -> Result<(HashMap<String, Abstract>, Vec<Session>)> {
+    let mut abstracts = match kind {
+        SourceKind::Workbook => {
+            return match grouping {
+                Some(g) => excel::parse_two_workbooks(input, g, text),
+                None => excel::parse_workbook(input, text),
+            };
+        }
+        SourceKind::Easychair => easychair::read_submissions(input)?,
+        SourceKind::Conftool => conftool::read_submissions(input)?,
+        SourceKind::Pretalx => pretalx::read_submissions(input)?,
+    };
+    tracing::info!("Parsed {} submissions from {}", abstracts.len(), input);
+    // rich text is read from xlsx cells only; exports are plain or typed markup
+    if text.markup {
+        for abs in abstracts.iter_mut() {
+            if let Some(warning) = crate::richtext::underscore_warning(&abs.abstract_text) {
+                tracing::warn!("Abstract {}: {}", abs.id, warning);
+            }
+            crate::richtext::apply_markup(abs);
+        }
+    }
+    finish(abstracts, grouping)
+}
+
+fn finish(
+    abstracts: Vec<Abstract>,
+    grouping: Option<&str>,
+) -> Result<(HashMap<String, Abstract>, Vec<Session>)> {
+    let mut abstract_map: HashMap<String, Abstract> = HashMap::new();
+    let mut in_order: Vec<String> = Vec::new();
+    for a in abstracts.into_iter() {
+        if a.id.is_empty() {
+            continue;
+        }
+        if abstract_map.contains_key(&a.id) {
+            return Err(anyhow!("Duplicate abstract id found: {}", a.id));
+        }
+        in_order.push(a.id.clone());
+        abstract_map.insert(a.id.clone(), a);
+    }
+
+    let sessions = match grouping {
+        Some(path) => {
+            tracing::info!("Parsing sessions from {}", path);
+            let rows_b = excel::load_grouping_rows(path)?;
+            excel::parse_sessions_from_rows(&rows_b, &abstract_map)?
+        }
+        None => sessions_by_track(&in_order, &abstract_map),
+    };
+    Ok((abstract_map, sessions))
+}
+
+// One session per track, in order of first appearance; items keep export order.
+fn sessions_by_track(ids: &[String], abstracts: &HashMap<String, Abstract>) -> Vec<Session> {
+    let mut sessions: Vec<Session> = Vec::new();
+    for id in ids.iter() {
+        let track = abstracts
+            .get(id)
+            .and_then(|a| a.track.clone())
+            .unwrap_or_else(|| "(unnamed)".to_string());
+        let idx = match sessions.iter().position(|s| s.id == track) {
+            Some(idx) => idx,
+            None => {
+                sessions.push(Session {
+                    id: track.clone(),
+                    title: track.clone(),
+                    order: sessions.len() as u32 + 1,
+                    items: Vec::new(),
+                });
+                sessions.len() - 1
+            }
+        };
+        let session = &mut sessions[idx];
+        let order = session.items.len() as u32 + 1;
+        session.items.push(ItemRef {
+            id: id.clone(),
+            order,
+        });
+    }
+    sessions
+}
+
+// A header row plus data rows, with column lookup by (case-insensitive) name.
+pub(crate) struct Table {
+    /// Sheet or file name, for `SourceRef`.
+    name: String,
+    names: Vec<String>,
+    header: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// 1-based row number of each data row in the sheet or file.
+    numbers: Vec<usize>,
+}
+
+impl Table {
+    pub fn new(name: &str, mut rows: Vec<Vec<String>>) -> Result<Self> {
+        if rows.is_empty() {
+            return Err(anyhow!("export contains no header row"));
+        }
+        let names: Vec<String> = rows
+            .remove(0)
+            .into_iter()
+            .map(|h| h.trim().trim_start_matches('\u{feff}').to_string())
+            .collect();
+        let header = names.iter().map(|h| h.to_lowercase()).collect();
+        let (numbers, rows) = rows
+            .into_iter()
+            .enumerate()
+            .filter(|(_, r)| r.iter().any(|c| !c.trim().is_empty()))
+            .map(|(i, r)| (i + 2, r))
+            .unzip();
+        Ok(Table {
+            name: name.to_string(),
+            names,
+            header,
+            rows,
+            numbers,
+        })
+    }
+
+    // Source of data row `idx`, with the headers of the given field columns.
+    pub fn source(&self, idx: usize, fields: &[(&str, Option<usize>)]) -> SourceRef {
+        SourceRef {
+            sheet: self.name.clone(),
+            row: self.numbers.get(idx).copied().unwrap_or_default(),
+            columns: fields
+                .iter()
+                .filter_map(|(field, col)| {
+                    let name = self.names.get((*col)?)?;
+                    Some((field.to_string(), name.clone()))
+                })
+                .collect(),
+        }
+    }
+
+    // Exact header match first, then substring match, over the candidate names.
+    pub fn col(&self, names: &[&str]) -> Option<usize> {
+        for name in names {
+            if let Some(idx) = self.header.iter().position(|h| h == name) {
+                return Some(idx);
+            }
+        }
+        for name in names {
+            if let Some(idx) = self.header.iter().position(|h| h.contains(name)) {
+                return Some(idx);
+            }
+        }
+        None
+    }
+
+    pub fn get(&self, row: &[String], col: Option<usize>) -> Option<String> {
+        col.and_then(|c| row.get(c))
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    }
+}
+
+// File name of an export, as `SourceRef::sheet`.
+pub(crate) fn file_name(path: &str) -> String {
+    std::path::Path::new(path)
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
+// Read a CSV export. Exports from Excel-centric tools are often `;` separated, so
+// the delimiter is sniffed from the header line.
+pub(crate) fn read_csv(path: &str) -> Result<Vec<Vec<String>>> {
+    let text = read_to_string(path).map_err(|e| anyhow!("Failed to read {}: {}", path, e))?;
+    let text = text.trim_start_matches('\u{feff}');
+    let first = text.lines().next().unwrap_or("");
+    let delimiter = [b';', b'\t', b',']
+        .into_iter()
+        .max_by_key(|d| first.matches(*d as char).count())
+        .unwrap_or(b',');
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| anyhow!("Failed to parse CSV {}: {}", path, e))?;
+        rows.push(record.iter().map(|c| c.to_string()).collect());
+    }
+    Ok(rows)
+}
+
+pub(crate) fn join_unique(values: &[String]) -> Option<String> {
+    let mut unique: Vec<String> = Vec::new();
+    for v in values {
+        let v = v.trim();
+        if !v.is_empty() && !unique.iter().any(|u| u == v) {
+            unique.push(v.to_string());
+        }
+    }
+    if unique.is_empty() {
+        None
+    } else {
+        Some(unique.join("; "))
+    }
+}
+
+// Build an `Abstract` with the shared section splitting applied to `text`.
+pub(crate) fn build_abstract(
+    id: String,
+    title: String,
+    authors: Vec<String>,
+    affiliations: &[String],
+    text: &str,
+    locale: String,
+) -> Abstract {
+    let (abstract_sections, abstract_text) = excel::abstract_body(text, &locale);
+    Abstract {
+        id,
+        title,
+        authors,
+        affiliation: join_unique(affiliations),
+        abstract_text,
+        abstract_sections,
+        locale,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,113 @@
+// Pretalx submissions export (API `/submissions/` JSON, either a bare array or a
+// paginated object with `results`). Localized fields may be plain strings or
+// `{ "en": ..., "da": ... }` objects.
+use super::{build_abstract, file_name};
+use crate::model::{Abstract, SourceRef};
+use anyhow::{Result, anyhow};
+use serde_json::Value as JsonValue;
+use std::fs::read_to_string;
+
+pub fn read_submissions(input: &str) -> Result<Vec<Abstract>> {
+    let text = read_to_string(input).map_err(|e| anyhow!("Failed to read {}: {}", input, e))?;
+    let json: JsonValue = serde_json::from_str(&text)
+        .map_err(|e| anyhow!("Failed to parse Pretalx JSON {}: {}", input, e))?;
+    let items = json
+        .as_array()
+        .or_else(|| json.get("results").and_then(|r| r.as_array()))
+        .ok_or_else(|| anyhow!("Pretalx export must be an array or contain `results`"))?;
+
+    let mut out = Vec::new();
+    for (idx, item) in items.iter().enumerate() {
+        let id = text_field(item.get("code")).unwrap_or_default();
+        if id.is_empty() {
+            continue;
+        }
+        let locale = text_field(item.get("content_locale")).unwrap_or_else(|| "da".to_string());
+        let mut authors = Vec::new();
+        let mut affiliations = Vec::new();
+        let mut email = None;
+        for speaker in item
+            .get("speakers")
+            .and_then(|s| s.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let name = match speaker {
+                JsonValue::String(s) => Some(s.clone()),
+                _ => text_field(speaker.get("name")),
+            };
+            if let Some(name) = name {
+                authors.push(name);
+            }
+            if let Some(aff) = text_field(speaker.get("affiliation")) {
+                affiliations.push(aff);
+            }
+            if email.is_none() {
+                email = text_field(speaker.get("email"));
+            }
+        }
+        let text = text_field(item.get("abstract"))
+            .or_else(|| text_field(item.get("description")))
+            .unwrap_or_default();
+        let mut abs = build_abstract(
+            id,
+            text_field(item.get("title")).unwrap_or_default(),
+            authors,
+            &affiliations,
+            &text,
+            locale,
+        );
+        abs.contact_email = email;
+        abs.keywords = item
+            .get("tags")
+            .and_then(|t| t.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|t| match t {
+                JsonValue::String(s) => Some(s.clone()),
+                _ => text_field(t.get("tag")),
+            })
+            .collect();
+        abs.track = text_field(item.get("track"));
+        abs.status = text_field(item.get("state"));
+        // the n-th submission of the export, with JSON keys as columns
+        abs.source = Some(SourceRef {
+            sheet: file_name(input),
+            row: idx + 1,
+            columns: [
+                ("title", "title"),
+                ("authors", "speakers"),
+                ("keywords", "tags"),
+            ]
+            .into_iter()
+            .chain([(
+                "abstract",
+                if item.get("abstract").is_some() {
+                    "abstract"
+                } else {
+                    "description"
+                },
+            )])
+            .map(|(f, c)| (f.to_string(), c.to_string()))
+            .collect(),
+        });
+        out.push(abs);
+    }
+    Ok(out)
+}
+
+// A string, number, or localized object (`en` preferred, else the first value).
+fn text_field(value: Option<&JsonValue>) -> Option<String> {
+    let s = match value? {
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Number(n) => n.to_string(),
+        JsonValue::Object(map) => map
+            .get("en")
+            .or_else(|| map.get("name"))
+            .or_else(|| map.values().next())
+            .and_then(|v| text_field(Some(v)))?,
+        _ => return None,
+    };
+    let s = s.trim().to_string();
+    if s.is_empty() { None } else { Some(s) }
+}
@@ -1,10 +1,11 @@
+use crate::config::EventConfig;
 use crate::model::{Abstract, Session};
-use anyhow::{anyhow, Result};
+use anyhow::{Result, anyhow};
 use slug::slugify;
-use std::collections::HashMap;
-use std::fs::{create_dir_all, File};
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, create_dir_all};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 const MAX_SESSION_SLUG_LEN: usize = 60;
 const MAX_TITLE_SLUG_LEN: usize = 80;
@@ -25,51 +26,68 @@ fn yaml_escape(input: &str) -> String {
         .replace('\n', "\\n")
 }
 
-pub fn write_markdown(
-    abstracts: &HashMap<String, Abstract>,
-    sessions: &[Session],
-    outdir: &str,
-) -> Result<()> {
-    // ensure output exists
-    create_dir_all(outdir)?;
+// Directory name for a session: ASCII-only slug of the title, truncated, with a
+// positional fallback when the title has no usable characters.
+pub fn session_slug(session: &Session) -> String {
+    let mut slug = slugify(&session.title);
+    if slug.trim().is_empty() {
+        slug = format!("session-{}", session.order);
+    }
+    // guard characters (extra safety)
+    let slug_safe: String = slug
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    if slug_safe.is_empty() {
+        format!("session-{}", session.order)
+    } else {
+        truncate_slug(&slug_safe, MAX_SESSION_SLUG_LEN)
+    }
+}
 
-    let mut manifest_sessions = Vec::new();
+// Where the abstracts go: per session its directory and, in item order, one
+// `NNNN-<title slug>.md` per abstract. Names are unique per directory (sessions
+// with the same slug share one), also against `.md` files already on disk when
+// `existing` is set. Shared by the writer and the dry-run plan.
+pub struct SessionFiles<'a> {
+    pub session: &'a Session,
+    pub dir: PathBuf,
+    /// Markdown file, abstract and item order.
+    pub files: Vec<(PathBuf, &'a Abstract, u32)>,
+}
 
+fn layout<'a>(
+    abstracts: &'a HashMap<String, Abstract>,
+    sessions: &'a [Session],
+    outdir: &str,
+    existing: bool,
+) -> Result<Vec<SessionFiles<'a>>> {
+    let mut used: HashMap<PathBuf, HashSet<String>> = HashMap::new();
+    let mut layout = Vec::new();
     for session in sessions.iter() {
-        // build a safe slug for the session directory
-        let mut slug = slugify(&session.title);
-        if slug.trim().is_empty() {
-            slug = format!("session-{}", session.order);
+        let dir = Path::new(outdir).join(session_slug(session));
+        if !used.contains_key(&dir) {
+            let mut names = HashSet::new();
+            if existing && dir.is_dir() {
+                for entry in std::fs::read_dir(&dir)? {
+                    let path = entry?.path();
+                    if path.extension().and_then(|s| s.to_str()) != Some("md") {
+                        continue;
+                    }
+                    if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                        names.insert(stem.to_string());
+                    }
+                }
+            }
+            used.insert(dir.clone(), names);
         }
-        // guard characters (extra safety)
-        let slug_safe: String = slug
-            .chars()
-            .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
-            .collect();
-        let session_slug = if slug_safe.is_empty() {
-            format!("session-{}", session.order)
-        } else {
-            truncate_slug(&slug_safe, MAX_SESSION_SLUG_LEN)
-        };
-
-        let session_dir = Path::new(outdir).join(&session_slug);
-        create_dir_all(&session_dir)?;
+        let used_names = used.get_mut(&dir).expect("inserted above");
 
         // sort items by order
         let mut items = session.items.clone();
         items.sort_by_key(|i| i.order);
 
-        let mut used_names: std::collections::HashSet<String> = std::collections::HashSet::new();
-        for entry in std::fs::read_dir(&session_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) != Some("md") {
-                continue;
-            }
-            if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                used_names.insert(stem.to_string());
-            }
-        }
+        let mut files = Vec::new();
         for (idx, item) in items.iter().enumerate() {
             let abs = abstracts
                 .get(&item.id)
@@ -89,21 +107,47 @@ pub fn write_markdown(
                 format!("{:04}-{}", idx + 1, title_slug)
             };
 
-            // ensure filename uniqueness within session and vs existing files by
-            // appending `-1`, `-2`, ... when collisions are detected
+            // ensure filename uniqueness within the directory by appending
+            // `-1`, `-2`, ... when collisions are detected
             let mut candidate = filename_base.clone();
             let mut suffix: u32 = 0;
-            loop {
-                if !used_names.contains(&candidate) {
-                    break;
-                }
+            while used_names.contains(&candidate) {
                 suffix += 1;
                 candidate = format!("{}-{}", filename_base, suffix);
             }
             used_names.insert(candidate.clone());
 
-            let path = session_dir.join(format!("{}.md", candidate));
+            files.push((dir.join(format!("{}.md", candidate)), abs, item.order));
+        }
+        layout.push(SessionFiles {
+            session,
+            dir,
+            files,
+        });
+    }
+    Ok(layout)
+}
+
+pub fn write_markdown(
+    abstracts: &HashMap<String, Abstract>,
+    sessions: &[Session],
+    outdir: &str,
+    event: &EventConfig,
+) -> Result<()> {
+    // ensure output exists
+    create_dir_all(outdir)?;
 
+    let mut manifest_sessions = Vec::new();
+
+    for SessionFiles {
+        session,
+        dir,
+        files,
+    } in layout(abstracts, sessions, outdir, true)?
+    {
+        create_dir_all(&dir)?;
+
+        for (path, abs, order) in files {
             let mut f = File::create(&path)
                 .map_err(|e| anyhow!("Failed to create file {}: {}", path.display(), e))?;
             // write yaml frontmatter
@@ -118,7 +162,7 @@ pub fn write_markdown(
                 writeln!(f, "affiliation: \"{}\"", yaml_escape(aff))?;
             }
             writeln!(f, "session: \"{}\"", yaml_escape(&session.title))?;
-            writeln!(f, "order: {}", item.order)?;
+            writeln!(f, "order: {}", order)?;
             writeln!(f, "locale: \"{}\"", yaml_escape(&abs.locale))?;
             if !abs.keywords.is_empty() {
                 writeln!(f, "keywords:")?;
@@ -129,6 +173,12 @@ pub fn write_markdown(
             if let Some(take_home) = &abs.take_home {
                 writeln!(f, "take_home: \"{}\"", yaml_escape(take_home))?;
             }
+            if let Some(reference) = &abs.reference {
+                writeln!(f, "reference: \"{}\"", yaml_escape(reference))?;
+            }
+            if abs.abstract_sections.iter().any(|s| s.rich.is_some()) {
+                writeln!(f, "markup: true")?;
+            }
             if !abs.abstract_sections.is_empty() {
                 writeln!(f, "sections:")?;
                 for section in abs.abstract_sections.iter() {
@@ -136,6 +186,27 @@ pub fn write_markdown(
                     writeln!(f, "    text: \"{}\"", yaml_escape(&section.text))?;
                 }
             }
+            if !abs.variants.is_empty() {
+                writeln!(f, "variants:")?;
+                for (locale, variant) in abs.variants.iter() {
+                    writeln!(f, "  \"{}\":", yaml_escape(locale))?;
+                    writeln!(f, "    title: \"{}\"", yaml_escape(&variant.title))?;
+                    writeln!(f, "    sections:")?;
+                    for section in variant.abstract_sections.iter() {
+                        writeln!(f, "      - label: \"{}\"", yaml_escape(&section.label))?;
+                        writeln!(f, "        text: \"{}\"", yaml_escape(&section.text))?;
+                    }
+                }
+            }
+            if let Some(source) = &abs.source {
+                writeln!(f, "source:")?;
+                writeln!(f, "  sheet: \"{}\"", yaml_escape(&source.sheet))?;
+                writeln!(f, "  row: {}", source.row)?;
+                writeln!(f, "  columns:")?;
+                for (field, column) in source.columns.iter() {
+                    writeln!(f, "    {}: \"{}\"", field, yaml_escape(column))?;
+                }
+            }
             writeln!(f, "---\n")?;
 
             // write body
@@ -145,7 +216,7 @@ pub fn write_markdown(
         manifest_sessions.push(serde_json::json!({
             "id": session.id,
             "title": session.title,
-            "slug": slug,
+            "slug": session_slug(session),
             "order": session.order,
             "count": session.items.len()
         }));
@@ -153,7 +224,15 @@ pub fn write_markdown(
 
     // write manifest file
     let manifest = serde_json::json!({
-        "event": "symposium-2026",
+        "event": event.id,
+        "event_details": {
+            "id": event.id,
+            "title": event.title,
+            "acronym": event.acronym,
+            "start": event.start,
+            "time_zone": event.time_zone,
+            "url": event.url,
+        },
         "sessions": manifest_sessions
     });
     let mf = Path::new(outdir).join("manifest.json");
@@ -163,6 +242,32 @@ pub fn write_markdown(
     Ok(())
 }
 
+// The layout `write_markdown` writes to a fresh output, relative to the output
+// directory.
+pub fn markdown_files<'a>(
+    abstracts: &'a HashMap<String, Abstract>,
+    sessions: &'a [Session],
+) -> Result<Vec<SessionFiles<'a>>> {
+    layout(abstracts, sessions, "", false)
+}
+
+// Record in manifest.json that this output holds only a subset of the booklet, so
+// later steps (PDF naming, `emit-typst`) never mistake it for the full build.
+pub fn mark_partial(outdir: &str, subset: &crate::filter::Subset) -> Result<()> {
+    let mf = Path::new(outdir).join("manifest.json");
+    let txt = std::fs::read_to_string(&mf)?;
+    let mut manifest: serde_json::Value = serde_json::from_str(&txt)?;
+    manifest["partial"] = serde_json::json!({
+        "label": subset.label(),
+        "sessions": subset.sessions,
+        "ids": subset.ids,
+        "keywords": subset.keywords,
+    });
+    let mut f = File::create(mf)?;
+    write!(f, "{}", serde_json::to_string_pretty(&manifest)?)?;
+    Ok(())
+}
+
 // produce a Plan of filesystem actions without performing writes
 pub fn write_markdown_plan(
     abstracts: &HashMap<String, Abstract>,
@@ -171,70 +276,26 @@ pub fn write_markdown_plan(
     plan: &mut crate::io::plan::Plan,
 ) -> Result<()> {
     use crate::io::plan::PlanAction;
-    use std::path::PathBuf;
 
     // ensure output dir would exist
     plan.push(PlanAction::CreateDir {
         path: PathBuf::from(outdir),
     });
 
-    for session in sessions.iter() {
-        let mut slug = slugify(&session.title);
-        if slug.trim().is_empty() {
-            slug = format!("session-{}", session.order);
-        }
-        let slug_safe: String = slug
-            .chars()
-            .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
-            .collect();
-        let session_slug = if slug_safe.is_empty() {
-            format!("session-{}", session.order)
-        } else {
-            truncate_slug(&slug_safe, MAX_SESSION_SLUG_LEN)
-        };
-
-        let session_dir = Path::new(outdir).join(&session_slug);
-        plan.push(PlanAction::CreateDir {
-            path: session_dir.clone(),
-        });
+    for SessionFiles {
+        session,
+        dir,
+        files,
+    } in layout(abstracts, sessions, outdir, false)?
+    {
+        plan.push(PlanAction::CreateDir { path: dir });
 
-        let mut items = session.items.clone();
-        items.sort_by_key(|i| i.order);
-
-        let mut used_names: std::collections::HashSet<String> = std::collections::HashSet::new();
-        for (idx, item) in items.iter().enumerate() {
-            let abs = abstracts
-                .get(&item.id)
-                .ok_or_else(|| anyhow!(format!("Referenced abstract {} not found", item.id)))?;
-            let mut title_slug = slugify(&abs.title);
-            if title_slug.trim().is_empty() {
-                title_slug = abs.id.clone();
-            }
-            let title_safe: String = title_slug
-                .chars()
-                .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
-                .collect();
-            let title_slug = truncate_slug(&title_safe, MAX_TITLE_SLUG_LEN);
-            let filename_base = if title_slug.is_empty() {
-                format!("{:04}", idx + 1)
-            } else {
-                format!("{:04}-{}", idx + 1, title_slug)
-            };
-
-            let mut candidate = filename_base.clone();
-            let mut suffix: u32 = 0;
-            loop {
-                if !used_names.contains(&candidate) {
-                    break;
-                }
-                suffix += 1;
-                candidate = format!("{}-{}", filename_base, suffix);
-            }
-            used_names.insert(candidate.clone());
-
-            let path = session_dir.join(format!("{}.md", candidate));
+        for (path, abs, _) in files {
             // produce a short summary for plan
-            let summary = format!("{} — locale:{}", abs.title, abs.locale);
+            let mut summary = format!("{} — locale:{}", abs.title, abs.locale);
+            for locale in abs.variants.keys() {
+                summary.push_str(&format!("+{}", locale));
+            }
             plan.push(PlanAction::WriteFile { path, summary });
         }
 
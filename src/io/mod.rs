@@ -1,12 +1,52 @@
+pub mod cache;
 pub mod excel;
+pub mod frab;
+pub mod ingest;
 pub mod markdown;
 pub mod plan;
+pub mod serve;
+pub mod watch;
+pub mod xlsx_rich;
 
 use crate::cli::BuildOpts;
+use crate::config::Config;
+use crate::model::{Abstract, Session};
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
-pub fn run_build(opts: BuildOpts) -> Result<()> {
+// The programme a build was made from: the parsed input after the status
+// filter, the proofing subset and the ordering (see `select`).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Selection {
+    pub abstracts: HashMap<String, Abstract>,
+    pub sessions: Vec<Session>,
+    /// Abstracts left out by `[status] include`.
+    pub excluded: usize,
+    pub subset: crate::filter::Subset,
+    /// Non-fatal findings about the input (see `validation::model_warnings`).
+    pub warnings: Vec<String>,
+}
+
+impl Selection {
+    /// Filter label of a partial (subset) build.
+    pub fn partial(&self) -> Option<String> {
+        (!self.subset.is_empty()).then(|| self.subset.label())
+    }
+}
+
+pub fn run_build(opts: BuildOpts) -> Result<Selection> {
+    run_build_with(&opts, &opts.source, &crate::render::Registry::default())
+}
+
+// `build` reading the input with `ingestor` and writing the selected formats
+// of `registry`, for library users with their own sources or renderers.
+pub fn run_build_with(
+    opts: &BuildOpts,
+    ingestor: &dyn ingest::Ingestor,
+    registry: &crate::render::Registry,
+) -> Result<Selection> {
     // if user passed an option to emit parse JSON, handle it here
     if opts.dry_run {
         tracing::info!("Dry run: validating input {}", opts.input);
@@ -14,11 +54,34 @@ pub fn run_build(opts: BuildOpts) -> Result<()> {
         tracing::info!("Building with input={} output={}", opts.input, opts.output);
     }
 
-    // validate input (parse + reference checks)
-    crate::validation::validate_input(&opts.input)?;
+    let config = crate::config::load_config(opts.config.as_deref())?;
+    let renderers = registry.select(&crate::render::format_names(opts, &config))?;
+
+    // an unchanged rebuild has nothing to do, not even parsing the input
+    let cache_key = if opts.dry_run || opts.no_cache || opts.emit_parse_json {
+        None
+    } else {
+        Some(cache::build_key(opts, &config, &renderers)?)
+    };
+    if let Some(selection) = cache_key
+        .as_deref()
+        .and_then(|key| cache::cached_build(&opts.output, key))
+    {
+        tracing::info!(
+            "Output in {} is up to date (build cache); pass --no-cache to rebuild",
+            opts.output
+        );
+        return Ok(selection);
+    }
 
-    // parse excel (again to obtain values for the build path)
-    let (abstracts, sessions) = excel::parse_workbook(&opts.input)?;
+    // parse and validate input (reference checks)
+    let (mut abstracts, mut sessions) = ingest::ingest(
+        ingestor,
+        &opts.input,
+        opts.grouping.as_deref(),
+        &config.text,
+    )?;
+    let warnings = crate::validation::validate_model(&abstracts, &sessions, &config)?;
 
     // In dry-run mode, collect a plan of actions instead of writing files
     let mut plan = plan::Plan::default();
@@ -35,23 +98,68 @@ pub fn run_build(opts: BuildOpts) -> Result<()> {
         }))?;
         std::fs::write(&manifest_path, json)?;
         tracing::info!("Wrote parse JSON to {}", manifest_path.display());
-        return Ok(());
+        return Ok(Selection {
+            abstracts,
+            sessions,
+            excluded: 0,
+            subset: Default::default(),
+            warnings,
+        });
+    }
+
+    // status filter, proofing subset (--only-session/--only-id/--keyword) and
+    // item order, decided once before any writer runs
+    let (excluded, subset) = select(opts, &config, &mut abstracts, &mut sessions)?;
+    if excluded > 0 {
+        tracing::info!(
+            "Excluded {} abstract(s) by status (included: {})",
+            excluded,
+            config.status.include.join(", ")
+        );
+    }
+    let selection = Selection {
+        abstracts,
+        sessions,
+        excluded,
+        subset,
+        warnings,
+    };
+    let partial = selection.partial();
+    if let Some(label) = &partial {
+        tracing::warn!(
+            "Partial build ({}): {} abstract(s) in {} session(s)",
+            label,
+            selection.abstracts.len(),
+            selection.sessions.len()
+        );
     }
 
+    // Typst, LaTeX, HTML, EPUB and DOCX from the same booklet; markdown and
+    // frab from the model
+    let book = crate::booklet::BookOptions {
+        linkify: config.text.linkify && !opts.no_linkify,
+        content: config.content.clone(),
+        index: config.index,
+        session_order: config.ordering.session_order,
+        thesaurus: crate::keywords::Thesaurus::from_config(config.keywords.thesaurus.as_deref())?,
+    };
+    let render =
+        crate::render::RenderContext::new(opts, &config, &book, &selection, partial.as_deref());
+
     if opts.dry_run {
         let outdir = Path::new(&opts.output);
         plan.push(plan::PlanAction::DeleteDir {
             path: outdir.to_path_buf(),
         });
-        // ask markdown writer to produce plan entries
-        markdown::write_markdown_plan(&abstracts, &sessions, &opts.output, &mut plan)?;
-        crate::typst::emit_typst_plan(&opts.output, &opts.locales, &opts.template, &mut plan)?;
+        for (_, renderer) in &renderers {
+            renderer.plan(&render, &mut plan)?;
+        }
 
         // print pretty plan and json to stdout
         println!("DRY-RUN PLAN:\n{}", plan.pretty_print());
         let plan_json = serde_json::to_string_pretty(&plan)?;
         println!("PLAN JSON:\n{}", plan_json);
-        return Ok(());
+        return Ok(selection);
     }
 
     let outdir = Path::new(&opts.output);
@@ -69,17 +177,46 @@ pub fn run_build(opts: BuildOpts) -> Result<()> {
         ));
     }
     if outdir.exists() {
-        std::fs::remove_dir_all(outdir)?;
+        cache::clear_output(outdir)?;
     }
+    std::fs::create_dir_all(outdir)?;
 
-    // write md
-    markdown::write_markdown(&abstracts, &sessions, &opts.output)?;
+    // markdown, frab, Typst (and PDFs), LaTeX, HTML, EPUB, DOCX, ...
+    for (_, renderer) in &renderers {
+        renderer.render(&render)?;
+    }
 
-    // emit typst
-    crate::typst::emit_typst(&opts.output, &opts.locales, &opts.template)?;
+    if let Some(key) = &cache_key {
+        let mut outputs = Vec::new();
+        for locale in crate::util::locales(&opts.locales) {
+            for (_, renderer) in &renderers {
+                outputs.extend(renderer.outputs(&render, locale));
+            }
+        }
+        outputs.sort();
+        outputs.dedup();
+        cache::record_build(&opts.output, key, outputs, &selection)?;
+    }
 
-    // attempt to run typst if available
-    crate::typst::maybe_run_typst(&opts.output, &opts.locales, opts.typst_bin.as_deref())?;
+    Ok(selection)
+}
 
-    Ok(())
+// What goes into the booklet: the status filter, the proofing subset and the
+// item order within sessions. Shared by `build` and `serve`; returns the number
+// of abstracts excluded by status and the subset.
+pub fn select(
+    opts: &BuildOpts,
+    config: &Config,
+    abstracts: &mut HashMap<String, Abstract>,
+    sessions: &mut Vec<Session>,
+) -> Result<(usize, crate::filter::Subset)> {
+    let excluded = crate::filter::apply_status_filter(abstracts, sessions, &config.status.include);
+    let subset = crate::filter::Subset {
+        sessions: opts.only_session.clone(),
+        ids: opts.only_id.clone(),
+        keywords: opts.keyword.clone(),
+    };
+    subset.apply(abstracts, sessions)?;
+    crate::ordering::apply_ordering(abstracts, sessions, &config.ordering);
+    Ok((excluded, subset))
 }
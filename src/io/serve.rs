@@ -0,0 +1,328 @@
+use super::ingest::Ingestor;
+use super::{Selection, watch};
+use crate::cli::{BuildOpts, ServeOpts};
+use crate::model::{Abstract, Session};
+use crate::render::Registry;
+use anyhow::{Context, Result, anyhow};
+use minijinja::syntax::SyntaxConfig;
+use minijinja::value::{Serde, Value};
+use minijinja::{Environment, context, path_loader};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// `serve`: a local preview for reviewers who do not use the terminal. The
+// `watch` loop builds as usual; the programme each build selected is shown as
+// HTML next to the latest PDF per locale. Open pages reload through
+// server-sent events. Page (`templates/serve/`), script and PDFs all come from
+// this process, so it works offline.
+
+pub const SERVE_TEMPLATE_DIR: &str = "templates/serve";
+const PAGE: &str = "serve.html";
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Default, Serialize)]
+struct Programme {
+    generation: u64,
+    // build failure, or input that could not be read
+    error: Option<String>,
+    warnings: Vec<String>,
+    notes: Vec<String>,
+    pdfs: Vec<PdfLink>,
+    sessions: Vec<SessionView>,
+}
+
+#[derive(Debug, Serialize)]
+struct PdfLink {
+    locale: String,
+    file: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SessionView {
+    slug: String,
+    title: String,
+    items: Vec<ItemView>,
+}
+
+#[derive(Debug, Serialize)]
+struct ItemView {
+    id: String,
+    title: String,
+    authors: String,
+    affiliation: Option<String>,
+    locale: String,
+    keywords: Vec<String>,
+    // (label, text); one unlabeled section for a plain abstract
+    sections: Vec<(String, String)>,
+}
+
+// The current programme; waiters on `changed` are event streams.
+#[derive(Default)]
+struct Shared {
+    programme: Mutex<Programme>,
+    changed: Condvar,
+}
+
+pub fn run_serve(opts: ServeOpts) -> Result<()> {
+    run_serve_with(&opts, &opts.watch.build.source, &Registry::default())
+}
+
+// `serve` with any ingestor and registry, as `io::run_build_with`.
+pub fn run_serve_with(
+    opts: &ServeOpts,
+    ingestor: &dyn Ingestor,
+    registry: &Registry,
+) -> Result<()> {
+    let build = opts.watch.build.clone();
+    watch::check_build_opts(&build)?;
+    let listener = TcpListener::bind((opts.bind.as_str(), opts.port))
+        .with_context(|| format!("could not listen on {}:{}", opts.bind, opts.port))?;
+    println!(
+        "Serving the preview at http://{}/ (Ctrl-C to stop)",
+        listener.local_addr()?
+    );
+
+    let shared = Arc::new(Shared::default());
+    {
+        let shared = Arc::clone(&shared);
+        let outdir = build.output.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().filter_map(|s| s.ok()) {
+                let shared = Arc::clone(&shared);
+                let outdir = outdir.clone();
+                thread::spawn(move || {
+                    if let Err(e) = handle(stream, &shared, &outdir) {
+                        tracing::debug!("preview request failed: {:#}", e);
+                    }
+                });
+            }
+        });
+    }
+
+    watch::watch_loop(&opts.watch, ingestor, registry, |outcome| {
+        let mut current = shared.programme.lock().unwrap();
+        let mut next = read_programme(&build, outcome, &mut current);
+        next.generation = current.generation + 1;
+        *current = next;
+        shared.changed.notify_all();
+    })
+}
+
+// The programme of a finished build. A failed build keeps the last programme
+// on show under its error, as `watch` keeps the last good PDFs.
+fn read_programme(
+    opts: &BuildOpts,
+    outcome: &Result<Selection>,
+    last: &mut Programme,
+) -> Programme {
+    let mut programme = Programme::default();
+    programme.error = match outcome {
+        Ok(selection) => fill_programme(opts, selection, &mut programme)
+            .err()
+            .map(|e| format!("{:#}", e)),
+        Err(e) => {
+            programme.sessions = std::mem::take(&mut last.sessions);
+            programme.pdfs = std::mem::take(&mut last.pdfs);
+            Some(format!("{:#}", e))
+        }
+    };
+    programme
+}
+
+fn fill_programme(
+    opts: &BuildOpts,
+    selection: &Selection,
+    programme: &mut Programme,
+) -> Result<()> {
+    let config = crate::config::load_config(opts.config.as_deref())?;
+    programme.warnings = selection.warnings.clone();
+    programme.warnings.extend(crate::validation::label_warnings(
+        &opts.locales,
+        &opts.template,
+    )?);
+
+    if selection.excluded > 0 {
+        programme.notes.push(format!(
+            "{} abstract(s) excluded by status (included: {})",
+            selection.excluded,
+            config.status.include.join(", ")
+        ));
+    }
+    let partial = selection.partial();
+    if let Some(label) = &partial {
+        programme.notes.push(format!("Partial build ({})", label));
+    }
+
+    for locale in crate::util::locales(&opts.locales) {
+        let file = crate::typst::pdf_file_name(locale, partial.as_deref());
+        if Path::new(&opts.output).join(&file).is_file() {
+            programme.pdfs.push(PdfLink {
+                locale: locale.to_string(),
+                file,
+            });
+        }
+    }
+    programme.sessions = session_views(&selection.abstracts, &selection.sessions);
+    Ok(())
+}
+
+fn session_views(abstracts: &HashMap<String, Abstract>, sessions: &[Session]) -> Vec<SessionView> {
+    sessions
+        .iter()
+        .map(|s| SessionView {
+            slug: super::markdown::session_slug(s),
+            title: s.title.clone(),
+            items: s
+                .items
+                .iter()
+                .filter_map(|item| abstracts.get(&item.id))
+                .map(|a| ItemView {
+                    id: a.id.clone(),
+                    title: a.title.clone(),
+                    authors: a.authors.join(", "),
+                    affiliation: a.affiliation.clone(),
+                    locale: a.locale.clone(),
+                    keywords: a.keywords.clone(),
+                    sections: if a.abstract_sections.is_empty() {
+                        vec![(String::new(), a.abstract_text.clone())]
+                    } else {
+                        a.abstract_sections
+                            .iter()
+                            .map(|s| (s.label.clone(), crate::richtext::plain_section(s)))
+                            .collect()
+                    },
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+// One request per connection: `/`, `/view/<locale>`, `/files/<pdf>` and the
+// `/events` stream.
+fn handle(stream: TcpStream, shared: &Shared, outdir: &str) -> Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+    let mut parts = request.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    if method != "GET" {
+        return respond(stream, "405 Method Not Allowed", "text/plain", b"GET only");
+    }
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    match path {
+        "/" => {
+            let html = render(shared, "index", None)?;
+            respond(
+                stream,
+                "200 OK",
+                "text/html; charset=utf-8",
+                html.as_bytes(),
+            )
+        }
+        "/events" => {
+            let since = query
+                .split('&')
+                .find_map(|kv| kv.strip_prefix("since="))
+                .and_then(|v| v.parse().ok());
+            events(stream, shared, since)
+        }
+        _ if path.starts_with("/view/") => {
+            let locale = &path["/view/".len()..];
+            let html = render(shared, "view", Some(locale))?;
+            respond(
+                stream,
+                "200 OK",
+                "text/html; charset=utf-8",
+                html.as_bytes(),
+            )
+        }
+        _ if path.starts_with("/files/") => {
+            // only PDFs at the top of the output directory
+            let name = &path["/files/".len()..];
+            let file = Path::new(outdir).join(name);
+            if name.contains(['/', '\\']) || !name.ends_with(".pdf") || !file.is_file() {
+                return respond(stream, "404 Not Found", "text/plain", b"not found");
+            }
+            respond(stream, "200 OK", "application/pdf", &fs::read(file)?)
+        }
+        _ => respond(stream, "404 Not Found", "text/plain", b"not found"),
+    }
+}
+
+fn respond(mut stream: TcpStream, status: &str, content_type: &str, body: &[u8]) -> Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn render(shared: &Shared, page: &str, locale: Option<&str>) -> Result<String> {
+    let mut env = Environment::new();
+    env.set_syntax(
+        SyntaxConfig::builder()
+            .trim_blocks(true)
+            .lstrip_blocks(true)
+            .build()?,
+    );
+    env.set_loader(path_loader(SERVE_TEMPLATE_DIR));
+    let programme = shared.programme.lock().unwrap();
+    let pdf = locale.and_then(|l| programme.pdfs.iter().find(|p| p.locale == l));
+    env.get_template(PAGE)
+        .map_err(|e| anyhow!("template {}/{}: {:#}", SERVE_TEMPLATE_DIR, PAGE, e))?
+        .render(context! {
+            page => page,
+            locale => locale,
+            pdf => pdf.map(|p| Value::from(Serde(p))),
+            programme => Value::from(Serde(&*programme)),
+        })
+        .map_err(|e| anyhow!("rendering {}/{}: {:#}", SERVE_TEMPLATE_DIR, PAGE, e))
+}
+
+// Server-sent events: a `reload` event once a build newer than `since` (or the
+// one current at connect time) is done, and comments to keep the connection
+// open. Ends when the browser goes away.
+fn events(mut stream: TcpStream, shared: &Shared, since: Option<u64>) -> Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\nConnection: keep-alive\r\n\r\nretry: 1000\n\n"
+    )?;
+    stream.flush()?;
+    let mut seen = since.unwrap_or_else(|| shared.programme.lock().unwrap().generation);
+    loop {
+        let generation = {
+            let programme = shared.programme.lock().unwrap();
+            let (programme, _) = shared
+                .changed
+                .wait_timeout_while(programme, KEEP_ALIVE, |p| p.generation <= seen)
+                .unwrap();
+            programme.generation
+        };
+        if generation > seen {
+            seen = generation;
+            write!(stream, "event: reload\ndata: {}\n\n", generation)?;
+        } else {
+            write!(stream, ": keep-alive\n\n")?;
+        }
+        stream.flush()?;
+    }
+}
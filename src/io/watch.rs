@@ -0,0 +1,271 @@
+use super::ingest::Ingestor;
+use super::{Selection, cache};
+use crate::cli::{BuildOpts, WatchOpts};
+use crate::render::Registry;
+use anyhow::{Result, anyhow};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread::sleep;
+use std::time::{Duration, Instant, SystemTime};
+
+// `watch`: rebuild whenever the input, grouping workbook, config, thesaurus,
+// template (with its locale files) or fonts change. Files are polled, and a
+// burst of saves has to settle for `--debounce` before the rebuild, which
+// covers Excel's save (temp file, delete, rename). Rebuilds go through the
+// build caches, so only the affected sessions and locales are regenerated. A
+// failed rebuild restores the last good PDFs.
+
+const LAST_GOOD: &str = "last-good";
+
+type Snapshot = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+pub fn run_watch(opts: WatchOpts) -> Result<()> {
+    run_watch_with(&opts, &opts.build.source, &Registry::default())
+}
+
+// `watch` with any ingestor and registry, as `io::run_build_with`.
+pub fn run_watch_with(
+    opts: &WatchOpts,
+    ingestor: &dyn Ingestor,
+    registry: &Registry,
+) -> Result<()> {
+    check_build_opts(&opts.build)?;
+    watch_loop(opts, ingestor, registry, |_| {})
+}
+
+pub fn check_build_opts(build: &BuildOpts) -> Result<()> {
+    if build.dry_run || build.emit_parse_json {
+        return Err(anyhow!(
+            "watch writes the output; it cannot be combined with --dry-run or --emit-parse-json"
+        ));
+    }
+    Ok(())
+}
+
+// Build, then rebuild after every settled change until `--max-builds`.
+// `built` sees the outcome of each build.
+pub fn watch_loop(
+    opts: &WatchOpts,
+    ingestor: &dyn Ingestor,
+    registry: &Registry,
+    mut built: impl FnMut(&Result<Selection>),
+) -> Result<()> {
+    let build = &opts.build;
+    let poll = Duration::from_millis(opts.poll.max(10));
+    let debounce = Duration::from_millis(opts.debounce);
+
+    let mut last = snapshot(build, registry);
+    let mut builds = 0;
+    loop {
+        built(&rebuild(build, ingestor, registry));
+        builds += 1;
+        if opts.max_builds.is_some_and(|max| builds >= max) {
+            return Ok(());
+        }
+        println!(
+            "Watching {} path(s) for changes (Ctrl-C to stop)",
+            watched_paths(build, registry).len()
+        );
+        loop {
+            sleep(poll);
+            let now = snapshot(build, registry);
+            if now == last {
+                continue;
+            }
+            let settled = settle(build, registry, now, poll, debounce);
+            let changed = changed_paths(&last, &settled);
+            last = settled;
+            if !Path::new(&build.input).exists() {
+                println!("Waiting for {} to reappear", build.input);
+                continue;
+            }
+            if !changed.is_empty() {
+                println!("Changed: {}", changed.join(", "));
+            }
+            break;
+        }
+    }
+}
+
+fn rebuild(opts: &BuildOpts, ingestor: &dyn Ingestor, registry: &Registry) -> Result<Selection> {
+    let started = Instant::now();
+    let result = super::run_build_with(opts, ingestor, registry);
+    match &result {
+        Ok(_) => {
+            if let Err(e) = keep_last_good(&opts.output) {
+                tracing::warn!("Could not keep a copy of the PDFs: {:#}", e);
+            }
+            println!("Build ok ({:.1}s)", started.elapsed().as_secs_f64());
+        }
+        Err(e) => {
+            println!(
+                "Build failed ({:.1}s): {:#}",
+                started.elapsed().as_secs_f64(),
+                e
+            );
+            match restore_last_good(&opts.output) {
+                Ok(restored) if !restored.is_empty() => {
+                    println!("Kept the last good PDF(s): {}", restored.join(", "))
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Could not restore the last good PDFs: {:#}", e),
+            }
+        }
+    }
+    result
+}
+
+// Files and directories a build reads. The thesaurus and the renderers' own
+// files come from the config as it is now, so pointing the config at other
+// ones is picked up.
+fn watched_paths(opts: &BuildOpts, registry: &Registry) -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from(&opts.input)];
+    paths.extend(opts.grouping.iter().map(PathBuf::from));
+    let mut loaded = crate::config::Config::default();
+    if let Some(config) = &opts.config {
+        paths.push(PathBuf::from(config));
+        if let Ok(config) = crate::config::load_config(Some(config)) {
+            loaded = config;
+        }
+    }
+    paths.extend(loaded.keywords.thesaurus.iter().map(PathBuf::from));
+    paths.push(crate::typst::template::template_location(&opts.template).0);
+    paths.push(crate::typst::font_dir(&opts.template));
+    let names = crate::render::format_names(opts, &loaded);
+    for (_, renderer) in registry.select(&names).unwrap_or_default() {
+        paths.extend(renderer.inputs(&loaded));
+    }
+    paths
+}
+
+// Lock and temp files of spreadsheet programs and editors; Excel's own temp
+// file during a save has an 8-character hex name without extension.
+fn is_scratch(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy())
+        .unwrap_or_default();
+    name.starts_with("~$")
+        || name.starts_with(".~lock.")
+        || name.starts_with(".#")
+        || name.ends_with('~')
+        || name.ends_with(".tmp")
+        || name.ends_with(".swp")
+        || (name.len() == 8
+            && path.extension().is_none()
+            && name.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn snapshot(opts: &BuildOpts, registry: &Registry) -> Snapshot {
+    let output = Path::new(&opts.output);
+    let mut snap = Snapshot::new();
+    for path in watched_paths(opts, registry) {
+        collect(&path, output, &mut snap);
+    }
+    snap
+}
+
+fn collect(path: &Path, output: &Path, snap: &mut Snapshot) {
+    if path.starts_with(output) || is_scratch(path) {
+        return;
+    }
+    let Ok(meta) = fs::metadata(path) else {
+        return;
+    };
+    if meta.is_dir() {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                collect(&entry.path(), output, snap);
+            }
+        }
+    } else {
+        let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        snap.insert(path.to_path_buf(), (modified, meta.len()));
+    }
+}
+
+// Poll until nothing has changed for `debounce`.
+fn settle(
+    opts: &BuildOpts,
+    registry: &Registry,
+    mut current: Snapshot,
+    poll: Duration,
+    debounce: Duration,
+) -> Snapshot {
+    let mut quiet_since = Instant::now();
+    loop {
+        sleep(poll);
+        let next = snapshot(opts, registry);
+        if next != current {
+            current = next;
+            quiet_since = Instant::now();
+        } else if quiet_since.elapsed() >= debounce {
+            return current;
+        }
+    }
+}
+
+fn changed_paths(before: &Snapshot, after: &Snapshot) -> Vec<String> {
+    let mut changed: Vec<String> = after
+        .iter()
+        .filter(|(path, stamp)| before.get(*path) != Some(stamp))
+        .map(|(path, _)| path.display().to_string())
+        .collect();
+    changed.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .map(|path| format!("{} (removed)", path.display())),
+    );
+    changed
+}
+
+// PDFs at the top of the output directory.
+fn pdfs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut pdfs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "pdf"))
+        .collect();
+    pdfs.sort();
+    pdfs
+}
+
+// Copy the PDFs of a successful build to `.cache/last-good/`.
+fn keep_last_good(outdir: &str) -> Result<()> {
+    let dir = cache::cache_dir(outdir).join(LAST_GOOD);
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    let current = pdfs(Path::new(outdir));
+    if current.is_empty() {
+        return Ok(());
+    }
+    fs::create_dir_all(&dir)?;
+    for pdf in current {
+        if let Some(name) = pdf.file_name() {
+            fs::copy(&pdf, dir.join(name))?;
+        }
+    }
+    Ok(())
+}
+
+// Put back the kept PDFs that the failed build removed.
+fn restore_last_good(outdir: &str) -> Result<Vec<String>> {
+    let dir = cache::cache_dir(outdir).join(LAST_GOOD);
+    let mut restored = Vec::new();
+    for pdf in pdfs(&dir) {
+        let Some(name) = pdf.file_name() else {
+            continue;
+        };
+        let target = Path::new(outdir).join(name);
+        if !target.exists() {
+            fs::copy(&pdf, &target)?;
+            restored.push(name.to_string_lossy().into_owned());
+        }
+    }
+    Ok(restored)
+}
@@ -0,0 +1,353 @@
+use crate::richtext::escape_inline;
+use anyhow::{Result, anyhow};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+
+// Rich text runs of xlsx cells. calamine only hands out the plain cell string, so
+// this reads sharedStrings.xml and the worksheet directly and turns formatted
+// runs (bold, italic, underline, super-/subscript) into the inline markup of
+// `richtext`, which the rest of the pipeline already renders.
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Format {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    sup: bool,
+    sub: bool,
+}
+
+impl Format {
+    fn is_plain(&self) -> bool {
+        *self == Format::default()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Run {
+    format: Format,
+    text: String,
+}
+
+fn read_part(archive: &mut zip::ZipArchive<File>, name: &str) -> Result<Option<String>> {
+    let mut file = match archive.by_name(name) {
+        Ok(f) => f,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(anyhow!("failed to read {}: {}", name, e)),
+    };
+    let mut out = String::new();
+    file.read_to_string(&mut out)?;
+    Ok(Some(out))
+}
+
+fn attr(e: &BytesStart, name: &[u8]) -> Option<String> {
+    e.try_get_attribute(name).ok().flatten().and_then(|a| {
+        let raw = std::str::from_utf8(&a.value).ok()?;
+        quick_xml::escape::unescape(raw)
+            .ok()
+            .map(|v| v.into_owned())
+    })
+}
+
+// `<b/>` is on, `<b val="0"/>` is off; `<u val="none"/>` is no underline.
+fn toggle(e: &BytesStart) -> bool {
+    !matches!(
+        attr(e, b"val").as_deref(),
+        Some("0") | Some("false") | Some("none")
+    )
+}
+
+// Collects the runs of one `<si>` / `<is>` element from reader events.
+#[derive(Default)]
+struct RunCollector {
+    runs: Vec<Run>,
+    format: Format,
+    in_props: bool,
+    in_text: bool,
+    in_phonetic: bool,
+    text: String,
+}
+
+impl RunCollector {
+    fn start(&mut self, e: &BytesStart, empty: bool) {
+        match e.local_name().as_ref() {
+            b"r" => self.format = Format::default(),
+            b"rPr" if !empty => self.in_props = true,
+            b"rPh" if !empty => self.in_phonetic = true,
+            b"t" if !empty && !self.in_phonetic => {
+                self.in_text = true;
+                self.text.clear();
+            }
+            b"b" if self.in_props => self.format.bold = toggle(e),
+            b"i" if self.in_props => self.format.italic = toggle(e),
+            b"u" if self.in_props => self.format.underline = toggle(e),
+            b"vertAlign" if self.in_props => {
+                let val = attr(e, b"val").unwrap_or_default();
+                self.format.sup = val == "superscript";
+                self.format.sub = val == "subscript";
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, name: &[u8]) {
+        match name {
+            b"rPr" => self.in_props = false,
+            b"rPh" => self.in_phonetic = false,
+            b"t" if self.in_text => {
+                self.in_text = false;
+                self.runs.push(Run {
+                    format: self.format,
+                    text: std::mem::take(&mut self.text),
+                });
+            }
+            b"r" => self.format = Format::default(),
+            _ => {}
+        }
+    }
+
+    fn event(&mut self, ev: &Event) -> Result<()> {
+        match ev {
+            Event::Start(e) => self.start(e, false),
+            Event::Empty(e) => self.start(e, true),
+            Event::End(e) => self.end(e.local_name().as_ref()),
+            Event::Text(t) if self.in_text => self.text.push_str(&t.xml_content()?),
+            Event::CData(t) if self.in_text => self.text.push_str(&t.xml_content()?),
+            Event::GeneralRef(r) if self.in_text => {
+                if let Some(ch) = r.resolve_char_ref()? {
+                    self.text.push(ch);
+                } else {
+                    let name = r.xml_content()?;
+                    let resolved = quick_xml::escape::resolve_predefined_entity(&name)
+                        .ok_or_else(|| anyhow!("unknown entity &{};", name))?;
+                    self.text.push_str(resolved);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn take(&mut self) -> Vec<Run> {
+        std::mem::take(self).runs
+    }
+}
+
+fn shared_strings(xml: &str) -> Result<Vec<Vec<Run>>> {
+    let mut reader = Reader::from_str(xml);
+    let mut out = Vec::new();
+    let mut collector = RunCollector::default();
+    loop {
+        let ev = reader.read_event()?;
+        match &ev {
+            Event::Start(e) if e.local_name().as_ref() == b"si" => {
+                collector = RunCollector::default();
+            }
+            Event::End(e) if e.local_name().as_ref() == b"si" => {
+                out.push(std::mem::take(&mut collector.runs));
+            }
+            Event::Empty(e) if e.local_name().as_ref() == b"si" => out.push(Vec::new()),
+            Event::Eof => break,
+            _ => collector.event(&ev)?,
+        }
+    }
+    Ok(out)
+}
+
+// Worksheet part for a sheet name, via workbook.xml and its relationships.
+fn sheet_part(archive: &mut zip::ZipArchive<File>, sheet: &str) -> Result<String> {
+    let workbook =
+        read_part(archive, "xl/workbook.xml")?.ok_or_else(|| anyhow!("xl/workbook.xml missing"))?;
+    let mut reader = Reader::from_str(&workbook);
+    let mut rel_id = None;
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) | Event::Empty(e)
+                if e.local_name().as_ref() == b"sheet"
+                    && attr(&e, b"name").as_deref() == Some(sheet) =>
+            {
+                rel_id = attr(&e, b"r:id");
+                break;
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    let rel_id = rel_id.ok_or_else(|| anyhow!("sheet {} not found in workbook", sheet))?;
+    let rels = read_part(archive, "xl/_rels/workbook.xml.rels")?
+        .ok_or_else(|| anyhow!("xl/_rels/workbook.xml.rels missing"))?;
+    let mut reader = Reader::from_str(&rels);
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) | Event::Empty(e)
+                if e.local_name().as_ref() == b"Relationship"
+                    && attr(&e, b"Id").as_deref() == Some(rel_id.as_str()) =>
+            {
+                let target = attr(&e, b"Target").unwrap_or_default();
+                return Ok(match target.strip_prefix('/') {
+                    Some(abs) => abs.to_string(),
+                    None => format!("xl/{}", target),
+                });
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Err(anyhow!("no worksheet part for sheet {}", sheet))
+}
+
+// "D12" -> (row 11, col 3), zero-based.
+fn cell_position(reference: &str) -> Option<(u32, u32)> {
+    let letters: String = reference
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect();
+    let row: u32 = reference[letters.len()..].parse().ok()?;
+    let col = letters.chars().try_fold(0u32, |acc, c| {
+        Some(acc * 26 + (c.to_ascii_uppercase() as u32 - 'A' as u32 + 1))
+    })?;
+    Some((row.checked_sub(1)?, col.checked_sub(1)?))
+}
+
+// Wrap `core` (no surrounding whitespace) in the delimiters of `format`.
+fn wrap(core: &str, format: Format) -> String {
+    let mut inner = escape_inline(core);
+    if format.sup || format.sub {
+        // script spans cannot contain spaces, so mark each word
+        let delim = if format.sup { "^" } else { "~" };
+        inner = inner
+            .split(' ')
+            .map(|w| {
+                if w.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}{}{}", delim, w, delim)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+    for (on, delim) in [
+        (format.italic, "*"),
+        (format.bold, "**"),
+        (format.underline, "__"),
+    ] {
+        if on {
+            inner = format!("{}{}{}", delim, inner, delim);
+        }
+    }
+    inner
+}
+
+// Markup for a cell's runs; `None` when no run carries formatting, so plain
+// cells keep their text untouched.
+fn runs_to_markup(runs: &[Run]) -> Option<String> {
+    if runs
+        .iter()
+        .all(|r| r.format.is_plain() || r.text.trim().is_empty())
+    {
+        return None;
+    }
+    let mut merged: Vec<Run> = Vec::new();
+    for run in runs {
+        match merged.last_mut() {
+            Some(last) if last.format == run.format => last.text.push_str(&run.text),
+            _ => merged.push(run.clone()),
+        }
+    }
+    let mut out = String::new();
+    for run in merged {
+        if run.format.is_plain() {
+            out.push_str(&escape_inline(&run.text));
+            continue;
+        }
+        for (i, line) in run.text.split('\n').enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            let core = line.trim();
+            if core.is_empty() {
+                out.push_str(line);
+                continue;
+            }
+            let lead = &line[..line.len() - line.trim_start().len()];
+            let trail = &line[line.trim_end().len()..];
+            out.push_str(lead);
+            out.push_str(&wrap(core, run.format));
+            out.push_str(trail);
+        }
+    }
+    Some(out)
+}
+
+// Markup for every cell of `sheet` that has formatted runs, keyed by zero-based
+// (row, column) in sheet coordinates.
+pub fn rich_cells(path: &str, sheet: &str) -> Result<HashMap<(u32, u32), String>> {
+    let file = File::open(path)?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| anyhow!("{} is not an xlsx file: {}", path, e))?;
+    let shared = match read_part(&mut archive, "xl/sharedStrings.xml")? {
+        Some(xml) => shared_strings(&xml)?,
+        None => Vec::new(),
+    };
+    let part = sheet_part(&mut archive, sheet)?;
+    let xml = read_part(&mut archive, &part)?
+        .ok_or_else(|| anyhow!("worksheet part {} missing", part))?;
+
+    let mut out = HashMap::new();
+    let mut reader = Reader::from_str(&xml);
+    let mut cell: Option<((u32, u32), String)> = None;
+    let mut in_value = false;
+    let mut value = String::new();
+    let mut in_inline = false;
+    let mut collector = RunCollector::default();
+    loop {
+        let ev = reader.read_event()?;
+        match &ev {
+            Event::Start(e) if e.local_name().as_ref() == b"c" => {
+                cell = attr(e, b"r")
+                    .and_then(|r| cell_position(&r))
+                    .map(|pos| (pos, attr(e, b"t").unwrap_or_default()));
+            }
+            Event::End(e) if e.local_name().as_ref() == b"c" => cell = None,
+            Event::Start(e) if e.local_name().as_ref() == b"v" => {
+                in_value = true;
+                value.clear();
+            }
+            Event::Text(t) if in_value => value.push_str(&t.xml_content()?),
+            Event::End(e) if e.local_name().as_ref() == b"v" => {
+                in_value = false;
+                if let Some((pos, kind)) = &cell
+                    && kind == "s"
+                    && let Some(markup) = value
+                        .trim()
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|idx| shared.get(idx))
+                        .and_then(|runs| runs_to_markup(runs))
+                {
+                    out.insert(*pos, markup);
+                }
+            }
+            Event::Start(e) if e.local_name().as_ref() == b"is" => {
+                in_inline = true;
+                collector = RunCollector::default();
+            }
+            Event::End(e) if e.local_name().as_ref() == b"is" => {
+                in_inline = false;
+                if let Some((pos, _)) = &cell
+                    && let Some(markup) = runs_to_markup(&collector.take())
+                {
+                    out.insert(*pos, markup);
+                }
+            }
+            Event::Eof => break,
+            _ if in_inline => collector.event(&ev)?,
+            _ => {}
+        }
+    }
+    Ok(out)
+}
@@ -0,0 +1,202 @@
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::path::Path;
+use toml::Value;
+
+// Keyword handling in one place: splitting free-text keyword cells, the match
+// key and `#tag` form, and an optional thesaurus that maps aliases onto one
+// canonical term (with per-locale translations) so "sim" and "Simulation"
+// share an index entry.
+
+// Split a keyword cell on newlines, `;`, `,`, ` - ` and `. `; drops empty
+// parts, trailing full stops and case-insensitive repeats.
+pub fn split(input: &str) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    let normalized = input.replace(" - ", ",").replace(". ", ",");
+    for part in normalized.split(['\n', ';', ',']) {
+        let part = part.trim().trim_end_matches('.').trim();
+        if part.is_empty() || out.iter().any(|k| key(k) == key(part)) {
+            continue;
+        }
+        out.push(part.to_string());
+    }
+    out
+}
+
+// Match key: lowercase, without a leading `#`, `_` read as a space, single spaces.
+pub fn key(keyword: &str) -> String {
+    keyword
+        .trim()
+        .trim_start_matches('#')
+        .replace('_', " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+// Display form under an abstract and in the tag index: `#patient_safety`.
+pub fn tag(keyword: &str) -> String {
+    format!("#{}", key(keyword).replace(' ', "_"))
+}
+
+#[derive(Debug, Clone)]
+struct Term {
+    canonical: String,
+    /// Locale -> term in that language.
+    translations: HashMap<String, String>,
+}
+
+// Thesaurus file (TOML): one table per canonical term; `aliases` lists other
+// spellings, every other key is a translation for that locale:
+//
+//   [simulation]
+//   aliases = ["sim", "simulation training"]
+//   da = "simulationstræning"
+#[derive(Debug, Clone, Default)]
+pub struct Thesaurus {
+    terms: Vec<Term>,
+    by_key: HashMap<String, usize>,
+}
+
+impl Thesaurus {
+    pub fn load(path: &str) -> Result<Self> {
+        if !Path::new(path).exists() {
+            return Err(anyhow!("thesaurus file not found: {}", path));
+        }
+        let txt = read_to_string(path)?;
+        let value: Value = toml::from_str(&txt)
+            .map_err(|e| anyhow!("Failed to parse thesaurus {}: {}", path, e))?;
+        let Value::Table(table) = value else {
+            return Err(anyhow!("thesaurus {}: expected a table", path));
+        };
+        let mut thesaurus = Thesaurus::default();
+        for (canonical, entry) in table {
+            let Value::Table(entry) = entry else {
+                return Err(anyhow!(
+                    "thesaurus {}: [{}] must be a table",
+                    path,
+                    canonical
+                ));
+            };
+            let idx = thesaurus.terms.len();
+            let mut spellings = vec![canonical.clone()];
+            let mut translations = HashMap::new();
+            for (field, value) in entry {
+                match (field.as_str(), value) {
+                    ("aliases", Value::Array(aliases)) => {
+                        for alias in aliases {
+                            let Value::String(alias) = alias else {
+                                return Err(anyhow!(
+                                    "thesaurus {}: aliases of [{}] must be strings",
+                                    path,
+                                    canonical
+                                ));
+                            };
+                            spellings.push(alias);
+                        }
+                    }
+                    (locale, Value::String(term)) => {
+                        spellings.push(term.clone());
+                        translations.insert(locale.to_lowercase().replace('_', "-"), term);
+                    }
+                    (field, _) => {
+                        return Err(anyhow!(
+                            "thesaurus {}: [{}] {} must be a string",
+                            path,
+                            canonical,
+                            field
+                        ));
+                    }
+                }
+            }
+            for spelling in spellings {
+                let k = key(&spelling);
+                match thesaurus.by_key.get(&k) {
+                    Some(&other) if other != idx => {
+                        return Err(anyhow!(
+                            "thesaurus {}: \"{}\" belongs to both [{}] and [{}]",
+                            path,
+                            spelling,
+                            thesaurus.terms[other].canonical,
+                            canonical
+                        ));
+                    }
+                    _ => {
+                        thesaurus.by_key.insert(k, idx);
+                    }
+                }
+            }
+            thesaurus.terms.push(Term {
+                canonical,
+                translations,
+            });
+        }
+        Ok(thesaurus)
+    }
+
+    pub fn from_config(path: Option<&str>) -> Result<Self> {
+        match path {
+            Some(p) => Thesaurus::load(p),
+            None => Ok(Thesaurus::default()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    fn lookup(&self, keyword: &str) -> Option<&Term> {
+        self.by_key.get(&key(keyword)).map(|&i| &self.terms[i])
+    }
+
+    // Term shown for a keyword in a booklet: the locale's translation, else the
+    // canonical term, else the keyword itself.
+    pub fn term(&self, keyword: &str, locale: &str) -> String {
+        let Some(term) = self.lookup(keyword) else {
+            return keyword.trim().to_string();
+        };
+        let locale = locale.to_lowercase().replace('_', "-");
+        let primary = locale.split('-').next().unwrap_or_default();
+        term.translations
+            .get(&locale)
+            .or_else(|| term.translations.get(primary))
+            .unwrap_or(&term.canonical)
+            .clone()
+    }
+
+    // `#tag` forms of an abstract's keywords for a booklet, without repeats.
+    pub fn tags(&self, keywords: &[String], locale: &str) -> Vec<String> {
+        let mut out: Vec<String> = Vec::new();
+        for keyword in keywords.iter().flat_map(|k| split(k)) {
+            let t = tag(&self.term(&keyword, locale));
+            if !out.contains(&t) {
+                out.push(t);
+            }
+        }
+        out
+    }
+
+    // Keywords with no thesaurus entry, with how many abstracts use them.
+    pub fn unmapped<'a>(
+        &self,
+        keywords: impl Iterator<Item = &'a [String]>,
+    ) -> Vec<(String, usize)> {
+        let mut counts: HashMap<String, (String, usize)> = HashMap::new();
+        for list in keywords {
+            let mut seen = Vec::new();
+            for keyword in list.iter().flat_map(|k| split(k)) {
+                let k = key(&keyword);
+                if self.lookup(&keyword).is_some() || seen.contains(&k) {
+                    continue;
+                }
+                seen.push(k.clone());
+                counts.entry(k).or_insert_with(|| (keyword.clone(), 0)).1 += 1;
+            }
+        }
+        let mut out: Vec<(String, usize)> = counts.into_values().collect();
+        out.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| key(&a.0).cmp(&key(&b.0))));
+        out
+    }
+}
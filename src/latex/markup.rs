@@ -0,0 +1,134 @@
+use crate::typst::markup::{Block, Inline, Target};
+
+// The generated body tree (see `typst::markup`) as LaTeX. Text is escaped so a
+// submission cannot run commands or unbalance groups; the Typst functions the
+// tree uses map to their LaTeX commands.
+
+pub fn escape(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for ch in input.chars() {
+        match ch {
+            '\\' => out.push_str("\\textbackslash{}"),
+            '{' | '}' | '$' | '&' | '#' | '_' | '%' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '^' => out.push_str("\\textasciicircum{}"),
+            '~' => out.push_str("\\textasciitilde{}"),
+            // active in some babel languages (Danish, German)
+            '"' => out.push_str("\\textquotedbl{}"),
+            // break hints of long links; LaTeX breaks URLs itself
+            '\u{200B}' => {}
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+// A URL as the first argument of `\href`.
+pub fn url(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for ch in input.chars() {
+        match ch {
+            '\\' => out.push_str("\\%5C"),
+            '{' => out.push_str("\\%7B"),
+            '}' => out.push_str("\\%7D"),
+            '#' | '%' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '\u{200B}' => {}
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+// Escaped text as a makeindex entry: `!`, `@`, `|` and `"` are quoted.
+pub fn index_entry(input: &str) -> String {
+    let mut out = String::new();
+    for ch in escape(input).chars() {
+        if matches!(ch, '!' | '@' | '|' | '"') {
+            out.push('"');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+pub fn blocks(blocks: &[Block]) -> String {
+    let mut out = String::new();
+    for block in blocks {
+        match block {
+            Block::Paragraph(body) => {
+                if body.is_empty() {
+                    continue;
+                }
+                inlines(body, &mut out);
+                out.push_str("\n\n");
+            }
+            Block::List(items) => {
+                out.push_str("\\begin{itemize}\n");
+                for item in items {
+                    // `{}` so text starting with `[` is not an item label
+                    out.push_str("\\item{} ");
+                    inlines(item, &mut out);
+                    out.push('\n');
+                }
+                out.push_str("\\end{itemize}\n\n");
+            }
+        }
+    }
+    out
+}
+
+// Inline content only, for fields printed after a label (take-home, reference).
+pub fn inline_blocks(blocks: &[Block]) -> String {
+    let mut out = String::new();
+    for (i, block) in blocks.iter().enumerate() {
+        if i > 0 {
+            out.push_str("\\newline\n");
+        }
+        match block {
+            Block::Paragraph(body) => inlines(body, &mut out),
+            Block::List(items) => {
+                for (j, item) in items.iter().enumerate() {
+                    if j > 0 {
+                        out.push_str("\\newline\n");
+                    }
+                    out.push_str("-- ");
+                    inlines(item, &mut out);
+                }
+            }
+        }
+    }
+    out
+}
+
+fn inlines(items: &[Inline], out: &mut String) {
+    for item in items {
+        match item {
+            Inline::Text(s) => out.push_str(&escape(s).replace('\n', "\\newline\n")),
+            Inline::Strong(body) => wrap("textbf", body, out),
+            Inline::Link { target, body } => {
+                let Target::Url(target) = target;
+                out.push_str(&format!("\\href{{{}}}{{", url(target)));
+                inlines(body, out);
+                out.push('}');
+            }
+            Inline::Call { func, body } => match func.as_str() {
+                "emph" => wrap("emph", body, out),
+                "underline" => wrap("underline", body, out),
+                "sub" => wrap("textsubscript", body, out),
+                "super" => wrap("textsuperscript", body, out),
+                _ => inlines(body, out),
+            },
+        }
+    }
+}
+
+fn wrap(command: &str, body: &[Inline], out: &mut String) {
+    out.push_str(&format!("\\{}{{", command));
+    inlines(body, out);
+    out.push('}');
+}
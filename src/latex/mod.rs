@@ -0,0 +1,309 @@
+pub mod markup;
+
+use crate::booklet::{AbstractData, BookData};
+use crate::config::LatexConfig;
+use anyhow::{Context, Result, anyhow};
+use markup::{escape, index_entry};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+// LaTeX booklet per locale (`latex/book_<locale>.tex`) from the same book data
+// as the Typst booklet (see `booklet::Booklet`), for partners who typeset in LaTeX.
+// The document class and its options come from `[latex]`; a `.cls` file and
+// the partner's preamble are copied next to the books. Sessions are chapters
+// and abstracts sections with a hyperref label (`abs-<id>`, as in Typst).
+// Keywords and authors go into two imakeidx indexes, sorted by the position
+// the collated Typst index gives them so makeindex keeps the locale's order.
+
+pub const LATEX_DIR: &str = "latex";
+// Name of the copied `[latex] preamble` file, read in with `\input`.
+const PREAMBLE: &str = "preamble.tex";
+
+pub fn tex_file_name(locale: &str) -> String {
+    format!("book_{}.tex", locale)
+}
+
+// Every LaTeX book has the author index, whatever `[index] authors` says.
+pub fn write_latex(outdir: &str, books: &[BookData], config: &LatexConfig) -> Result<()> {
+    let dir = Path::new(outdir).join(LATEX_DIR);
+    fs::create_dir_all(&dir)?;
+
+    let class = match class_file(config) {
+        Some(path) => {
+            let name = path
+                .file_name()
+                .ok_or_else(|| anyhow!("[latex] class: not a file: {}", config.class))?;
+            fs::copy(path, dir.join(name))
+                .with_context(|| format!("copying LaTeX class {}", path.display()))?;
+            path.file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned()
+        }
+        None => config.class.clone(),
+    };
+    if let Some(preamble) = &config.preamble {
+        fs::copy(preamble, dir.join(PREAMBLE))
+            .with_context(|| format!("copying LaTeX preamble {}", preamble))?;
+    }
+
+    for book in books {
+        let path = dir.join(tex_file_name(&book.locale));
+        let tex = document(book, &class, config);
+        fs::write(&path, tex).with_context(|| format!("writing {}", path.display()))?;
+        tracing::info!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+// Dry-run plan entries for the LaTeX files.
+pub fn write_latex_plan(
+    outdir: &str,
+    locales_csv: &str,
+    config: &LatexConfig,
+    plan: &mut crate::io::plan::Plan,
+) -> Result<()> {
+    use crate::io::plan::PlanAction;
+
+    let dir = Path::new(outdir).join(LATEX_DIR);
+    plan.push(PlanAction::CreateDir { path: dir.clone() });
+    if let Some(path) = class_file(config) {
+        plan.push(PlanAction::WriteFile {
+            path: dir.join(path.file_name().unwrap_or_default()),
+            summary: format!("LaTeX class copied from {}", path.display()),
+        });
+    }
+    if let Some(preamble) = &config.preamble {
+        plan.push(PlanAction::WriteFile {
+            path: dir.join(PREAMBLE),
+            summary: format!("LaTeX preamble copied from {}", preamble),
+        });
+    }
+    for locale in crate::util::locales(locales_csv) {
+        plan.push(PlanAction::WriteFile {
+            path: dir.join(tex_file_name(locale)),
+            summary: format!("LaTeX booklet for {}", locale),
+        });
+    }
+    Ok(())
+}
+
+// Files the LaTeX books read besides the book data, for the build cache and
+// `watch`.
+pub fn inputs(config: &LatexConfig) -> Vec<&Path> {
+    class_file(config)
+        .into_iter()
+        .chain(config.preamble.as_deref().map(Path::new))
+        .collect()
+}
+
+// `[latex] class` names a file rather than an installed class.
+fn class_file(config: &LatexConfig) -> Option<&Path> {
+    config
+        .class
+        .ends_with(".cls")
+        .then(|| Path::new(&config.class))
+}
+
+// babel's name for a booklet locale; English for the ones we do not know.
+fn babel_language(locale: &str) -> &'static str {
+    let primary = locale.split(['-', '_']).next().unwrap_or(locale);
+    match primary.to_lowercase().as_str() {
+        "da" => "danish",
+        "sv" => "swedish",
+        "nb" | "no" => "norsk",
+        "nn" => "nynorsk",
+        "de" => "ngerman",
+        "fi" => "finnish",
+        "fr" => "french",
+        "nl" => "dutch",
+        "es" => "spanish",
+        _ => "english",
+    }
+}
+
+fn label<'a>(book: &'a BookData, key: &'a str) -> &'a str {
+    book.labels.get(key).and_then(|v| v.as_str()).unwrap_or(key)
+}
+
+// One line of a title or heading.
+fn heading(text: &str) -> String {
+    escape(&text.replace('\n', " "))
+}
+
+fn document(book: &BookData, class: &str, config: &LatexConfig) -> String {
+    let language = babel_language(&book.locale);
+    let title = book
+        .labels
+        .get("title")
+        .and_then(|v| v.as_str())
+        .filter(|t| !t.is_empty())
+        .unwrap_or(&book.event.title);
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "% {} -- generated by symposium-booklet; edit the class or preamble, not this file.",
+        tex_file_name(&book.locale)
+    );
+    let _ = writeln!(
+        out,
+        "\\documentclass[{}]{{{}}}",
+        config.class_options, class
+    );
+    out.push_str("\\usepackage[T1]{fontenc}\n\\usepackage[utf8]{inputenc}\n");
+    let _ = writeln!(out, "\\usepackage[{}]{{babel}}", language);
+    out.push_str("\\usepackage{imakeidx}\n");
+    let _ = writeln!(
+        out,
+        "\\makeindex[name=tags,title={{{}}}]",
+        heading(label(book, "tag_index_label"))
+    );
+    let _ = writeln!(
+        out,
+        "\\makeindex[name=authors,title={{{}}}]",
+        heading(label(book, "author_index_label"))
+    );
+    if config.preamble.is_some() {
+        out.push_str("\\input{preamble}\n");
+    }
+    // hyperref last; classes without front and back matter still work
+    out.push_str("\\usepackage[hidelinks]{hyperref}\n");
+    out.push_str(
+        "\\providecommand\\frontmatter{}\n\\providecommand\\mainmatter{}\n\\providecommand\\backmatter{}\n",
+    );
+    let _ = writeln!(
+        out,
+        "\\addto\\captions{}{{\\renewcommand\\contentsname{{{}}}}}",
+        language,
+        heading(label(book, "toc_label"))
+    );
+    let _ = writeln!(out, "\\title{{{}}}", heading(title));
+    let _ = writeln!(
+        out,
+        "\\author{{{}}}",
+        book.partial.as_deref().map(heading).unwrap_or_default()
+    );
+    let _ = writeln!(out, "\\date{{{}}}", heading(label(book, "cover_date")));
+    out.push_str(
+        "\n\\begin{document}\n\\frontmatter\n\\maketitle\n\\tableofcontents\n\\mainmatter\n",
+    );
+
+    let entries = index_entries(book);
+    for session in &book.sessions {
+        let _ = write!(
+            out,
+            "\n\\chapter{{{}}}\\label{{session-{}}}\n",
+            heading(&session.title),
+            session.slug
+        );
+        for abs in &session.abstracts {
+            abstract_tex(book, abs, &entries, &mut out);
+        }
+    }
+
+    out.push_str("\n\\backmatter\n");
+    if !book.tag_index.is_empty() {
+        out.push_str("\\printindex[tags]\n");
+    }
+    if !book.author_index.is_empty() {
+        out.push_str("\\printindex[authors]\n");
+    }
+    out.push_str("\\end{document}\n");
+    out
+}
+
+// `\index` commands per abstract label. The sort key is the entry's position
+// in the collated index, so `Ø` files after `Z` in a Danish book.
+fn index_entries(book: &BookData) -> HashMap<&str, Vec<String>> {
+    let tags = book
+        .tag_index
+        .iter()
+        .enumerate()
+        .map(|(i, t)| ("tags", i, &t.tag, &t.abstracts));
+    let authors = book
+        .author_index
+        .iter()
+        .enumerate()
+        .map(|(i, e)| ("authors", i, &e.name, &e.abstracts));
+    let mut entries: HashMap<&str, Vec<String>> = HashMap::new();
+    for (index, position, name, refs) in tags.chain(authors) {
+        for r in refs {
+            entries.entry(r.label.as_str()).or_default().push(format!(
+                "\\index[{}]{{{:04}@{}}}",
+                index,
+                position,
+                index_entry(name)
+            ));
+        }
+    }
+    entries
+}
+
+fn abstract_tex(
+    book: &BookData,
+    abs: &AbstractData,
+    entries: &HashMap<&str, Vec<String>>,
+    out: &mut String,
+) {
+    let title = if abs.title.is_empty() {
+        &abs.id
+    } else {
+        &abs.title
+    };
+    let _ = write!(
+        out,
+        "\n\\section{{{}}}\\label{{{}}}\n",
+        heading(title),
+        abs.label
+    );
+    for entry in entries.get(abs.label.as_str()).into_iter().flatten() {
+        out.push_str(entry);
+        out.push('\n');
+    }
+    let field = |out: &mut String, key: &str, value: &str| {
+        let _ = writeln!(
+            out,
+            "\\noindent\\textbf{{{}:}} {}\\par",
+            heading(label(book, key)),
+            value
+        );
+    };
+    if abs.untranslated {
+        let _ = writeln!(
+            out,
+            "\\noindent\\textit{{{}}}\\par",
+            heading(label(book, "untranslated_label"))
+        );
+    }
+    if !abs.authors.is_empty() {
+        field(out, "authors_label", &escape(&abs.authors.join(", ")));
+    }
+    if !abs.affiliations.is_empty() {
+        field(
+            out,
+            "affiliation_label",
+            &escape(&abs.affiliations.join("; ")),
+        );
+    }
+    for section in &abs.sections {
+        if !section.label.is_empty() {
+            let _ = writeln!(out, "\\subsection*{{{}}}", heading(&section.label));
+        } else {
+            out.push('\n');
+        }
+        out.push_str(&markup::blocks(&section.body));
+    }
+    if let Some(take_home) = &abs.take_home {
+        field(out, "take_home_label", &markup::inline_blocks(take_home));
+    }
+    if let Some(reference) = &abs.reference {
+        field(out, "reference_label", &markup::inline_blocks(reference));
+    }
+    if !abs.tags.is_empty() {
+        let tags: Vec<String> = abs.tags.iter().map(|t| escape(t)).collect();
+        field(out, "tags_label", &tags.join(", "));
+    }
+}
@@ -1,8 +1,21 @@
+pub mod booklet;
 pub mod cli;
+pub mod collate;
+pub mod config;
+pub mod docx;
+pub mod epub;
+pub mod filter;
+pub mod html;
 pub mod io;
+pub mod keywords;
+pub mod latex;
 pub mod log;
 pub mod model;
+pub mod ordering;
+pub mod render;
+pub mod richtext;
 pub mod typst;
+pub mod util;
 pub mod validation;
 
 pub use io::excel::{parse_two_workbooks, parse_workbook};
@@ -1,8 +1,21 @@
+mod booklet;
 mod cli;
+mod collate;
+mod config;
+mod docx;
+mod epub;
+mod filter;
+mod html;
 mod io;
+mod keywords;
+mod latex;
 mod log;
 mod model;
+mod ordering;
+mod render;
+mod richtext;
 mod typst;
+mod util;
 mod validation;
 
 use anyhow::Result;
@@ -13,12 +26,63 @@ fn main() -> Result<()> {
     log::init()?;
     let cli = Cli::parse();
     match cli.command {
-        cli::Commands::Build(opts) => crate::io::run_build(opts),
-        cli::Commands::EmitTypst { output, template, locales, typst_bin } => {
-            crate::typst::emit_typst(&output, &locales, &template)?;
-            crate::typst::maybe_run_typst(&output, &locales, typst_bin.as_deref())?;
+        cli::Commands::Build(opts) => crate::io::run_build(opts).map(drop),
+        cli::Commands::Watch(opts) => crate::io::watch::run_watch(opts),
+        cli::Commands::Serve(opts) => crate::io::serve::run_serve(opts),
+        cli::Commands::EmitTypst {
+            output,
+            template,
+            locales,
+            typst_bin,
+            no_pdf,
+            preview,
+            jobs,
+            no_cache,
+            no_linkify,
+            data_json,
+            config,
+        } => {
+            let config = crate::config::load_config(config.as_deref())?;
+            let book = crate::booklet::BookOptions {
+                linkify: config.text.linkify && !no_linkify,
+                content: config.content,
+                index: config.index,
+                session_order: config.ordering.session_order,
+                thesaurus: crate::keywords::Thesaurus::from_config(
+                    config.keywords.thesaurus.as_deref(),
+                )?,
+            };
+            let emit = crate::typst::EmitOptions {
+                data_json,
+                cache: !no_cache,
+                author_index: config.index.authors,
+            };
+            crate::typst::emit_typst(&output, &locales, &template, &book, &emit)?;
+            let pdf = crate::typst::PdfOptions {
+                typst_bin,
+                no_pdf,
+                previews: preview,
+                template,
+                jobs,
+                cache: !no_cache,
+            };
+            crate::typst::compile_pdfs(&output, &locales, &pdf)?;
+            Ok(())
+        }
+        cli::Commands::Validate {
+            input,
+            source,
+            grouping,
+            config,
+            locales,
+            template,
+        } => {
+            let config = crate::config::load_config(config.as_deref())?;
+            crate::validation::validate_source(source, &input, grouping.as_deref(), &config)?;
+            for warning in crate::validation::label_warnings(&locales, &template)? {
+                tracing::warn!("{}", warning);
+            }
             Ok(())
         }
-        cli::Commands::Validate { input } => crate::validation::validate_input(&input),
     }
 }
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Abstract {
     pub id: String,
     pub title: String,
@@ -15,12 +16,46 @@ pub struct Abstract {
     pub reference: Option<String>,
     pub literature: Option<String>,
     pub locale: String,
+    #[serde(default)]
+    pub track: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    /// The same abstract in other languages (`title_en`, `abstract_en` columns),
+    /// keyed by locale.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variants: BTreeMap<String, AbstractVariant>,
+    /// Where the abstract was read from, for error messages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SourceRef>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// Sheet (or export file), row and column headers of an abstract's input.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SourceRef {
+    pub sheet: String,
+    /// 1-based row as the spreadsheet shows it (CSV: record number).
+    pub row: usize,
+    /// Field (`title`, `authors`, `abstract`, `keywords`, `take_home`,
+    /// `reference`, `title_<locale>`, `abstract_<locale>`) -> column header.
+    #[serde(default)]
+    pub columns: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AbstractVariant {
+    pub title: String,
+    pub abstract_text: String,
+    pub abstract_sections: Vec<AbstractSection>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct AbstractSection {
     pub label: String,
     pub text: String,
+    /// Parsed inline markup when `[text] markup` is enabled; `text` then holds
+    /// its canonical markup form.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rich: Option<crate::richtext::RichText>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
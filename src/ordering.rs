@@ -0,0 +1,107 @@
+use crate::collate;
+use crate::config::{OrderingConfig, SortMode};
+use crate::io::markdown::session_slug;
+use crate::model::{Abstract, Session};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+// Look up a per-session setting by session id, then by slug.
+fn for_session<'a, T>(map: &'a HashMap<String, T>, session: &Session) -> Option<&'a T> {
+    map.get(&session.id)
+        .or_else(|| map.get(&session_slug(session)))
+}
+
+pub fn session_mode(config: &OrderingConfig, session: &Session) -> SortMode {
+    for_session(&config.sessions, session)
+        .copied()
+        .unwrap_or(config.default)
+}
+
+// Surname of the presenting (first) author: the last word of the name part,
+// ignoring a trailing ", title" from the presenter column.
+fn presenting_surname(abs: &Abstract) -> String {
+    abs.authors
+        .first()
+        .map(|a| a.split(',').next().unwrap_or("").trim())
+        .and_then(|name| name.split_whitespace().last())
+        .unwrap_or("")
+        .to_string()
+}
+
+// Natural order for ids, so "a2" sorts before "a10".
+fn compare_ids(a: &str, b: &str) -> Ordering {
+    fn chunks(s: &str) -> Vec<(bool, String)> {
+        let mut out: Vec<(bool, String)> = Vec::new();
+        for ch in s.chars() {
+            let digit = ch.is_ascii_digit();
+            match out.last_mut() {
+                Some((d, buf)) if *d == digit => buf.push(ch),
+                _ => out.push((digit, ch.to_string())),
+            }
+        }
+        out
+    }
+    let (ca, cb) = (chunks(a), chunks(b));
+    for ((da, sa), (db, sb)) in ca.iter().zip(cb.iter()) {
+        let ord = if *da && *db {
+            let na = sa.trim_start_matches('0');
+            let nb = sb.trim_start_matches('0');
+            na.len().cmp(&nb.len()).then_with(|| na.cmp(nb))
+        } else {
+            sa.to_lowercase().cmp(&sb.to_lowercase())
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    ca.len().cmp(&cb.len()).then_with(|| a.cmp(b))
+}
+
+// Reorder the items of every session according to its sort mode and renumber
+// `order` from 1. This is the single place item order is decided; markdown,
+// Typst and the other writers follow `order`.
+pub fn apply_ordering(
+    abstracts: &HashMap<String, Abstract>,
+    sessions: &mut [Session],
+    config: &OrderingConfig,
+) {
+    let locale = config.locale.as_str();
+    for session in sessions.iter_mut() {
+        let mode = session_mode(config, session);
+        session.items.sort_by_key(|i| i.order);
+        let get = |id: &str| abstracts.get(id);
+        match mode {
+            SortMode::Sheet => {}
+            SortMode::Title => session
+                .items
+                .sort_by(|a, b| match (get(&a.id), get(&b.id)) {
+                    (Some(x), Some(y)) => collate::compare(&x.title, &y.title, locale),
+                    _ => Ordering::Equal,
+                }),
+            SortMode::Author => session
+                .items
+                .sort_by(|a, b| match (get(&a.id), get(&b.id)) {
+                    (Some(x), Some(y)) => {
+                        collate::compare(&presenting_surname(x), &presenting_surname(y), locale)
+                            .then_with(|| collate::compare(&x.title, &y.title, locale))
+                    }
+                    _ => Ordering::Equal,
+                }),
+            SortMode::Id => session.items.sort_by(|a, b| compare_ids(&a.id, &b.id)),
+            SortMode::Explicit => match for_session(&config.explicit, session) {
+                Some(list) => {
+                    let rank = |id: &str| list.iter().position(|l| l == id).unwrap_or(usize::MAX);
+                    // stable sort: unlisted items keep sheet order after the listed ones
+                    session.items.sort_by_key(|i| rank(&i.id));
+                }
+                None => tracing::warn!(
+                    "Session {} uses explicit ordering but has no [ordering.explicit] list; keeping sheet order",
+                    session.title
+                ),
+            },
+        }
+        for (idx, item) in session.items.iter_mut().enumerate() {
+            item.order = idx as u32 + 1;
+        }
+    }
+}
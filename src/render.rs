@@ -0,0 +1,372 @@
+use crate::booklet::{BookOptions, Booklet};
+use crate::cli::BuildOpts;
+use crate::config::Config;
+use crate::io::Selection;
+use crate::io::plan::Plan;
+use anyhow::{Result, anyhow};
+use std::cell::OnceCell;
+use std::path::{Path, PathBuf};
+
+// Output formats, typeset from the `booklet::Booklet` (markdown and frab from
+// the model). Each is a `Renderer` registered under a name in a `Registry`: it
+// writes its files, reports them to the `--dry-run` plan and names the files
+// the build cache and `watch` look at.
+// `build` picks formats with `--format` or `[output] formats` (markdown and
+// Typst when neither is given); library users register their own renderers and
+// build with `io::run_build_with`.
+
+// What every renderer gets to know about the build.
+pub struct RenderContext<'a> {
+    pub opts: &'a BuildOpts,
+    pub config: &'a Config,
+    pub book: &'a BookOptions,
+    /// The parsed programme being built.
+    pub selection: &'a Selection,
+    /// Filter label of a partial (subset) build.
+    pub partial: Option<&'a str>,
+    booklet: OnceCell<Booklet>,
+}
+
+impl<'a> RenderContext<'a> {
+    pub fn new(
+        opts: &'a BuildOpts,
+        config: &'a Config,
+        book: &'a BookOptions,
+        selection: &'a Selection,
+        partial: Option<&'a str>,
+    ) -> Self {
+        RenderContext {
+            opts,
+            config,
+            book,
+            selection,
+            partial,
+            booklet: OnceCell::new(),
+        }
+    }
+
+    pub fn outdir(&self) -> &str {
+        &self.opts.output
+    }
+
+    // The booklet, built on first use and shared by the renderers of a build.
+    pub fn booklet(&self) -> Result<&Booklet> {
+        if let Some(booklet) = self.booklet.get() {
+            return Ok(booklet);
+        }
+        let booklet = Booklet::new(
+            self.selection,
+            &self.config.event,
+            self.partial,
+            &self.opts.locales,
+            &self.opts.template,
+            self.book,
+        )?;
+        Ok(self.booklet.get_or_init(|| booklet))
+    }
+}
+
+pub trait Renderer {
+    fn render(&self, ctx: &RenderContext) -> Result<()>;
+    /// Dry-run plan entries for the files `render` writes.
+    fn plan(&self, ctx: &RenderContext, plan: &mut Plan) -> Result<()>;
+    /// Files a finished build leaves behind for a locale.
+    fn outputs(&self, ctx: &RenderContext, locale: &str) -> Vec<PathBuf>;
+    /// Files and directories read besides the input, config and template.
+    fn inputs(&self, _config: &Config) -> Vec<PathBuf> {
+        Vec::new()
+    }
+}
+
+// Renderers by format name, in the order they run.
+pub struct Registry {
+    renderers: Vec<(String, Box<dyn Renderer>)>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry {
+            renderers: Vec::new(),
+        }
+    }
+
+    // Add a format at the end, or replace the renderer of a known one.
+    pub fn register(&mut self, name: &str, renderer: impl Renderer + 'static) -> &mut Self {
+        let renderer: Box<dyn Renderer> = Box::new(renderer);
+        match self.renderers.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = renderer,
+            None => self.renderers.push((name.to_string(), renderer)),
+        }
+        self
+    }
+
+    // The named formats in registry order, each once.
+    pub fn select(&self, names: &[String]) -> Result<Vec<(&str, &dyn Renderer)>> {
+        if let Some(unknown) = names
+            .iter()
+            .find(|name| !self.renderers.iter().any(|(n, _)| n == *name))
+        {
+            let known: Vec<&str> = self.renderers.iter().map(|(n, _)| n.as_str()).collect();
+            return Err(anyhow!(
+                "unknown output format: {} (known: {})",
+                unknown,
+                known.join(", ")
+            ));
+        }
+        Ok(self
+            .renderers
+            .iter()
+            .filter(|(n, _)| names.contains(n))
+            .map(|(n, r)| (n.as_str(), r.as_ref()))
+            .collect())
+    }
+}
+
+// The built-in formats.
+impl Default for Registry {
+    fn default() -> Self {
+        let mut registry = Registry::new();
+        registry
+            .register("markdown", MarkdownRenderer)
+            .register("frab", FrabRenderer)
+            .register("typst", TypstRenderer)
+            .register("latex", LatexRenderer)
+            .register("html", HtmlRenderer)
+            .register("epub", EpubRenderer)
+            .register("docx", DocxRenderer);
+        registry
+    }
+}
+
+// `--format`, else `[output] formats`, else markdown and Typst.
+pub fn format_names(opts: &BuildOpts, config: &Config) -> Vec<String> {
+    let names = if !opts.format.is_empty() {
+        &opts.format
+    } else if !config.output.formats.is_empty() {
+        &config.output.formats
+    } else {
+        return vec!["markdown".to_string(), "typst".to_string()];
+    };
+    names.iter().map(|n| n.trim().to_lowercase()).collect()
+}
+
+// `manifest.json` and one markdown file per abstract, which `emit-typst` reads
+// back; a partial build marks the manifest.
+struct MarkdownRenderer;
+
+impl Renderer for MarkdownRenderer {
+    fn render(&self, ctx: &RenderContext) -> Result<()> {
+        let selection = ctx.selection;
+        crate::io::markdown::write_markdown(
+            &selection.abstracts,
+            &selection.sessions,
+            ctx.outdir(),
+            &ctx.config.event,
+        )?;
+        if !selection.subset.is_empty() {
+            crate::io::markdown::mark_partial(ctx.outdir(), &selection.subset)?;
+        }
+        Ok(())
+    }
+
+    fn plan(&self, ctx: &RenderContext, plan: &mut Plan) -> Result<()> {
+        crate::io::markdown::write_markdown_plan(
+            &ctx.selection.abstracts,
+            &ctx.selection.sessions,
+            ctx.outdir(),
+            plan,
+        )
+    }
+
+    fn outputs(&self, ctx: &RenderContext, _locale: &str) -> Vec<PathBuf> {
+        vec![Path::new(ctx.outdir()).join("manifest.json")]
+    }
+}
+
+// The frab/Pentabarf `schedule.xml` for conference apps, from the model.
+struct FrabRenderer;
+
+impl Renderer for FrabRenderer {
+    fn render(&self, ctx: &RenderContext) -> Result<()> {
+        crate::io::frab::write_frab(
+            &ctx.selection.abstracts,
+            &ctx.selection.sessions,
+            ctx.outdir(),
+            &ctx.config.event,
+        )
+    }
+
+    fn plan(&self, ctx: &RenderContext, plan: &mut Plan) -> Result<()> {
+        crate::io::frab::write_frab_plan(
+            &ctx.selection.abstracts,
+            &ctx.selection.sessions,
+            ctx.outdir(),
+            &ctx.config.event,
+            plan,
+        )
+    }
+
+    fn outputs(&self, ctx: &RenderContext, _locale: &str) -> Vec<PathBuf> {
+        vec![Path::new(ctx.outdir()).join(crate::io::frab::SCHEDULE_FILE)]
+    }
+}
+
+// The `.typ` files and, unless `--no-pdf`, the PDFs compiled from them. The
+// author index is printed with `[index] authors`.
+struct TypstRenderer;
+
+impl Renderer for TypstRenderer {
+    fn render(&self, ctx: &RenderContext) -> Result<()> {
+        let opts = ctx.opts;
+        let emit = crate::typst::EmitOptions {
+            data_json: opts.data_json,
+            cache: !opts.no_cache,
+            author_index: ctx.config.index.authors,
+        };
+        let booklet = ctx.booklet()?;
+        for (book, origins) in booklet.editions.iter().zip(&booklet.origins) {
+            crate::typst::write_book(&opts.output, &opts.template, book, origins, &emit)?;
+        }
+        let pdf = crate::typst::PdfOptions {
+            typst_bin: opts.typst_bin.clone(),
+            no_pdf: opts.no_pdf,
+            previews: opts.preview.clone(),
+            template: opts.template.clone(),
+            jobs: opts.jobs,
+            cache: !opts.no_cache,
+        };
+        crate::typst::compile_pdfs(&opts.output, &opts.locales, &pdf)
+    }
+
+    fn plan(&self, ctx: &RenderContext, plan: &mut Plan) -> Result<()> {
+        crate::typst::emit_typst_plan(
+            ctx.outdir(),
+            &ctx.opts.locales,
+            &ctx.opts.template,
+            ctx.partial,
+            ctx.opts.data_json,
+            plan,
+        )
+    }
+
+    fn outputs(&self, ctx: &RenderContext, locale: &str) -> Vec<PathBuf> {
+        let outdir = Path::new(ctx.outdir());
+        let mut outputs = vec![outdir.join("typst").join(format!("book_{}.typ", locale))];
+        if !ctx.opts.no_pdf {
+            outputs.push(outdir.join(crate::typst::pdf_file_name(locale, ctx.partial)));
+        }
+        outputs
+    }
+}
+
+// The author index is always printed.
+struct LatexRenderer;
+
+impl Renderer for LatexRenderer {
+    fn render(&self, ctx: &RenderContext) -> Result<()> {
+        crate::latex::write_latex(ctx.outdir(), &ctx.booklet()?.editions, &ctx.config.latex)
+    }
+
+    fn plan(&self, ctx: &RenderContext, plan: &mut Plan) -> Result<()> {
+        crate::latex::write_latex_plan(ctx.outdir(), &ctx.opts.locales, &ctx.config.latex, plan)
+    }
+
+    fn outputs(&self, ctx: &RenderContext, locale: &str) -> Vec<PathBuf> {
+        vec![
+            Path::new(ctx.outdir())
+                .join(crate::latex::LATEX_DIR)
+                .join(crate::latex::tex_file_name(locale)),
+        ]
+    }
+
+    fn inputs(&self, config: &Config) -> Vec<PathBuf> {
+        crate::latex::inputs(&config.latex)
+            .into_iter()
+            .map(Path::to_path_buf)
+            .collect()
+    }
+}
+
+// The author index is always printed.
+struct HtmlRenderer;
+
+impl Renderer for HtmlRenderer {
+    fn render(&self, ctx: &RenderContext) -> Result<()> {
+        crate::html::write_site(ctx.outdir(), &ctx.booklet()?.editions, &ctx.opts.template)
+    }
+
+    fn plan(&self, ctx: &RenderContext, plan: &mut Plan) -> Result<()> {
+        crate::html::write_site_plan(ctx.outdir(), &ctx.opts.locales, plan)
+    }
+
+    fn outputs(&self, ctx: &RenderContext, locale: &str) -> Vec<PathBuf> {
+        vec![
+            Path::new(ctx.outdir())
+                .join(crate::html::SITE_DIR)
+                .join(locale)
+                .join("index.html"),
+        ]
+    }
+
+    fn inputs(&self, _config: &Config) -> Vec<PathBuf> {
+        vec![PathBuf::from(crate::html::SITE_TEMPLATE_DIR)]
+    }
+}
+
+// The author index is printed with `[index] authors`.
+struct EpubRenderer;
+
+impl Renderer for EpubRenderer {
+    fn render(&self, ctx: &RenderContext) -> Result<()> {
+        crate::epub::write_epubs(
+            ctx.outdir(),
+            &ctx.booklet()?.editions,
+            &ctx.opts.template,
+            ctx.config.index.authors,
+            &ctx.config.epub,
+        )
+    }
+
+    fn plan(&self, ctx: &RenderContext, plan: &mut Plan) -> Result<()> {
+        crate::epub::write_epub_plan(ctx.outdir(), &ctx.opts.locales, ctx.partial, plan)
+    }
+
+    fn outputs(&self, ctx: &RenderContext, locale: &str) -> Vec<PathBuf> {
+        vec![Path::new(ctx.outdir()).join(crate::typst::book_file_name(
+            locale,
+            ctx.partial,
+            "epub",
+        ))]
+    }
+
+    fn inputs(&self, _config: &Config) -> Vec<PathBuf> {
+        vec![PathBuf::from(crate::epub::EPUB_TEMPLATE_DIR)]
+    }
+}
+
+// Word documents for proofreading; `--docx-split session` splits them per
+// session. No author index.
+struct DocxRenderer;
+
+impl Renderer for DocxRenderer {
+    fn render(&self, ctx: &RenderContext) -> Result<()> {
+        crate::docx::write_docx(ctx.booklet()?, ctx.outdir(), ctx.opts.docx_split)
+    }
+
+    fn plan(&self, ctx: &RenderContext, plan: &mut Plan) -> Result<()> {
+        crate::docx::write_docx_plan(ctx.booklet()?, ctx.outdir(), ctx.opts.docx_split, plan)
+    }
+
+    fn outputs(&self, ctx: &RenderContext, locale: &str) -> Vec<PathBuf> {
+        ctx.booklet()
+            .ok()
+            .and_then(|booklet| booklet.editions.iter().find(|book| book.locale == locale))
+            .and_then(|book| crate::docx::first_docx_path(ctx.outdir(), book, ctx.opts.docx_split))
+            .into_iter()
+            .collect()
+    }
+
+    fn inputs(&self, _config: &Config) -> Vec<PathBuf> {
+        vec![PathBuf::from(crate::docx::DOCX_TEMPLATE_DIR)]
+    }
+}
@@ -0,0 +1,382 @@
+use crate::model::{Abstract, AbstractSection};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+// Opt-in lightweight markup for abstract cells (`[text] markup = true`): a
+// Markdown subset with `*emph*`, `**strong**`, `__underline__`, `H~2~O`, `x^2^`
+// and `- ` bullet lists. A backslash makes the next markup character literal. Anything that is
+// not understood stays plain text and is reported as a warning.
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Inline {
+    Text(String),
+    Emph(Vec<Inline>),
+    Strong(Vec<Inline>),
+    Underline(Vec<Inline>),
+    Sub(Vec<Inline>),
+    Sup(Vec<Inline>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Block {
+    Paragraph(Vec<Inline>),
+    List(Vec<Vec<Inline>>),
+}
+
+pub type RichText = Vec<Block>;
+
+const SPECIAL: &[char] = &['*', '~', '^', '\\'];
+
+fn list_item(line: &str) -> Option<&str> {
+    let t = line.trim_start();
+    t.strip_prefix("- ")
+        .or_else(|| t.strip_prefix("• "))
+        .or_else(|| t.strip_prefix("* "))
+}
+
+fn unsupported_patterns() -> &'static [(Regex, &'static str)] {
+    static PATTERNS: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        vec![
+            (
+                Regex::new(r"(?i)</?(b|i|u|em|strong|sub|sup|br|p|span)\b[^>]*>").unwrap(),
+                "HTML tag",
+            ),
+            (Regex::new(r"\[[^\]]+\]\([^)]+\)").unwrap(), "Markdown link"),
+            (Regex::new(r"(?m)^\s*#{1,6}\s").unwrap(), "Markdown heading"),
+            (Regex::new(r"`[^`]+`").unwrap(), "code span"),
+        ]
+    })
+}
+
+// Markdown's `__bold__` underlines here. Checked on typed text only, as
+// underlined xlsx runs are read in as `__`.
+pub fn underscore_warning(input: &str) -> Option<String> {
+    static UNDERSCORE: OnceLock<Regex> = OnceLock::new();
+    let re = UNDERSCORE.get_or_init(|| Regex::new(r"(?:^|[^\\_])(__[^_]+__)").unwrap());
+    re.captures(input).map(|caps| {
+        format!(
+            "underscore emphasis `{}` is set as underline; use `**` for bold",
+            &caps[1]
+        )
+    })
+}
+
+// Parse cell text into blocks; returns warnings for markup that was kept as text.
+pub fn parse(input: &str) -> (RichText, Vec<String>) {
+    let mut warnings = Vec::new();
+    for (re, what) in unsupported_patterns() {
+        if let Some(m) = re.find(input) {
+            warnings.push(format!(
+                "unsupported {} `{}` kept as text",
+                what,
+                m.as_str().trim()
+            ));
+        }
+    }
+
+    let mut blocks: RichText = Vec::new();
+    let mut para: Vec<&str> = Vec::new();
+    let mut items: Vec<&str> = Vec::new();
+    let flush_para = |para: &mut Vec<&str>, blocks: &mut RichText, warnings: &mut Vec<String>| {
+        if !para.is_empty() {
+            let text = para.join("\n");
+            blocks.push(Block::Paragraph(parse_inlines(text.trim(), warnings)));
+            para.clear();
+        }
+    };
+    let flush_list = |items: &mut Vec<&str>, blocks: &mut RichText, warnings: &mut Vec<String>| {
+        if !items.is_empty() {
+            let parsed = items
+                .iter()
+                .map(|i| parse_inlines(i.trim(), warnings))
+                .collect();
+            blocks.push(Block::List(parsed));
+            items.clear();
+        }
+    };
+    for line in input.lines() {
+        if line.trim().is_empty() {
+            flush_para(&mut para, &mut blocks, &mut warnings);
+            flush_list(&mut items, &mut blocks, &mut warnings);
+        } else if let Some(item) = list_item(line) {
+            flush_para(&mut para, &mut blocks, &mut warnings);
+            items.push(item);
+        } else {
+            flush_list(&mut items, &mut blocks, &mut warnings);
+            para.push(line);
+        }
+    }
+    flush_para(&mut para, &mut blocks, &mut warnings);
+    flush_list(&mut items, &mut blocks, &mut warnings);
+    (blocks, warnings)
+}
+
+fn push_text(out: &mut Vec<Inline>, s: &str) {
+    if s.is_empty() {
+        return;
+    }
+    if let Some(Inline::Text(t)) = out.last_mut() {
+        t.push_str(s);
+    } else {
+        out.push(Inline::Text(s.to_string()));
+    }
+}
+
+fn parse_inlines(input: &str, warnings: &mut Vec<String>) -> Vec<Inline> {
+    let chars: Vec<char> = input.chars().collect();
+    let (spans, _) = parse_until(&chars, 0, None, warnings, &mut HashSet::new());
+    spans
+}
+
+// Parse from `pos` until `close` (or the end). Returns the spans and the position
+// after the closing delimiter, or `None` when `close` was never found. `failed`
+// remembers unclosed openers so runs of stray delimiters stay linear.
+fn parse_until(
+    chars: &[char],
+    mut pos: usize,
+    close: Option<&'static str>,
+    warnings: &mut Vec<String>,
+    failed: &mut HashSet<(usize, &'static str)>,
+) -> (Vec<Inline>, Option<usize>) {
+    let mut out = Vec::new();
+    let at = |p: usize, delim: &str| {
+        delim
+            .chars()
+            .enumerate()
+            .all(|(i, c)| chars.get(p + i) == Some(&c))
+    };
+    while pos < chars.len() {
+        let ch = chars[pos];
+        if let Some(delim) = close
+            && at(pos, delim)
+            && pos > 0
+            && !chars[pos - 1].is_whitespace()
+        {
+            return (out, Some(pos + delim.chars().count()));
+        }
+        if ch == '\\' {
+            match chars.get(pos + 1) {
+                Some(next) if SPECIAL.contains(next) || matches!(next, '_' | '-' | '•') => {
+                    push_text(&mut out, &next.to_string());
+                    pos += 2;
+                }
+                _ => {
+                    push_text(&mut out, "\\");
+                    pos += 1;
+                }
+            }
+            continue;
+        }
+        let opener = if at(pos, "**") {
+            Some("**")
+        } else if at(pos, "__") {
+            Some("__")
+        } else if ch == '*' {
+            Some("*")
+        } else if ch == '~' {
+            Some("~")
+        } else if ch == '^' {
+            Some("^")
+        } else {
+            None
+        };
+        if let Some(delim) = opener {
+            let start = pos + delim.len();
+            let opens = chars.get(start).is_some_and(|c| !c.is_whitespace());
+            if opens && !failed.contains(&(start, delim)) {
+                let mut inner_warnings = Vec::new();
+                let (inner, end) =
+                    parse_until(chars, start, Some(delim), &mut inner_warnings, failed);
+                let script = delim == "~" || delim == "^";
+                let spaced = script
+                    && end.is_some_and(|e| chars[start..e].iter().any(|c| c.is_whitespace()));
+                if let Some(end) = end.filter(|_| !spaced && !inner.is_empty()) {
+                    warnings.extend(inner_warnings);
+                    out.push(match delim {
+                        "**" => Inline::Strong(inner),
+                        "*" => Inline::Emph(inner),
+                        "__" => Inline::Underline(inner),
+                        "~" => Inline::Sub(inner),
+                        _ => Inline::Sup(inner),
+                    });
+                    pos = end;
+                    continue;
+                }
+                failed.insert((start, delim));
+                warnings.push(format!("unmatched `{}` kept as text", delim));
+            }
+            push_text(&mut out, delim);
+            pos += delim.len();
+            continue;
+        }
+        push_text(&mut out, &ch.to_string());
+        pos += 1;
+    }
+    (out, None)
+}
+
+// Escape markup characters in literal text. List markers are left alone, so
+// text typed as `- item` lines still becomes a list.
+pub fn escape_inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if SPECIAL.contains(&ch) || (ch == '_' && chars.peek() == Some(&'_')) {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+fn escape_markdown(text: &str, out: &mut String) {
+    for line in text.split_inclusive('\n') {
+        let line_start = out.is_empty() || out.ends_with('\n');
+        let rest = line.trim_start();
+        if line_start && rest.starts_with(['-', '•']) {
+            out.push_str(&line[..line.len() - rest.len()]);
+            out.push('\\');
+            out.push_str(&escape_inline(rest));
+        } else {
+            out.push_str(&escape_inline(line));
+        }
+    }
+}
+
+fn inlines_markdown(spans: &[Inline], out: &mut String) {
+    for span in spans {
+        let (delim, children) = match span {
+            Inline::Text(t) => {
+                escape_markdown(t, out);
+                continue;
+            }
+            Inline::Emph(c) => ("*", c),
+            Inline::Strong(c) => ("**", c),
+            Inline::Underline(c) => ("__", c),
+            Inline::Sub(c) => ("~", c),
+            Inline::Sup(c) => ("^", c),
+        };
+        out.push_str(delim);
+        inlines_markdown(children, out);
+        out.push_str(delim);
+    }
+}
+
+// Canonical markup form; `parse` of the result yields the same blocks.
+pub fn to_markdown(blocks: &[Block]) -> String {
+    let parts: Vec<String> = blocks
+        .iter()
+        .map(|b| {
+            let mut out = String::new();
+            match b {
+                Block::Paragraph(spans) => inlines_markdown(spans, &mut out),
+                Block::List(items) => {
+                    for (i, item) in items.iter().enumerate() {
+                        if i > 0 {
+                            out.push('\n');
+                        }
+                        out.push_str("- ");
+                        inlines_markdown(item, &mut out);
+                    }
+                }
+            }
+            out
+        })
+        .collect();
+    parts.join("\n\n")
+}
+
+// Text without markup for plain-text outputs (frab's `<abstract>`, the serve
+// programme): spans flattened, list items on `- ` lines.
+pub fn plain_text(blocks: &[Block]) -> String {
+    fn inlines(spans: &[Inline], out: &mut String) {
+        for span in spans {
+            match span {
+                Inline::Text(t) => out.push_str(t),
+                Inline::Emph(c)
+                | Inline::Strong(c)
+                | Inline::Underline(c)
+                | Inline::Sub(c)
+                | Inline::Sup(c) => inlines(c, out),
+            }
+        }
+    }
+    let parts: Vec<String> = blocks
+        .iter()
+        .map(|b| {
+            let mut out = String::new();
+            match b {
+                Block::Paragraph(spans) => inlines(spans, &mut out),
+                Block::List(items) => {
+                    for (i, item) in items.iter().enumerate() {
+                        if i > 0 {
+                            out.push('\n');
+                        }
+                        out.push_str("- ");
+                        inlines(item, &mut out);
+                    }
+                }
+            }
+            out
+        })
+        .collect();
+    parts.join("\n\n")
+}
+
+// A section's text without markup.
+pub fn plain_section(section: &AbstractSection) -> String {
+    match &section.rich {
+        Some(rich) => plain_text(rich),
+        None => section.text.clone(),
+    }
+}
+
+// An abstract body without markup: the parsed sections, else the text as read.
+pub fn plain_body(text: &str, sections: &[AbstractSection]) -> String {
+    if !sections.iter().any(|s| s.rich.is_some()) {
+        return text.to_string();
+    }
+    sections
+        .iter()
+        .map(plain_section)
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+// Parse the abstract body sections as markup: each section keeps its parsed
+// blocks in `rich` and its canonical markup in `text`. Language variants too.
+pub fn apply_markup(abs: &mut Abstract) {
+    apply_sections(&abs.id, &mut abs.abstract_sections, &mut abs.abstract_text);
+    for variant in abs.variants.values_mut() {
+        apply_sections(
+            &abs.id,
+            &mut variant.abstract_sections,
+            &mut variant.abstract_text,
+        );
+    }
+}
+
+fn apply_sections(id: &str, sections: &mut [AbstractSection], joined: &mut String) {
+    for section in sections.iter_mut() {
+        let (rich, warnings) = parse(&section.text);
+        for w in warnings {
+            tracing::warn!("Abstract {}: {}", id, w);
+        }
+        section.text = to_markdown(&rich);
+        section.rich = Some(rich);
+    }
+    if !sections.is_empty() {
+        *joined = sections
+            .iter()
+            .map(|s| s.text.as_str())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+    }
+}
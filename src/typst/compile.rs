@@ -0,0 +1,223 @@
+use super::PreviewFormat;
+use super::sourcemap::Diagnostic;
+use anyhow::{Result, anyhow};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use typst::diag::{FileError, FileResult, Severity, SourceDiagnostic};
+use typst::foundations::{Bytes, Datetime, Duration};
+use typst::syntax::{FileId, RootedPath, Source, VirtualPath, VirtualRoot};
+use typst::text::{Font, FontBook};
+use typst::utils::LazyHash;
+use typst::{Library, LibraryExt, World, WorldExt};
+use typst_kit::datetime::Time;
+use typst_kit::files::{FileLoader, FileStore, FsRoot};
+use typst_kit::fonts::{self, FontStore};
+use typst_layout::PagedDocument;
+
+// In-process Typst (`--features embedded-typst`): the same job as
+// `typst compile --font-path <fonts>`, without a typst binary. The output
+// directory is the project root; templates name their files from the working
+// directory (`/templates/starter/theme.typ`), so paths under the template
+// tree's top directory are read from there. Nothing else resolves outside the
+// two; packages are not available.
+
+struct ProjectFiles {
+    output: FsRoot,
+    // top directory of the template tree (`templates`), relative to the CWD
+    templates: Option<(String, FsRoot)>,
+}
+
+impl ProjectFiles {
+    fn root(&self, vpath: &VirtualPath) -> &FsRoot {
+        match &self.templates {
+            Some((top, root)) if vpath.get_without_slash().split('/').next() == Some(top) => root,
+            _ => &self.output,
+        }
+    }
+}
+
+impl FileLoader for ProjectFiles {
+    fn load(&self, id: FileId) -> FileResult<Bytes> {
+        match id.root() {
+            VirtualRoot::Project => self.root(id.vpath()).load(id.vpath()),
+            VirtualRoot::Package(spec) => Err(FileError::Other(Some(
+                format!("package {} is not available to the embedded compiler", spec).into(),
+            ))),
+        }
+    }
+}
+
+pub struct BookWorld {
+    main: FileId,
+    library: LazyHash<Library>,
+    fonts: FontStore,
+    files: FileStore<ProjectFiles>,
+    time: Time,
+}
+
+// First directory of the template path as seen from the working directory.
+fn template_top(template_dir: &Path) -> Option<String> {
+    let cwd = std::env::current_dir().ok()?;
+    let dir = fs::canonicalize(template_dir).ok()?;
+    match dir.strip_prefix(&cwd).ok()?.components().next()? {
+        Component::Normal(top) => top.to_str().map(str::to_string),
+        _ => None,
+    }
+}
+
+impl BookWorld {
+    pub fn new(outdir: &Path, template_dir: &Path, main: &Path, font_dir: &Path) -> Result<Self> {
+        if !main.is_file() {
+            return Err(anyhow!("typst file {} not found", main.display()));
+        }
+        let vpath = VirtualPath::virtualize(outdir, main).map_err(|_| {
+            anyhow!(
+                "{} is outside the output directory {}",
+                main.display(),
+                outdir.display()
+            )
+        })?;
+        let mut fonts = FontStore::new();
+        fonts.extend(fonts::scan(font_dir));
+        fonts.extend(fonts::embedded());
+        Ok(BookWorld {
+            main: RootedPath::new(VirtualRoot::Project, vpath).intern(),
+            library: LazyHash::new(Library::default()),
+            fonts,
+            files: FileStore::new(ProjectFiles {
+                output: FsRoot::new(outdir.to_path_buf()),
+                templates: template_top(template_dir).map(|top| (top, FsRoot::new(PathBuf::new()))),
+            }),
+            time: Time::system(),
+        })
+    }
+}
+
+impl World for BookWorld {
+    fn library(&self) -> &LazyHash<Library> {
+        &self.library
+    }
+
+    fn book(&self) -> &LazyHash<FontBook> {
+        self.fonts.book()
+    }
+
+    fn main(&self) -> FileId {
+        self.main
+    }
+
+    fn source(&self, id: FileId) -> FileResult<Source> {
+        self.files.source(id)
+    }
+
+    fn file(&self, id: FileId) -> FileResult<Bytes> {
+        self.files.file(id)
+    }
+
+    fn font(&self, index: usize) -> Option<Font> {
+        self.fonts.font(index)
+    }
+
+    fn today(&self, offset: Option<Duration>) -> Option<Datetime> {
+        self.time.today(offset)
+    }
+}
+
+fn describe(diag: &SourceDiagnostic) -> String {
+    let mut msg = diag.message.to_string();
+    for hint in &diag.hints {
+        msg.push_str(&format!(" (hint: {})", hint.v));
+    }
+    msg
+}
+
+// File on disk, 1-based line and 0-based column, like the CLI's short
+// diagnostic format.
+fn locate(world: &BookWorld, diag: &SourceDiagnostic) -> Option<(String, usize, usize)> {
+    let id = diag.span.id()?;
+    let range = world.range(diag.span)?;
+    let source = world.source(id).ok()?;
+    let (line, column) = source.lines().byte_to_line_column(range.start)?;
+    let path = world
+        .files
+        .loader()
+        .root(id.vpath())
+        .resolve(id.vpath())
+        .ok()?;
+    Some((path.display().to_string(), line + 1, column))
+}
+
+fn report(world: &BookWorld, diags: &[SourceDiagnostic]) -> Vec<Diagnostic> {
+    diags
+        .iter()
+        .map(|d| {
+            let (file, line, column) = locate(world, d).unwrap_or_default();
+            Diagnostic {
+                file,
+                line,
+                column,
+                error: d.severity == Severity::Error,
+                message: describe(d),
+            }
+        })
+        .collect()
+}
+
+// Compile `typst_file` to `pdf`, plus one image per page for every preview
+// format (`<preview_dir>/page-01.png`, ...). Returns the diagnostics; when one
+// of them is an error nothing is written.
+pub fn compile_book(
+    outdir: &Path,
+    template_dir: &Path,
+    typst_file: &Path,
+    font_dir: &Path,
+    pdf: &Path,
+    previews: &[PreviewFormat],
+    preview_dir: &Path,
+) -> Result<Vec<Diagnostic>> {
+    let world = BookWorld::new(outdir, template_dir, typst_file, font_dir)?;
+    let warned = typst::compile::<PagedDocument>(&world);
+    let mut diags = report(&world, &warned.warnings);
+    let document = match warned.output {
+        Ok(document) => document,
+        Err(errors) => {
+            diags.extend(report(&world, &errors));
+            return Ok(diags);
+        }
+    };
+    let bytes = match typst_pdf::pdf(&document, &typst_pdf::PdfOptions::default()) {
+        Ok(bytes) => bytes,
+        Err(errors) => {
+            diags.extend(report(&world, &errors));
+            return Ok(diags);
+        }
+    };
+    fs::write(pdf, bytes)?;
+
+    if previews.is_empty() {
+        return Ok(diags);
+    }
+    fs::create_dir_all(preview_dir)?;
+    let pages = document.pages();
+    let width = pages.len().to_string().len();
+    for (i, page) in pages.iter().enumerate() {
+        let stem = format!("page-{:0width$}", i + 1, width = width);
+        for format in previews {
+            let path: PathBuf = preview_dir.join(format!("{}.{}", stem, format.extension()));
+            match format {
+                PreviewFormat::Png => {
+                    // 2 px per pt, like the CLI's 144 ppi default
+                    let png = typst_render::render(page, &typst_render::RenderOptions::default())
+                        .encode_png()
+                        .map_err(|e| anyhow!("PNG preview {}: {}", path.display(), e))?;
+                    fs::write(&path, png)?;
+                }
+                PreviewFormat::Svg => {
+                    let svg = typst_svg::svg(page, &typst_svg::SvgOptions::default());
+                    fs::write(&path, svg)?;
+                }
+            }
+        }
+    }
+    Ok(diags)
+}
@@ -0,0 +1,24 @@
+use crate::booklet::BookData;
+use anyhow::Result;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+// `typst/data_<locale>.json`: an edition of the booklet as data, for
+// Typst-native templates (see `booklet` and spec/04 for the schema).
+
+pub fn data_file_name(locale: &str) -> String {
+    format!("data_{}.json", locale)
+}
+
+// The author index only when the Typst booklet prints it.
+pub fn write_data(typst_dir: &Path, data: &BookData, authors: bool) -> Result<()> {
+    let path = typst_dir.join(data_file_name(&data.locale));
+    let mut json = serde_json::to_value(data)?;
+    if !authors {
+        json["author_index"] = serde_json::Value::Array(Vec::new());
+    }
+    let mut f = File::create(path)?;
+    write!(f, "{}", serde_json::to_string_pretty(&json)?)?;
+    Ok(())
+}
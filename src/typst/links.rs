@@ -0,0 +1,123 @@
+use super::markup::{Block, Inline, Target, text};
+use regex::Regex;
+use std::sync::OnceLock;
+
+// Automatic links in abstract text: URLs, DOIs (`doi:10.1234/x` or a bare
+// `10.1234/x`) and email addresses become `#link`s. The shown text keeps what
+// the author wrote, with zero-width spaces as break opportunities so long URLs
+// wrap inside the A5 column.
+
+const BREAK: char = '\u{200B}';
+
+fn pattern() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(concat!(
+            r#"(?i)(?P<url>\b(?:https?://|www\.)[^\s<>"]+)"#,
+            r"|(?P<email>\b[a-z0-9._%+-]+@[a-z0-9-]+(?:\.[a-z0-9-]+)*\.[a-z]{2,}\b)",
+            r#"|(?P<doi>\b(?:doi:\s?)?10\.\d{4,9}/[^\s<>"]+)"#,
+        ))
+        .unwrap()
+    })
+}
+
+// Drop sentence punctuation after a match, and a closing bracket that has no
+// opening partner inside it: "(see https://x.dk/a)." links `https://x.dk/a`.
+fn trim_end(m: &str) -> &str {
+    let mut s = m;
+    loop {
+        let Some(last) = s.chars().last() else {
+            return s;
+        };
+        let drop = match last {
+            '.' | ',' | ';' | ':' | '!' | '?' | '\'' | '"' => true,
+            ')' => s.matches('(').count() < s.matches(')').count(),
+            ']' => s.matches('[').count() < s.matches(']').count(),
+            _ => false,
+        };
+        if !drop {
+            return s;
+        }
+        s = &s[..s.len() - last.len_utf8()];
+    }
+}
+
+// Zero-width spaces after separators, but not inside the scheme.
+fn with_breaks(shown: &str) -> String {
+    let start = shown.find("://").map(|i| i + 3).unwrap_or(0);
+    let mut out = String::from(&shown[..start]);
+    let rest: Vec<char> = shown[start..].chars().collect();
+    for (i, ch) in rest.iter().enumerate() {
+        out.push(*ch);
+        if matches!(ch, '/' | '.' | '-' | '_' | '?' | '&' | '=' | '#' | '@') && i + 1 < rest.len() {
+            out.push(BREAK);
+        }
+    }
+    out
+}
+
+fn link(kind: &str, shown: &str) -> Inline {
+    let prefix = |p: &str| {
+        shown
+            .get(..p.len())
+            .is_some_and(|s| s.eq_ignore_ascii_case(p))
+    };
+    let url = match kind {
+        "email" => format!("mailto:{}", shown),
+        "doi" if prefix("doi:") => format!("https://doi.org/{}", shown[4..].trim_start()),
+        "doi" => format!("https://doi.org/{}", shown),
+        _ if prefix("www.") => format!("https://{}", shown),
+        _ => shown.to_string(),
+    };
+    Inline::Link {
+        target: Target::Url(url),
+        body: vec![text(with_breaks(shown))],
+    }
+}
+
+fn split_text(input: &str, out: &mut Vec<Inline>) {
+    let mut last = 0;
+    for caps in pattern().captures_iter(input) {
+        let (kind, m) = ["url", "email", "doi"]
+            .iter()
+            .find_map(|k| caps.name(k).map(|m| (*k, m)))
+            .expect("one group matches");
+        let shown = trim_end(m.as_str());
+        if shown.is_empty() {
+            continue;
+        }
+        if m.start() > last {
+            out.push(text(&input[last..m.start()]));
+        }
+        out.push(link(kind, shown));
+        last = m.start() + shown.len();
+    }
+    if last < input.len() {
+        out.push(text(&input[last..]));
+    }
+}
+
+// Replace URLs, DOIs and emails in text spans with links. Existing links are
+// left alone.
+pub fn linkify(items: Vec<Inline>) -> Vec<Inline> {
+    let mut out = Vec::new();
+    for item in items {
+        match item {
+            Inline::Text(s) => split_text(&s, &mut out),
+            Inline::Strong(body) => out.push(Inline::Strong(linkify(body))),
+            Inline::Call { func, body } => out.push(Inline::Call {
+                func,
+                body: linkify(body),
+            }),
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+pub fn linkify_block(block: Block) -> Block {
+    match block {
+        Block::Paragraph(body) => Block::Paragraph(linkify(body)),
+        Block::List(items) => Block::List(items.into_iter().map(linkify).collect()),
+    }
+}
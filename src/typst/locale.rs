@@ -0,0 +1,216 @@
+use anyhow::{Result, anyhow};
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::fs::{read_dir, read_to_string};
+use std::path::{Path, PathBuf};
+use toml::Value;
+use toml::value::Table;
+
+// UI labels for a locale. Every key of `<locales>/<locale>.toml` reaches the
+// templates as `labels.<key>` (tables nest: `labels.cover.title`). A locale
+// falls back along its chain, e.g. `da-DK` -> `da` -> `en`, with the built-in
+// English defaults last. A template directory may ship its own `locales/`.
+
+pub const LOCALE_DIR: &str = "templates/starter/locales";
+const BASE_LOCALE: &str = "en";
+
+// `da-DK` -> [`da-DK`, `da`, `en`]; `en-GB` -> [`en-GB`, `en`].
+pub fn locale_chain(locale: &str) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut tag = locale.trim().replace('_', "-");
+    while !tag.is_empty() {
+        chain.push(tag.clone());
+        match tag.rfind('-') {
+            Some(i) => tag.truncate(i),
+            None => break,
+        }
+    }
+    if !chain.iter().any(|t| t == BASE_LOCALE) {
+        chain.push(BASE_LOCALE.to_string());
+    }
+    chain
+}
+
+// `<template>/locales` when the template has one, else the starter's.
+pub fn locale_dir(template_dir: &Path) -> PathBuf {
+    let own = template_dir.join("locales");
+    if own.is_dir() {
+        own
+    } else {
+        PathBuf::from(LOCALE_DIR)
+    }
+}
+
+fn read_locale_file(dir: &Path, locale: &str) -> Result<Option<Table>> {
+    let path = dir.join(format!("{}.toml", locale));
+    if !path.exists() {
+        return Ok(None);
+    }
+    let txt = read_to_string(&path)?;
+    match toml::from_str::<Value>(&txt)? {
+        Value::Table(t) => Ok(Some(t)),
+        _ => Err(anyhow!("{}: expected a table", path.display())),
+    }
+}
+
+fn default_labels() -> Table {
+    let mut m = Table::new();
+    for (key, value) in [
+        ("title", "Symposium 2026"),
+        ("authors_label", "Authors"),
+        ("affiliation_label", "Affiliation"),
+        ("toc_label", "Table of contents"),
+        ("index_label", "Index"),
+        ("take_home_label", "Take-home"),
+        ("reference_label", "Reference"),
+        ("tags_label", "Tags"),
+        ("programme_label", "Programme"),
+        ("search_label", "Search"),
+        ("tag_index_label", "Tag index"),
+        ("untranslated_label", "Not translated"),
+        ("author_index_label", "Author index"),
+        ("affiliation_index_label", "Affiliation index"),
+        ("cover_header", "Interprofessional Education Symposium 2026"),
+        ("cover_title", "PROGRAM"),
+        ("cover_symposium", "Interprofessional Education Symposium"),
+        ("cover_date", "13 March 2026"),
+        (
+            "cover_subtitle",
+            "The impact of intelligence on learning and guidance",
+        ),
+    ] {
+        m.insert(key.to_string(), Value::String(value.to_string()));
+    }
+    m
+}
+
+// Later tables win; nested tables merge key by key.
+fn merge(into: &mut Table, from: Table) {
+    for (key, value) in from {
+        match (into.get_mut(&key), value) {
+            (Some(Value::Table(a)), Value::Table(b)) => merge(a, b),
+            (_, value) => {
+                into.insert(key, value);
+            }
+        }
+    }
+}
+
+pub struct Labels {
+    pub values: Table,
+    /// Locale files found along the chain, most specific first.
+    pub found: Vec<String>,
+}
+
+pub fn load_labels(dir: &Path, locale: &str) -> Result<Labels> {
+    let mut values = default_labels();
+    let mut found = Vec::new();
+    let chain = locale_chain(locale);
+    for tag in chain.iter().rev() {
+        if let Some(table) = read_locale_file(dir, tag)? {
+            merge(&mut values, table);
+            found.insert(0, tag.clone());
+        }
+    }
+    Ok(Labels { values, found })
+}
+
+// Dotted key paths of the string leaves, e.g. `cover.title`.
+fn flatten(table: &Table, prefix: &str, out: &mut BTreeSet<String>) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            Value::Table(t) => flatten(t, &path, out),
+            _ => {
+                out.insert(path);
+            }
+        }
+    }
+}
+
+// `labels.<key>` references in the files (Typst or HTML) of a template directory.
+pub fn template_label_keys(dir: &Path) -> Result<BTreeSet<String>> {
+    let re = Regex::new(r"\blabels\.([A-Za-z_][A-Za-z0-9_]*(?:\.[A-Za-z_][A-Za-z0-9_]*)*)")?;
+    let mut keys = BTreeSet::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(d) = dirs.pop() {
+        for entry in read_dir(&d)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("typ" | "html")
+            ) {
+                let txt = read_to_string(&path)?;
+                keys.extend(re.captures_iter(&txt).map(|c| c[1].to_string()));
+            }
+        }
+    }
+    Ok(keys)
+}
+
+// Label coverage for one locale against the keys a template uses: keys that
+// fall back to another locale (or the built-in defaults) and keys the locale's
+// own files define but neither the template nor the HTML site (`site`) reads.
+pub fn label_report(
+    dir: &Path,
+    locale: &str,
+    used: &BTreeSet<String>,
+    site: &BTreeSet<String>,
+) -> Result<Vec<String>> {
+    let mut notes = Vec::new();
+    let chain = locale_chain(locale);
+    // the locale's own files: everything before the `en` fallback, or `en` itself
+    let own_len = chain
+        .iter()
+        .position(|t| t == BASE_LOCALE)
+        .filter(|&i| i > 0)
+        .unwrap_or(chain.len());
+    let mut own = BTreeSet::new();
+    let mut any_file = false;
+    for tag in &chain[..own_len] {
+        if let Some(table) = read_locale_file(dir, tag)? {
+            any_file = true;
+            flatten(&table, "", &mut own);
+        }
+    }
+    if !any_file {
+        let fallback = match &chain[own_len..] {
+            [] => "built-in defaults".to_string(),
+            rest => rest.join(" -> "),
+        };
+        notes.push(format!(
+            "Locale {}: no locale file in {} (falls back to {})",
+            locale,
+            dir.display(),
+            fallback
+        ));
+        return Ok(notes);
+    }
+    let missing: Vec<&str> = used.difference(&own).map(|s| s.as_str()).collect();
+    if !missing.is_empty() {
+        notes.push(format!(
+            "Locale {}: missing labels {}",
+            locale,
+            missing.join(", ")
+        ));
+    }
+    let unused: Vec<&str> = own
+        .difference(used)
+        .filter(|key| !site.contains(*key))
+        .map(|s| s.as_str())
+        .collect();
+    if !unused.is_empty() {
+        notes.push(format!(
+            "Locale {}: labels not used by the template: {}",
+            locale,
+            unused.join(", ")
+        ));
+    }
+    Ok(notes)
+}
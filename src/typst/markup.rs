@@ -0,0 +1,231 @@
+// Typst content as a small document tree, used for the generated parts of the
+// book (abstract bodies, take-home, reference). Abstract text only ever enters
+// the output through `Inline::Text`, which is escaped for the position it lands
+// in (line start, after an embedded `#expr`, single-line list items), so
+// arbitrary submissions cannot change the formatting or break compilation.
+// Typst code comes only from `Inline::Call` and is written verbatim; the page
+// layout lives in the book templates.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Inline {
+    Text(String),
+    Strong(Vec<Inline>),
+    Link {
+        target: Target,
+        body: Vec<Inline>,
+    },
+    /// Content block passed to a function, e.g. `#text(size: 6pt)[...]`.
+    Call {
+        func: String,
+        body: Vec<Inline>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+    Url(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Block {
+    Paragraph(Vec<Inline>),
+    List(Vec<Vec<Inline>>),
+}
+
+pub fn text(s: impl Into<String>) -> Inline {
+    Inline::Text(s.into())
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Document {
+    pub blocks: Vec<Block>,
+}
+
+impl Document {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, block: Block) {
+        self.blocks.push(block);
+    }
+
+    pub fn render(&self) -> String {
+        let mut w = Writer::default();
+        for block in &self.blocks {
+            w.block(block);
+        }
+        w.out
+    }
+}
+
+// A Typst string literal.
+pub fn string_literal(raw: &str) -> String {
+    let mut out = String::from('"');
+    for ch in raw.chars() {
+        match ch {
+            '"' | '\\' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            _ => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// Escape text for a single line of markup wherever a template prints it: at the
+// start of a line or content block, mid-line, or right after an expression.
+pub fn escape_line(input: &str) -> String {
+    let mut w = Writer {
+        single_line: true,
+        after_code: true,
+        ..Default::default()
+    };
+    w.text(input);
+    w.out
+}
+
+#[derive(Default)]
+struct Writer {
+    out: String,
+    // the previous output was an embedded `#expr`, which a following `.`, `(`
+    // or `[` would extend
+    after_code: bool,
+    // list items end at the newline
+    single_line: bool,
+}
+
+impl Writer {
+    fn at_line_start(&self) -> bool {
+        // a content block starts a line, also after leading spaces
+        if self.out.trim_end_matches([' ', '\t']).ends_with('[') {
+            return true;
+        }
+        // right after a list marker counts too: `- - x` nests a list
+        let tail = self.out.rsplit('\n').next().unwrap_or("").trim();
+        tail.is_empty() || tail == "-"
+    }
+
+    fn raw(&mut self, s: &str) {
+        self.out.push_str(s);
+        self.after_code = false;
+    }
+
+    fn text(&mut self, input: &str) {
+        let chars: Vec<char> = input.chars().filter(|c| *c != '\r').collect();
+        let mut i = 0;
+        while i < chars.len() {
+            let ch = chars[i];
+            let next = chars.get(i + 1).copied();
+            if ch == '\n' || ch == '\t' {
+                self.out.push(if self.single_line || ch == '\t' {
+                    ' '
+                } else {
+                    '\n'
+                });
+                self.after_code = false;
+                i += 1;
+                continue;
+            }
+            let line_start = self.at_line_start();
+            // `1.` at the start of a line is an enum item
+            if line_start && ch.is_ascii_digit() {
+                let mut j = i;
+                while j < chars.len() && chars[j].is_ascii_digit() {
+                    j += 1;
+                }
+                if chars.get(j) == Some(&'.') {
+                    self.out.extend(&chars[i..j]);
+                    self.out.push_str("\\.");
+                    self.after_code = false;
+                    i = j + 1;
+                    continue;
+                }
+            }
+            let escape = match ch {
+                '\\' | '#' | '$' | '@' | '`' | '<' | '>' | '_' | '*' | '[' | ']' | '{' | '}'
+                | '~' => true,
+                // comments, term lists
+                '/' => line_start || matches!(next, Some('/') | Some('*')),
+                // list marker, dash/minus/soft-hyphen shorthands
+                '-' => {
+                    line_start
+                        || matches!(next, Some('-') | Some('?'))
+                        || next.is_some_and(|c| c.is_numeric())
+                }
+                // enum items, headings
+                '+' | '=' => line_start,
+                // field access or call on a preceding expression
+                '.' | '(' => self.after_code,
+                _ => false,
+            };
+            if escape {
+                self.out.push('\\');
+            }
+            self.out.push(ch);
+            self.after_code = false;
+            i += 1;
+        }
+    }
+
+    fn inlines(&mut self, items: &[Inline]) {
+        for item in items {
+            self.inline(item);
+        }
+    }
+
+    fn content(&mut self, func: &str, body: &[Inline]) {
+        self.out.push('#');
+        self.out.push_str(func);
+        self.out.push('[');
+        self.inlines(body);
+        self.out.push(']');
+        self.after_code = true;
+    }
+
+    fn inline(&mut self, item: &Inline) {
+        match item {
+            Inline::Text(s) => self.text(s),
+            Inline::Strong(body) => self.content("strong", body),
+            Inline::Link { target, body } => {
+                let func = match target {
+                    Target::Url(u) => format!("link({})", string_literal(u)),
+                };
+                self.content(&func, body);
+            }
+            Inline::Call { func, body } => self.content(func, body),
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        match block {
+            Block::Paragraph(body) => {
+                if body.is_empty() {
+                    return;
+                }
+                self.inlines(body);
+                self.raw("\n\n");
+            }
+            Block::List(items) => {
+                for item in items {
+                    self.raw("- ");
+                    self.single_line = true;
+                    self.inlines(item);
+                    self.single_line = false;
+                    self.raw("\n");
+                }
+                self.raw("\n");
+            }
+        }
+    }
+}
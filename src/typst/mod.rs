@@ -0,0 +1,644 @@
+use anyhow::{Result, anyhow};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::fs::{File, create_dir_all, read_dir, read_to_string, remove_dir_all};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+#[cfg(feature = "embedded-typst")]
+pub mod compile;
+pub mod data;
+pub mod links;
+pub mod locale;
+pub mod markup;
+pub mod sourcemap;
+pub mod template;
+
+use crate::booklet::build::{FrontMatter, Sessions};
+use crate::booklet::{BookData, BookOptions, EventData, Origin};
+use crate::io::cache::{self, Fingerprint};
+use markup::string_literal;
+use sourcemap::{Diagnostic, SourceMap};
+use template::{BookContext, Markup};
+
+#[derive(Debug, Clone)]
+pub struct EmitOptions {
+    /// Also write `typst/data_<locale>.json` for Typst-native templates.
+    pub data_json: bool,
+    /// Reuse session renders from `output/.cache/`.
+    pub cache: bool,
+    /// Print the author index (`[index] authors`).
+    pub author_index: bool,
+}
+
+// Emit typst files by reading `outdir/manifest.json` and per-abstract markdown
+// frontmatter (`emit-typst`); `build` writes them from the `booklet::Booklet`.
+pub fn emit_typst(
+    outdir: &str,
+    locales_csv: &str,
+    template: &Option<String>,
+    book: &BookOptions,
+    opts: &EmitOptions,
+) -> Result<()> {
+    let (event, partial, all_sessions) = read_output(outdir)?;
+    let editions = crate::booklet::build::editions(
+        &all_sessions,
+        &event,
+        partial.as_deref(),
+        locales_csv,
+        template,
+        book,
+    )?;
+    for (book, origins) in &editions {
+        write_book(outdir, template, book, origins, opts)?;
+    }
+    Ok(())
+}
+
+// `typst/book_<locale>.typ` with its session include files and source map and,
+// with `--data-json`, the book data next to it.
+pub fn write_book(
+    outdir: &str,
+    template: &Option<String>,
+    book: &BookData,
+    origins: &HashMap<String, Origin>,
+    opts: &EmitOptions,
+) -> Result<()> {
+    let typst_dir = Path::new(outdir).join("typst");
+    create_dir_all(&typst_dir)?;
+    let locale = book.locale.as_str();
+    let filename = format!("book_{}.typ", locale);
+    let path = typst_dir.join(&filename);
+
+    let data_file = if opts.data_json {
+        data::write_data(&typst_dir, book, opts.author_index)?;
+        Some(data::data_file_name(locale))
+    } else {
+        None
+    };
+    let mut ctx = BookContext::new(book, data_file.as_deref(), opts.author_index);
+    let session_files = if template::has_session_partial(template) {
+        write_sessions(outdir, locale, template, &mut ctx, opts.cache)?
+    } else {
+        Vec::new()
+    };
+    let out_text = template::render_book(template, &ctx)?;
+    let mut f = File::create(&path)?;
+    write!(f, "{}", out_text)?;
+
+    let mut map = SourceMap::default();
+    for (name, text) in &session_files {
+        // only files the book actually includes
+        if out_text.contains(&string_literal(name)) {
+            map.add(name, text, &ctx, origins);
+        }
+    }
+    map.add(&filename, &out_text, &ctx, origins);
+    sourcemap::write_map(&typst_dir, locale, &map)
+}
+
+// Event details, partial label and sessions from `outdir/manifest.json` and the
+// per-abstract markdown files.
+fn read_output(outdir: &str) -> Result<(EventData, Option<String>, Sessions)> {
+    // read manifest
+    let mf_path = Path::new(outdir).join("manifest.json");
+    if !mf_path.exists() {
+        return Err(anyhow!(
+            "manifest.json not found in output directory: {}",
+            mf_path.display()
+        ));
+    }
+    let mf_text = read_to_string(&mf_path)?;
+    let mf: JsonValue = serde_json::from_str(&mf_text)?;
+
+    let event = event_data(&mf);
+    let partial = partial_label(outdir);
+    let mut all_sessions: Sessions = Vec::new();
+    if let Some(sessions) = mf.get("sessions").and_then(|s| s.as_array()) {
+        for sess in sessions.iter() {
+            let sess_title = sess
+                .get("title")
+                .and_then(|t| t.as_str())
+                .unwrap_or("")
+                .to_string();
+            let sess_slug = sess.get("slug").and_then(|s| s.as_str()).unwrap_or("");
+            if sess_slug.is_empty() {
+                continue;
+            }
+            let session_dir = Path::new(outdir).join(sess_slug);
+            if !session_dir.exists() {
+                continue;
+            }
+            // collect markdown files in session dir
+            let mut abstracts: Vec<(FrontMatter, String)> = Vec::new();
+            let mut entries: Vec<_> = read_dir(&session_dir)?.filter_map(|r| r.ok()).collect();
+            entries.sort_by_key(|e| e.path());
+            for ent in entries {
+                let p = ent.path();
+                if p.extension().and_then(|e| e.to_str()).unwrap_or("") != "md" {
+                    continue;
+                }
+                let txt = read_to_string(&p)?;
+                // parse frontmatter between first two '---' lines
+                if let Some(start) = txt.find("---")
+                    && let Some(rest) = txt[start + 3..].find("---")
+                {
+                    let fm_text = &txt[start + 3..start + 3 + rest];
+                    let body = txt[start + 3 + rest + 3..].trim().to_string();
+                    match serde_yaml::from_str::<FrontMatter>(fm_text) {
+                        Ok(mut fm) => {
+                            fm.file =
+                                format!("{}/{}", sess_slug, ent.file_name().to_string_lossy());
+                            abstracts.push((fm, body));
+                        }
+                        Err(_) => continue,
+                    }
+                }
+            }
+
+            if abstracts.is_empty() {
+                continue;
+            }
+            // sessions with the same title are merged
+            if let Some((_, _, v)) = all_sessions.iter_mut().find(|(t, _, _)| t == &sess_title) {
+                v.extend(abstracts);
+            } else {
+                all_sessions.push((sess_title, sess_slug.to_string(), abstracts));
+            }
+        }
+    }
+    Ok((event, partial, all_sessions))
+}
+
+// Render every session into `typst/sessions_<locale>/NN-<slug>.typ` and point
+// the book context at it. Renders are cached by a hash of everything the
+// session partial sees, so unchanged sessions are copied rather than rendered.
+fn write_sessions(
+    outdir: &str,
+    locale: &str,
+    template: &Option<String>,
+    ctx: &mut BookContext,
+    use_cache: bool,
+) -> Result<Vec<(String, String)>> {
+    let dir_name = format!("sessions_{}", locale);
+    let dir = Path::new(outdir).join("typst").join(&dir_name);
+    if dir.exists() {
+        remove_dir_all(&dir)?;
+    }
+    create_dir_all(&dir)?;
+    let cache_dir = cache::cache_dir(outdir).join("sessions").join(locale);
+    if use_cache {
+        create_dir_all(&cache_dir)?;
+    }
+    let (template_dir, _) = template::template_location(template);
+    let mut shared = Fingerprint::new();
+    shared
+        .path_where(&template_dir, &|p| {
+            p.extension().is_some_and(|e| e == "typ")
+        })?
+        .str(&serde_json::to_string(&(
+            ctx.locale,
+            ctx.event,
+            ctx.labels,
+            ctx.partial,
+            ctx.index_letters,
+        ))?);
+    let shared = shared.finish();
+
+    let mut files = Vec::new();
+    let mut used = Vec::new();
+    for index in 0..ctx.sessions.len() {
+        let name = format!(
+            "{}/{:02}-{}.typ",
+            dir_name,
+            index + 1,
+            ctx.sessions[index].slug
+        );
+        let key = Fingerprint::new()
+            .str(&shared)
+            .str(&serde_json::to_string(&ctx.sessions[index])?)
+            .finish();
+        let cached = cache_dir.join(format!("{}.typ", key));
+        let text = if use_cache && cached.is_file() {
+            read_to_string(&cached)?
+        } else {
+            let text = template::render_session(template, ctx, index)?;
+            if use_cache {
+                std::fs::write(&cached, &text)?;
+            }
+            text
+        };
+        used.push(cached);
+        std::fs::write(Path::new(outdir).join("typst").join(&name), &text)?;
+        ctx.sessions[index].file = Some(Markup::new(string_literal(&name)));
+        files.push((name, text));
+    }
+    if use_cache {
+        // drop renders of sessions that changed or went away
+        for entry in read_dir(&cache_dir)?.filter_map(|e| e.ok()) {
+            if !used.contains(&entry.path()) {
+                std::fs::remove_file(entry.path())?;
+            }
+        }
+    }
+    Ok(files)
+}
+
+// Event details recorded in the manifest by `build`.
+fn event_data(mf: &JsonValue) -> EventData {
+    let details = mf.get("event_details");
+    let field = |key: &str| {
+        details
+            .and_then(|d| d.get(key))
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+    };
+    EventData {
+        id: field("id")
+            .or_else(|| mf.get("event").and_then(|e| e.as_str()).map(String::from))
+            .unwrap_or_default(),
+        title: field("title").unwrap_or_default(),
+        acronym: field("acronym").unwrap_or_default(),
+        start: field("start").unwrap_or_default(),
+        time_zone: field("time_zone").unwrap_or_default(),
+        url: field("url"),
+    }
+}
+
+// PDF name for a locale; partial (subset) builds carry their filter label so they
+// are never mistaken for the full booklet.
+pub fn pdf_file_name(locale: &str, partial: Option<&str>) -> String {
+    book_file_name(locale, partial, "pdf")
+}
+
+// Booklet file name for any format (`pdf`, `epub`).
+pub fn book_file_name(locale: &str, partial: Option<&str>, ext: &str) -> String {
+    match partial.filter(|p| !p.is_empty()) {
+        Some(label) => format!("symposium-2026_{}_partial_{}.{}", locale, label, ext),
+        None => format!("symposium-2026_{}.{}", locale, ext),
+    }
+}
+
+// Subset label recorded in `outdir/manifest.json` by a partial build, if any.
+pub fn partial_label(outdir: &str) -> Option<String> {
+    let txt = read_to_string(Path::new(outdir).join("manifest.json")).ok()?;
+    let mf: JsonValue = serde_json::from_str(&txt).ok()?;
+    mf.get("partial")?
+        .get("label")?
+        .as_str()
+        .map(|s| s.to_string())
+}
+
+// Page preview image formats (`--preview png,svg`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PreviewFormat {
+    Png,
+    Svg,
+}
+
+impl PreviewFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            PreviewFormat::Png => "png",
+            PreviewFormat::Svg => "svg",
+        }
+    }
+}
+
+// How PDFs are produced after the typst files are written.
+#[derive(Debug, Clone, Default)]
+pub struct PdfOptions {
+    /// External typst binary; without it the embedded compiler is used when
+    /// built with `embedded-typst`, else `typst` from PATH.
+    pub typst_bin: Option<String>,
+    /// Stop after writing the typst files.
+    pub no_pdf: bool,
+    pub previews: Vec<PreviewFormat>,
+    pub template: Option<String>,
+    /// Locales compiled at the same time; default the number of CPUs.
+    pub jobs: Option<usize>,
+    /// Reuse a locale's PDF from `output/.cache/` when its sources are unchanged.
+    pub cache: bool,
+}
+
+// Fonts bundled with the template (`<template>/fonts`), else the starter's.
+pub fn font_dir(template: &Option<String>) -> PathBuf {
+    let own = template::template_location(template).0.join("fonts");
+    if own.is_dir() {
+        own
+    } else {
+        Path::new(template::DEFAULT_TEMPLATE_DIR).join("fonts")
+    }
+}
+
+// `<outdir>/preview/<locale>`: one image per page and preview format.
+pub fn preview_dir(outdir: &str, locale: &str) -> PathBuf {
+    Path::new(outdir).join("preview").join(locale)
+}
+
+// Compile `book_<locale>.typ` for every locale, up to `jobs` at a time.
+// Templates address their assets from the working directory (`/templates/...`),
+// so that is the typst binary's project root. The embedded compiler is rooted
+// at the output directory and reads only the template tree from the working
+// directory, so the output may live anywhere. A missing typst is an error
+// unless `no_pdf` is set.
+pub fn compile_pdfs(outdir: &str, locales_csv: &str, opts: &PdfOptions) -> Result<()> {
+    if opts.no_pdf {
+        tracing::info!(
+            "Skipping PDFs (--no-pdf); typst files are in {}/typst",
+            outdir
+        );
+        return Ok(());
+    }
+    let backend = backend(opts)?;
+    let locales: Vec<&str> = crate::util::locales(locales_csv).collect();
+    let jobs = opts
+        .jobs
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+        .clamp(1, locales.len().max(1));
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::new());
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| {
+                while let Some(locale) = locales.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let result = compile_locale(outdir, locale, opts, &backend);
+                    results.lock().unwrap().push((*locale, result));
+                }
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(locale, _)| locales.iter().position(|l| l == locale));
+    let errors: Vec<String> = results
+        .into_iter()
+        .filter_map(|(_, result)| result.err())
+        .map(|e| format!("{:#}", e))
+        .collect();
+    if !errors.is_empty() {
+        return Err(anyhow!("{}", errors.join("\n")));
+    }
+    Ok(())
+}
+
+fn compile_locale(outdir: &str, locale: &str, opts: &PdfOptions, backend: &Backend) -> Result<()> {
+    let typst_file = Path::new(outdir)
+        .join("typst")
+        .join(format!("book_{}.typ", locale));
+    let pdf_name = pdf_file_name(locale, partial_label(outdir).as_deref());
+    let out_pdf = Path::new(outdir).join(&pdf_name);
+    let previews = preview_dir(outdir, locale);
+    let fonts = font_dir(&opts.template);
+
+    // a locale whose typst sources, data and template are unchanged reuses its last PDF
+    let cached = if opts.cache {
+        Some(pdf_cache_dir(outdir, locale, opts, backend, &fonts)?)
+    } else {
+        None
+    };
+    if let Some(dir) = &cached
+        && dir.join(&pdf_name).is_file()
+    {
+        tracing::info!(
+            "Unchanged typst sources for {}; reusing {}",
+            locale,
+            out_pdf.display()
+        );
+        std::fs::copy(dir.join(&pdf_name), &out_pdf)?;
+        copy_files(&dir.join("preview"), &previews)?;
+        return Ok(());
+    }
+
+    tracing::info!(
+        "Running typst: {} -> {}",
+        typst_file.display(),
+        out_pdf.display()
+    );
+    let diags = match backend {
+        Backend::Binary(bin) => {
+            let mut diags = run_external(bin, &fonts, &typst_file, &out_pdf, None)?;
+            for format in &opts.previews {
+                create_dir_all(&previews)?;
+                let pattern = previews.join(format!("page-{{0p}}.{}", format.extension()));
+                // warnings were reported by the PDF run already
+                let mut more = run_external(bin, &fonts, &typst_file, &pattern, Some(*format))?;
+                more.retain(|d| d.error);
+                diags.extend(more);
+            }
+            diags
+        }
+        #[cfg(feature = "embedded-typst")]
+        Backend::Embedded => compile::compile_book(
+            Path::new(outdir),
+            &template::template_location(&opts.template).0,
+            &typst_file,
+            &fonts,
+            &out_pdf,
+            &opts.previews,
+            &previews,
+        )?,
+    };
+    report_diagnostics(outdir, locale, &diags)?;
+
+    if let Some(dir) = &cached {
+        // one cached PDF per locale
+        if let Some(parent) = dir.parent()
+            && parent.exists()
+        {
+            remove_dir_all(parent)?;
+        }
+        create_dir_all(dir)?;
+        std::fs::copy(&out_pdf, dir.join(&pdf_name))?;
+        if !opts.previews.is_empty() {
+            copy_files(&previews, &dir.join("preview"))?;
+        }
+    }
+    Ok(())
+}
+
+// `.cache/pdf/<locale>/<hash>/`, the hash covering what the PDF is made from.
+fn pdf_cache_dir(
+    outdir: &str,
+    locale: &str,
+    opts: &PdfOptions,
+    backend: &Backend,
+    fonts: &Path,
+) -> Result<PathBuf> {
+    let typst_dir = Path::new(outdir).join("typst");
+    let mut fp = Fingerprint::new();
+    fp.str(backend.name())
+        .str(&format!("{:?}", opts.previews))
+        .path(&typst_dir.join(format!("book_{}.typ", locale)))?
+        .path(&typst_dir.join(format!("sessions_{}", locale)))?
+        .path(&typst_dir.join(data::data_file_name(locale)))?
+        .path(&template::template_location(&opts.template).0)?
+        .path(fonts)?;
+    Ok(cache::cache_dir(outdir)
+        .join("pdf")
+        .join(locale)
+        .join(fp.finish()))
+}
+
+fn copy_files(from: &Path, to: &Path) -> Result<()> {
+    if !from.is_dir() {
+        return Ok(());
+    }
+    create_dir_all(to)?;
+    for entry in read_dir(from)?.filter_map(|e| e.ok()) {
+        if entry.path().is_file() {
+            std::fs::copy(entry.path(), to.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
+// Warnings are logged and errors fail the build, both described through
+// `sourcemap_<locale>.json` as abstract, row and column where possible.
+fn report_diagnostics(outdir: &str, locale: &str, diags: &[Diagnostic]) -> Result<()> {
+    let map = sourcemap::read_map(&Path::new(outdir).join("typst"), locale);
+    let describe = |d: &Diagnostic| match &map {
+        Some(map) => map.describe(d),
+        None => sourcemap::describe_plain(d),
+    };
+    for d in diags.iter().filter(|d| !d.error) {
+        tracing::warn!("typst ({}): {}", locale, describe(d));
+    }
+    let errors: Vec<String> = diags.iter().filter(|d| d.error).map(describe).collect();
+    if !errors.is_empty() {
+        return Err(anyhow!(
+            "typst failed for locale {}:\n  {}",
+            locale,
+            errors.join("\n  ")
+        ));
+    }
+    Ok(())
+}
+
+enum Backend {
+    Binary(String),
+    #[cfg(feature = "embedded-typst")]
+    Embedded,
+}
+
+impl Backend {
+    fn name(&self) -> &str {
+        match self {
+            Backend::Binary(bin) => bin,
+            #[cfg(feature = "embedded-typst")]
+            Backend::Embedded => "embedded",
+        }
+    }
+}
+
+fn backend(opts: &PdfOptions) -> Result<Backend> {
+    #[cfg(feature = "embedded-typst")]
+    if opts.typst_bin.is_none() {
+        return Ok(Backend::Embedded);
+    }
+    let bin = opts
+        .typst_bin
+        .clone()
+        .unwrap_or_else(|| "typst".to_string());
+    match Command::new(&bin).arg("--version").output() {
+        Ok(o) if o.status.success() => Ok(Backend::Binary(bin)),
+        _ => Err(anyhow!(
+            "typst binary '{}' not found or not runnable. Install typst, pass --typst-bin, \
+             build with `--features embedded-typst`, or skip PDFs with --no-pdf",
+            bin
+        )),
+    }
+}
+
+fn run_external(
+    bin: &str,
+    fonts: &Path,
+    typst_file: &Path,
+    output: &Path,
+    format: Option<PreviewFormat>,
+) -> Result<Vec<Diagnostic>> {
+    let mut cmd = Command::new(bin);
+    cmd.arg("compile")
+        .arg("--root")
+        .arg(".")
+        .arg("--font-path")
+        .arg(fonts);
+    if let Some(format) = format {
+        cmd.arg("--format").arg(format.extension());
+    }
+    // typst CLI accepts OUTPUT as a positional argument rather than `-o` in some versions
+    let out = cmd
+        .arg("--diagnostic-format")
+        .arg("short")
+        .arg(typst_file)
+        .arg(output)
+        .output()?;
+    let (mut diags, other) = sourcemap::parse_short(&String::from_utf8_lossy(&out.stderr));
+    if !out.status.success() && !diags.iter().any(|d| d.error) {
+        // nothing we could parse: pass typst's own output on
+        diags.push(Diagnostic {
+            file: typst_file.display().to_string(),
+            line: 0,
+            column: 0,
+            error: true,
+            message: if other.is_empty() {
+                format!("typst exited with {}", out.status)
+            } else {
+                other.join("\n  ")
+            },
+        });
+    }
+    Ok(diags)
+}
+
+// Emit a plan of typst files that would be generated.
+pub fn emit_typst_plan(
+    outdir: &str,
+    locales_csv: &str,
+    template: &Option<String>,
+    partial: Option<&str>,
+    data_json: bool,
+    plan: &mut crate::io::plan::Plan,
+) -> Result<()> {
+    use crate::io::plan::PlanAction;
+
+    let typst_dir = Path::new(outdir).join("typst");
+    plan.push(PlanAction::CreateDir {
+        path: PathBuf::from(&typst_dir),
+    });
+
+    for locale in crate::util::locales(locales_csv) {
+        if data_json {
+            plan.push(PlanAction::WriteFile {
+                path: typst_dir.join(data::data_file_name(locale)),
+                summary: format!("book data for {}", locale),
+            });
+        }
+        let filename = format!("book_{}.typ", locale);
+        let path = typst_dir.join(&filename);
+        let (template_dir, entry) = template::template_location(template);
+        let template_name = template_dir.join(entry).display().to_string();
+        let font_path = font_dir(template);
+        let cmd = Some(format!(
+            "typst compile --root . --font-path {} {} {}",
+            font_path.display(),
+            path.display(),
+            Path::new(outdir)
+                .join(pdf_file_name(locale, partial))
+                .display()
+        ));
+        plan.push(PlanAction::EmitTypst {
+            path,
+            template: template_name,
+            command: cmd,
+        });
+        plan.push(PlanAction::WriteFile {
+            path: typst_dir.join(sourcemap::map_file_name(locale)),
+            summary: format!("source map for {}", locale),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,228 @@
+use super::markup::escape_line;
+use super::template::BookContext;
+use crate::booklet::Origin;
+use crate::model::SourceRef;
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, read_to_string};
+use std::io::Write;
+use std::path::Path;
+
+// Where each abstract landed in the generated `book_<locale>.typ` and its
+// session include files, written as `typst/sourcemap_<locale>.json`. Typst
+// diagnostics on those lines are reported as "abstract A17 (row 23, column
+// 'Resumé'): ..." instead of a generated line.
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SourceMap {
+    pub entries: Vec<MapEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MapEntry {
+    /// Generated file relative to `typst/`, e.g. `sessions_en/01-posters.typ`.
+    pub file: String,
+    /// 1-based, inclusive line range of the abstract.
+    pub start_line: usize,
+    pub end_line: usize,
+    pub abstract_id: String,
+    /// Typst label of the abstract (`abs-a17`).
+    pub label: String,
+    pub session: String,
+    /// Markdown file of the abstract, relative to the output directory.
+    pub markdown: String,
+    pub source: Option<SourceRef>,
+    /// Lines of the printed fields (`title`, `authors`, `abstract`,
+    /// `take_home`, `reference`), keyed like `SourceRef::columns`.
+    pub fields: Vec<FieldSpan>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FieldSpan {
+    pub field: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+pub fn map_file_name(locale: &str) -> String {
+    format!("sourcemap_{}.json", locale)
+}
+
+// 1-based line of a byte offset.
+fn line_of(starts: &[usize], byte: usize) -> usize {
+    starts.partition_point(|&s| s <= byte)
+}
+
+impl SourceMap {
+    // Locate the abstracts of `ctx` in the rendered `file`. An abstract starts
+    // at the line holding its first `<label>` and owns the text up to the next
+    // one; its fields are found by their printed (escaped or generated) form.
+    // Abstracts mapped from an earlier file are skipped, so add session files
+    // before the book, whose indexes only refer to the labels.
+    pub fn add(
+        &mut self,
+        file: &str,
+        text: &str,
+        ctx: &BookContext,
+        origins: &HashMap<String, Origin>,
+    ) {
+        let mut starts = vec![0];
+        starts.extend(text.match_indices('\n').map(|(i, _)| i + 1));
+
+        let mut found = Vec::new();
+        for abs in ctx.sessions.iter().flat_map(|s| s.abstracts.iter()) {
+            let label = abs.label.as_str();
+            if self.entries.iter().any(|e| e.label == label) {
+                continue;
+            }
+            if let Some(pos) = text.find(&format!("<{}>", label)) {
+                // from the start of the label's line
+                let begin = starts[line_of(&starts, pos) - 1];
+                found.push((begin, abs, label));
+            }
+        }
+        found.sort_by_key(|(begin, _, _)| *begin);
+
+        for (i, (begin, abs, label)) in found.iter().enumerate() {
+            let end = found.get(i + 1).map(|f| f.0).unwrap_or(text.len());
+            let region = &text[*begin..end];
+            let mut needles: Vec<(&str, String)> = vec![("title", escape_line(&abs.title))];
+            needles.extend(abs.authors.iter().map(|a| ("authors", escape_line(a))));
+            needles.extend(
+                abs.sections
+                    .iter()
+                    .map(|s| ("abstract", s.body.as_str().trim().to_string())),
+            );
+            if let Some(m) = &abs.take_home {
+                needles.push(("take_home", m.as_str().trim().to_string()));
+            }
+            if let Some(m) = &abs.reference {
+                needles.push(("reference", m.as_str().trim().to_string()));
+            }
+            let mut fields = Vec::new();
+            for (field, needle) in needles {
+                if needle.is_empty() {
+                    continue;
+                }
+                if let Some(at) = region.find(&needle) {
+                    let from = begin + at;
+                    fields.push(FieldSpan {
+                        field: field.to_string(),
+                        start_line: line_of(&starts, from),
+                        end_line: line_of(&starts, from + needle.len() - 1),
+                    });
+                }
+            }
+            let start_line = line_of(&starts, *begin);
+            let end_line = fields
+                .iter()
+                .map(|f| f.end_line)
+                .max()
+                .unwrap_or(start_line)
+                .max(start_line);
+            let origin = origins.get(*label);
+            self.entries.push(MapEntry {
+                file: file.to_string(),
+                start_line,
+                end_line,
+                abstract_id: abs.id.clone(),
+                label: label.to_string(),
+                session: origin.map(|o| o.session.clone()).unwrap_or_default(),
+                markdown: origin.map(|o| o.markdown.clone()).unwrap_or_default(),
+                source: origin.and_then(|o| o.source.clone()),
+                fields,
+            });
+        }
+    }
+}
+
+pub fn write_map(typst_dir: &Path, locale: &str, map: &SourceMap) -> Result<()> {
+    let mut f = File::create(typst_dir.join(map_file_name(locale)))?;
+    write!(f, "{}", serde_json::to_string_pretty(map)?)?;
+    Ok(())
+}
+
+pub fn read_map(typst_dir: &Path, locale: &str) -> Option<SourceMap> {
+    let txt = read_to_string(typst_dir.join(map_file_name(locale))).ok()?;
+    serde_json::from_str(&txt).ok()
+}
+
+// One Typst diagnostic: file, 1-based line, column, severity and message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub error: bool,
+    pub message: String,
+}
+
+// `typst compile --diagnostic-format short` lines:
+// `out/typst/book_en.typ:12:4: error: unclosed delimiter`.
+pub fn parse_short(output: &str) -> (Vec<Diagnostic>, Vec<String>) {
+    let re = Regex::new(r"^(.+?):(\d+):(\d+): (error|warning): (.*)$").unwrap();
+    let mut diags = Vec::new();
+    let mut other = Vec::new();
+    for line in output.lines().filter(|l| !l.trim().is_empty()) {
+        match re.captures(line) {
+            Some(c) => diags.push(Diagnostic {
+                file: c[1].to_string(),
+                line: c[2].parse().unwrap_or(0),
+                column: c[3].parse().unwrap_or(0),
+                error: &c[4] == "error",
+                message: c[5].to_string(),
+            }),
+            None => other.push(line.to_string()),
+        }
+    }
+    (diags, other)
+}
+
+impl SourceMap {
+    fn entry(&self, diag: &Diagnostic) -> Option<&MapEntry> {
+        let path = Path::new(&diag.file);
+        self.entries.iter().find(|e| {
+            path.ends_with(&e.file) && e.start_line <= diag.line && diag.line <= e.end_line
+        })
+    }
+
+    // The diagnostic in terms of the input: abstract, row and column when the
+    // line belongs to an abstract, else the generated file and line.
+    pub fn describe(&self, diag: &Diagnostic) -> String {
+        let Some(entry) = self.entry(diag) else {
+            return describe_plain(diag);
+        };
+        let field = entry
+            .fields
+            .iter()
+            .find(|f| f.start_line <= diag.line && diag.line <= f.end_line);
+        let mut place = Vec::new();
+        match &entry.source {
+            Some(source) => {
+                place.push(format!("row {}", source.row));
+                if let Some(column) = field.and_then(|f| source.columns.get(&f.field)) {
+                    place.push(format!("column '{}'", column));
+                }
+            }
+            None => place.push(entry.markdown.clone()),
+        }
+        format!(
+            "abstract {} ({}): {}",
+            entry.abstract_id,
+            place.join(", "),
+            diag.message
+        )
+    }
+}
+
+pub fn describe_plain(diag: &Diagnostic) -> String {
+    if diag.file.is_empty() {
+        return diag.message.clone();
+    }
+    format!(
+        "{}:{}:{}: {}",
+        diag.file, diag.line, diag.column, diag.message
+    )
+}
@@ -0,0 +1,290 @@
+use super::markup::{Block, Document, escape_line, string_literal};
+use crate::booklet::{AbstractData, BookData, EventData, IndexEntry, TagRefData};
+use anyhow::{Result, anyhow};
+use minijinja::syntax::SyntaxConfig;
+use minijinja::value::{Serde, Value, merge_maps};
+use minijinja::{Environment, context, path_loader};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use toml::value::Table;
+
+// Book templates are Jinja (minijinja) templates around Typst code. Rust hands
+// over the parsed book as data; the templates (book.typ with session, abstract
+// and tag index partials) decide the layout. Every value printed with `{{ }}` is
+// escaped as Typst text, except fields that already hold generated markup.
+
+pub const DEFAULT_TEMPLATE_DIR: &str = "templates/starter";
+const ENTRY: &str = "book.typ";
+const SESSION_PARTIAL: &str = "partials/session.typ";
+
+#[derive(Debug, Serialize)]
+pub struct BookContext<'a> {
+    pub locale: &'a str,
+    pub event: &'a EventData,
+    pub labels: &'a Table,
+    pub partial: Option<&'a str>,
+    /// `data_<locale>.json` as a Typst string literal when `--data-json` wrote it.
+    pub data_file: Option<Markup>,
+    pub sessions: Vec<SessionContext>,
+    pub tag_index: Vec<TagEntry>,
+    pub author_index: Vec<NameEntry>,
+    pub affiliation_index: Vec<NameEntry>,
+    /// Print group letter headings in the indexes.
+    pub index_letters: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionContext {
+    pub title: String,
+    pub slug: String,
+    pub abstracts: Vec<AbstractContext>,
+    /// Include file holding the rendered session, as a Typst string literal
+    /// (`#include {{ session.file }}`); none when the template has no session
+    /// partial.
+    pub file: Option<Markup>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AbstractContext {
+    pub id: String,
+    /// Typst label name, e.g. `abs-a1` (print as `<{{ abstract.label }}>`).
+    pub label: Markup,
+    pub locale: String,
+    pub untranslated: bool,
+    pub title: String,
+    pub authors: Vec<String>,
+    pub affiliations: Vec<String>,
+    pub sections: Vec<SectionContext>,
+    pub take_home: Option<Markup>,
+    pub reference: Option<Markup>,
+    /// Tags in display form (`#patient_safety`).
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SectionContext {
+    pub label: String,
+    pub text: String,
+    pub body: Markup,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagEntry {
+    pub tag: String,
+    pub letter: String,
+    pub abstracts: Vec<TagRef>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NameEntry {
+    pub name: String,
+    pub letter: String,
+    pub abstracts: Vec<TagRef>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagRef {
+    pub title: String,
+    pub label: Markup,
+}
+
+/// Generated markup (Typst, or HTML for the site); printed by templates without
+/// escaping.
+#[derive(Debug, Clone, Serialize)]
+pub struct Markup(Value);
+
+impl Markup {
+    pub fn new(markup: impl Into<String>) -> Self {
+        Markup(Value::from_safe_string(markup.into()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str().unwrap_or_default()
+    }
+}
+
+// Typst markup for generated blocks.
+fn render_blocks(blocks: &[Block]) -> Markup {
+    let mut doc = Document::new();
+    for block in blocks {
+        doc.push(block.clone());
+    }
+    let rendered = doc.render();
+    // a list must start its own line when the template puts a label before it
+    let lead = if matches!(blocks.first(), Some(Block::List(_))) {
+        "\n"
+    } else {
+        ""
+    };
+    Markup::new(format!("{}{}", lead, rendered.trim_end()))
+}
+
+impl<'a> BookContext<'a> {
+    // `authors`: print the author index.
+    pub fn new(data: &'a BookData, data_file: Option<&str>, authors: bool) -> Self {
+        let sessions = data
+            .sessions
+            .iter()
+            .map(|session| SessionContext {
+                title: session.title.clone(),
+                slug: session.slug.clone(),
+                abstracts: session.abstracts.iter().map(abstract_context).collect(),
+                file: None,
+            })
+            .collect();
+        let tag_index = data
+            .tag_index
+            .iter()
+            .map(|entry| TagEntry {
+                tag: entry.tag.clone(),
+                letter: entry.letter.clone(),
+                abstracts: tag_refs(&entry.abstracts),
+            })
+            .collect();
+        let names = |entries: &[IndexEntry]| {
+            entries
+                .iter()
+                .map(|entry| NameEntry {
+                    name: entry.name.clone(),
+                    letter: entry.letter.clone(),
+                    abstracts: tag_refs(&entry.abstracts),
+                })
+                .collect()
+        };
+        BookContext {
+            locale: &data.locale,
+            event: &data.event,
+            labels: &data.labels,
+            partial: data.partial.as_deref(),
+            data_file: data_file.map(|f| Markup::new(string_literal(f))),
+            sessions,
+            tag_index,
+            author_index: if authors {
+                names(&data.author_index)
+            } else {
+                Vec::new()
+            },
+            affiliation_index: names(&data.affiliation_index),
+            index_letters: data.index_letters,
+        }
+    }
+}
+
+fn tag_refs(refs: &[TagRefData]) -> Vec<TagRef> {
+    refs.iter()
+        .map(|r| TagRef {
+            title: r.title.clone(),
+            label: Markup::new(r.label.clone()),
+        })
+        .collect()
+}
+
+fn abstract_context(abs: &AbstractData) -> AbstractContext {
+    AbstractContext {
+        id: abs.id.clone(),
+        label: Markup::new(abs.label.clone()),
+        locale: abs.locale.clone(),
+        untranslated: abs.untranslated,
+        title: abs.title.clone(),
+        authors: abs.authors.clone(),
+        affiliations: abs.affiliations.clone(),
+        sections: abs
+            .sections
+            .iter()
+            .map(|s| SectionContext {
+                label: s.label.clone(),
+                text: s.text.clone(),
+                body: render_blocks(&s.body),
+            })
+            .collect(),
+        take_home: abs.take_home.as_deref().map(render_blocks),
+        reference: abs.reference.as_deref().map(render_blocks),
+        tags: abs.tags.clone(),
+    }
+}
+
+// Template directory and entry file for `--template`, which names either a
+// directory holding `book.typ` or the entry file itself.
+pub fn template_location(template: &Option<String>) -> (PathBuf, String) {
+    let path = PathBuf::from(template.as_deref().unwrap_or(DEFAULT_TEMPLATE_DIR));
+    if path.is_file() {
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let entry = path
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_else(|| ENTRY.to_string());
+        (dir, entry)
+    } else {
+        (path, ENTRY.to_string())
+    }
+}
+
+fn environment(dir: &Path) -> Result<Environment<'static>> {
+    let mut env = Environment::new();
+    env.set_syntax(
+        SyntaxConfig::builder()
+            .trim_blocks(true)
+            .lstrip_blocks(true)
+            .keep_trailing_newline(true)
+            .build()
+            .map_err(|e| anyhow!("template syntax: {}", e))?,
+    );
+    env.set_loader(path_loader(dir));
+    env.set_formatter(|out, _state, value| {
+        if value.is_undefined() || value.is_none() {
+            return Ok(());
+        }
+        if value.is_safe() {
+            return out
+                .write_str(value.as_str().unwrap_or_default())
+                .map_err(Into::into);
+        }
+        out.write_str(&escape_line(&value.to_string()))
+            .map_err(Into::into)
+    });
+    Ok(env)
+}
+
+// Templates with `partials/session.typ` get each session rendered into its own
+// include file.
+pub fn has_session_partial(template: &Option<String>) -> bool {
+    template_location(template)
+        .0
+        .join(SESSION_PARTIAL)
+        .is_file()
+}
+
+// The session partial for `ctx.sessions[index]`, with the book context around it.
+pub fn render_session(
+    template: &Option<String>,
+    ctx: &BookContext,
+    index: usize,
+) -> Result<String> {
+    let (dir, _) = template_location(template);
+    let env = environment(&dir)?;
+    let tmpl = env
+        .get_template(SESSION_PARTIAL)
+        .map_err(|e| anyhow!("template {}: {:#}", dir.join(SESSION_PARTIAL).display(), e))?;
+    let values = merge_maps([
+        context! { session => Value::from(Serde(&ctx.sessions[index])) },
+        Value::from(Serde(ctx)),
+    ]);
+    tmpl.render(values).map_err(|e| {
+        anyhow!(
+            "rendering {} for session '{}': {:#}",
+            dir.join(SESSION_PARTIAL).display(),
+            ctx.sessions[index].title,
+            e
+        )
+    })
+}
+
+pub fn render_book(template: &Option<String>, ctx: &BookContext) -> Result<String> {
+    let (dir, entry) = template_location(template);
+    let env = environment(&dir)?;
+    let tmpl = env
+        .get_template(&entry)
+        .map_err(|e| anyhow!("template {}: {:#}", dir.join(&entry).display(), e))?;
+    tmpl.render(Value::from(Serde(ctx)))
+        .map_err(|e| anyhow!("rendering {}: {:#}", dir.join(&entry).display(), e))
+}
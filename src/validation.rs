@@ -1,15 +1,131 @@
+use crate::config::Config;
+use crate::filter::status_included;
+use crate::io::ingest::SourceKind;
+use crate::keywords::Thesaurus;
 use crate::model::{Abstract, Session};
-use anyhow::{anyhow, Result};
+use anyhow::{Result, anyhow};
 use std::collections::HashMap;
 
+// The workbook-only check from before other sources could be validated.
+#[deprecated(note = "use validate_source with SourceKind::Workbook")]
+#[allow(dead_code)]
 pub fn validate_input(input: &str) -> Result<()> {
-    // parse workbook (this now performs strict header checks and duplicate-id errors)
-    let (abstracts, sessions) = crate::io::excel::parse_workbook(input)?;
-    // ensure every referenced id exists
-    validate_refs(&abstracts, &sessions)?;
+    validate_source(SourceKind::Workbook, input, None, &Config::default())
+}
+
+pub fn validate_source(
+    kind: SourceKind,
+    input: &str,
+    grouping: Option<&str>,
+    config: &Config,
+) -> Result<()> {
+    // parse input (this now performs strict header checks and duplicate-id errors)
+    let (abstracts, sessions) =
+        crate::io::ingest::parse_source(kind, input, grouping, &config.text)?;
+    validate_model(&abstracts, &sessions, config)?;
     Ok(())
 }
 
+// Reference checks on parsed input. The non-fatal findings are logged and
+// returned.
+pub fn validate_model(
+    abstracts: &HashMap<String, Abstract>,
+    sessions: &[Session],
+    config: &Config,
+) -> Result<Vec<String>> {
+    // ensure every referenced id exists
+    validate_refs(abstracts, sessions)?;
+    let warnings = model_warnings(abstracts, sessions, config)?;
+    for warning in &warnings {
+        tracing::warn!("{}", warning);
+    }
+    Ok(warnings)
+}
+
+// Non-fatal findings about parsed input: excluded references and keywords
+// missing from the thesaurus.
+pub fn model_warnings(
+    abstracts: &HashMap<String, Abstract>,
+    sessions: &[Session],
+    config: &Config,
+) -> Result<Vec<String>> {
+    let mut warnings = status_warnings(abstracts, sessions, &config.status.include);
+    let thesaurus = Thesaurus::from_config(config.keywords.thesaurus.as_deref())?;
+    warnings.extend(keyword_warnings(abstracts, &thesaurus));
+    Ok(warnings)
+}
+
+// Keywords the thesaurus does not know, most used first. Without a thesaurus
+// every keyword is its own term and nothing is reported.
+pub fn keyword_warnings(
+    abstracts: &HashMap<String, Abstract>,
+    thesaurus: &Thesaurus,
+) -> Vec<String> {
+    if thesaurus.is_empty() {
+        return Vec::new();
+    }
+    // id order, so a keyword is reported in the spelling of its first abstract
+    let mut sorted: Vec<&Abstract> = abstracts.values().collect();
+    sorted.sort_by(|a, b| a.id.cmp(&b.id));
+    thesaurus
+        .unmapped(sorted.into_iter().map(|a| a.keywords.as_slice()))
+        .into_iter()
+        .map(|(keyword, count)| {
+            format!(
+                "Keyword not in thesaurus: {} ({} abstract{})",
+                keyword,
+                count,
+                if count == 1 { "" } else { "s" }
+            )
+        })
+        .collect()
+}
+
+// Grouping-sheet references to abstracts whose status keeps them out of the
+// booklet. These are reported rather than fatal; the build drops the items.
+pub fn status_warnings(
+    abstracts: &HashMap<String, Abstract>,
+    sessions: &[Session],
+    include: &[String],
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for s in sessions.iter() {
+        for item in s.items.iter() {
+            if let Some(abs) = abstracts.get(&item.id)
+                && !status_included(abs, include)
+            {
+                warnings.push(format!(
+                    "Session {} references abstract {} with status '{}' (not included)",
+                    s.title,
+                    item.id,
+                    abs.status.as_deref().unwrap_or("")
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+// Locale label coverage for the template: keys that fall back to another
+// locale and keys no template file reads.
+pub fn label_warnings(locales_csv: &str, template: &Option<String>) -> Result<Vec<String>> {
+    let (dir, _) = crate::typst::template::template_location(template);
+    let used = crate::typst::locale::template_label_keys(&dir)?;
+    // the HTML site reads the same locale files
+    let site = crate::html::label_keys()?;
+    let locale_dir = crate::typst::locale::locale_dir(&dir);
+    let mut warnings = Vec::new();
+    for locale in crate::util::locales(locales_csv) {
+        warnings.extend(crate::typst::locale::label_report(
+            &locale_dir,
+            locale,
+            &used,
+            &site,
+        )?);
+    }
+    Ok(warnings)
+}
+
 pub fn validate_refs(abstracts: &HashMap<String, Abstract>, sessions: &[Session]) -> Result<()> {
     // ensure every referenced id exists
     for s in sessions.iter() {
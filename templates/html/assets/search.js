@@ -0,0 +1,61 @@
+// Client-side search over search.json (next to the page): every word of the
+// query must occur in the title, authors, session, tags or text.
+(function () {
+  var input = document.getElementById("search");
+  var results = document.getElementById("search-results");
+  var programme = document.getElementById("programme");
+  if (!input || !results) {
+    return;
+  }
+  var entries = null;
+
+  function fold(s) {
+    return s.toLowerCase().normalize("NFD").replace(/[\u0300-\u036f]/g, "");
+  }
+
+  function haystack(e) {
+    return fold([e.title, e.authors.join(" "), e.session, e.tags.join(" "), e.text].join(" "));
+  }
+
+  function show(query) {
+    var words = fold(query).split(/\s+/).filter(Boolean);
+    results.textContent = "";
+    if (!words.length) {
+      results.hidden = true;
+      programme.hidden = false;
+      return;
+    }
+    entries.forEach(function (e) {
+      if (words.every(function (w) { return e.haystack.indexOf(w) >= 0; })) {
+        var li = document.createElement("li");
+        var a = document.createElement("a");
+        a.href = e.href;
+        a.textContent = e.title;
+        var meta = document.createElement("span");
+        meta.className = "meta";
+        meta.textContent = [e.authors.join(", "), e.session].filter(Boolean).join(" · ");
+        li.appendChild(a);
+        li.appendChild(meta);
+        results.appendChild(li);
+      }
+    });
+    results.hidden = false;
+    programme.hidden = true;
+  }
+
+  input.addEventListener("input", function () {
+    if (entries) {
+      show(input.value);
+      return;
+    }
+    fetch("search.json")
+      .then(function (r) { return r.json(); })
+      .then(function (data) {
+        entries = data.map(function (e) {
+          e.haystack = haystack(e);
+          return e;
+        });
+        show(input.value);
+      });
+  });
+})();
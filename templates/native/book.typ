@@ -0,0 +1,8 @@
+{# Typst-native template: all layout is in lib.typ, which reads the book data
+   written by `--data-json` (schema in spec/04). #}
+{% if data_file %}
+#import "/templates/native/lib.typ": book
+#book(json({{ data_file }}))
+{% else %}
+#panic("templates/native reads typst/data_<locale>.json: build with --data-json")
+{% endif %}
@@ -0,0 +1,244 @@
+// Typst-native booklet layout. `book(data)` renders a parsed
+// `typst/data_<locale>.json`; the look matches templates/starter.
+
+#let brand-blue = rgb("#007dbb")
+#let brand-navy = rgb("#002555")
+#let brand-sky = rgb("#009ce8")
+#let brand-white = rgb("#ffffff")
+#let muted = rgb("#646c6f")
+#let page-w = 148mm
+#let page-h = 210mm
+#let page-footer = [
+  #align(center)[
+    #text(fill: muted, size: 8.5pt)[#context counter(page).display()]
+  ]
+]
+#let logo-header = [#align(right)[#image("/templates/starter/images/Logo_dark.jpg", height: 6mm)]]
+
+// Inline spans: {text}, {strong}, {call: {func, body}}, {link: {target: {url}, body}}.
+#let spans(items) = {
+  for span in items {
+    if "text" in span {
+      span.text
+    } else if "strong" in span {
+      strong(spans(span.strong))
+    } else if "link" in span {
+      link(span.link.target.url, spans(span.link.body))
+    } else if "call" in span {
+      let body = spans(span.call.body)
+      let func = span.call.func
+      if func == "emph" {
+        emph(body)
+      } else if func == "underline" {
+        underline(body)
+      } else if func == "sub" {
+        sub(body)
+      } else if func == "super" {
+        super(body)
+      } else {
+        body
+      }
+    }
+  }
+}
+
+// Blocks: {paragraph: [spans]} or {list: [[spans]]}.
+#let blocks(items) = {
+  for (i, block) in items.enumerate() {
+    if i > 0 {
+      parbreak()
+    }
+    if "paragraph" in block {
+      spans(block.paragraph)
+    } else if "list" in block {
+      list(..block.list.map(spans))
+    }
+  }
+}
+
+#let labelled(name, body) = [#strong(name): #body]
+
+#let cover(labels) = {
+  set page(margin: 0mm, footer: none, fill: brand-navy)
+  let centered(dy, size, body) = place(top + left, dx: 0mm, dy: dy)[
+    #box(width: page-w)[
+      #align(center)[
+        #set text(size: size, weight: "bold")
+        #body
+      ]
+    ]
+  ]
+  place(top + left)[#rect(width: page-w, height: page-h, fill: brand-navy, stroke: none)]
+  place(top + left, dx: 4.25mm, dy: 0mm)[
+    #rect(width: 4.25mm, height: page-h, fill: brand-sky, stroke: none)
+  ]
+  centered(31mm, 34pt, labels.cover_title)
+  centered(59mm, 17pt, labels.cover_symposium)
+  centered(72mm, 17pt, labels.cover_date)
+  place(top + left, dx: 15.5mm, dy: 86mm)[
+    #image("/templates/starter/images/cover.jpg", width: 116mm)
+  ]
+  centered(155mm, 13pt, labels.cover_subtitle)
+  place(top + left, dx: 15.5mm, dy: 190mm)[
+    #image("/templates/starter/images/logo.png", height: 15.5mm)
+  ]
+}
+
+#let contents(labels) = {
+  show heading.where(level: 1): it => block(above: 0pt, below: 0pt)[
+    #align(center)[
+      #v(20pt)
+      #text(size: 15pt, weight: "bold", font: "Mari", fill: brand-blue)[#it.body]
+    ]
+  ]
+  set par(justify: false, spacing: 2pt)
+  set text(size: 8.5pt)
+  show outline.entry.where(level: 1): set text(weight: "bold")
+  outline(title: labels.toc_label, depth: 2, indent: 1.1em)
+}
+
+#let abstract-page(abs, labels) = {
+  [#heading(level: 2, abs.title)#label(abs.label)]
+  if abs.untranslated {
+    text(size: 7pt, fill: muted)[#labels.untranslated_label (#abs.locale)]
+    parbreak()
+  }
+  set text(size: 8.5pt)
+  if abs.authors.len() > 0 {
+    labelled(labels.authors_label, abs.authors.join(", "))
+    parbreak()
+  }
+  if abs.affiliations.len() > 0 {
+    if abs.authors.len() > 0 {
+      v(6pt)
+    }
+    labelled(labels.affiliation_label, abs.affiliations.join("; "))
+    parbreak()
+  }
+  if abs.authors.len() > 0 or abs.affiliations.len() > 0 {
+    v(8pt)
+  }
+  for (i, section) in abs.sections.enumerate() {
+    if i > 0 {
+      v(6pt)
+    }
+    if section.label != "" {
+      labelled(section.label, blocks(section.body))
+    } else {
+      blocks(section.body)
+    }
+    parbreak()
+  }
+  if abs.take_home != none {
+    v(8pt)
+    labelled(labels.take_home_label, blocks(abs.take_home))
+    parbreak()
+  }
+  if abs.reference != none {
+    v(8pt)
+    labelled(labels.reference_label, blocks(abs.reference))
+    parbreak()
+  }
+  if abs.tags.len() > 0 {
+    v(8pt)
+    set par(justify: false)
+    text(size: 6.5pt, fill: muted)[#strong(labels.tags_label): #abs.tags.join(" ")]
+  }
+}
+
+#let session-pages(session, labels) = {
+  {
+    set page(footer: none, header: none, fill: brand-blue)
+    show heading.where(level: 1): it => block(above: 0pt, below: 0pt)[
+      #align(center)[
+        #v(70pt)
+        #text(size: 28pt, weight: "bold", font: "Mari", fill: white)[#it.body]
+      ]
+    ]
+    heading(level: 1, upper(session.title))
+  }
+  pagebreak()
+  set page(fill: none, footer: page-footer, header: logo-header)
+  for (i, abs) in session.abstracts.enumerate() {
+    if i > 0 {
+      pagebreak()
+    }
+    abstract-page(abs, labels)
+  }
+}
+
+// Tag, author or affiliation index; `key` names the entry field shown.
+#let index-pages(title, entries, key, letters) = {
+  pagebreak()
+  set page(header: none)
+  show heading.where(level: 1): it => block(above: 10pt, below: 10pt)[
+    #set text(size: 13pt, weight: "bold", font: "Mari")
+    #text(fill: brand-blue)[#it.body]
+  ]
+  heading(level: 1, title)
+  let groups = ()
+  for entry in entries {
+    if groups.len() == 0 or groups.last().letter != entry.letter {
+      groups.push((letter: entry.letter, entries: ()))
+    }
+    groups.last().entries.push(entry)
+  }
+  for group in groups {
+    if letters {
+      block(above: 8pt, below: 4pt, text(weight: "bold", fill: brand-blue, group.letter))
+    }
+    list(..group.entries.map(entry => [
+      #entry.at(key): #entry.abstracts.map(ref => [
+        #link(label(ref.label), ref.title) (#context counter(page).at(label(ref.label)).at(0))
+      ]).join("; ")
+    ]))
+  }
+}
+
+#let book(data) = {
+  let labels = data.labels
+  set page(
+    width: page-w,
+    height: page-h,
+    margin: (top: 20mm, bottom: 18mm, left: 18mm, right: 18mm),
+  )
+  set text(font: "Mari", size: 10.5pt, fill: brand-white)
+  set par(justify: true)
+  set heading(numbering: none)
+  show heading.where(level: 2): it => block(above: 10pt, below: 10pt)[
+    #set text(size: 13pt, weight: "bold", font: "Mari")
+    #text(fill: brand-blue)[#it.body]
+  ]
+  show strong: set text(weight: "semibold", fill: brand-navy)
+
+  cover(labels)
+  pagebreak()
+
+  set page(
+    margin: (top: 20mm, bottom: 18mm, left: 18mm, right: 18mm),
+    fill: none,
+    footer: page-footer,
+  )
+  set text(fill: rgb("#333333"))
+  contents(labels)
+  pagebreak()
+
+  if data.sessions.len() == 0 {
+    [No content for locale "#data.locale".]
+  }
+  for (i, session) in data.sessions.enumerate() {
+    if i > 0 {
+      pagebreak()
+    }
+    session-pages(session, labels)
+  }
+  if data.tag_index.len() > 0 {
+    index-pages(labels.tag_index_label, data.tag_index, "tag", data.index_letters)
+  }
+  if data.author_index.len() > 0 {
+    index-pages(labels.author_index_label, data.author_index, "name", data.index_letters)
+  }
+  if data.affiliation_index.len() > 0 {
+    index-pages(labels.affiliation_index_label, data.affiliation_index, "name", data.index_letters)
+  }
+}
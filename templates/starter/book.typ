@@ -1,3 +1,7 @@
+{# Starter Typst template: book.typ is the entry; partials/ hold the session,
+   abstract and tag index layout. Sessions are rendered into include files
+   (`session.file`), so shared bindings live in theme.typ. See spec/04 for the
+   data passed in. #}
 // Starter Typst template
 // Design system aligned with Region H tokens.
 
@@ -6,16 +10,7 @@
   height: 210mm,
   margin: (top: 20mm, bottom: 18mm, left: 18mm, right: 18mm),
 )
-#let brand-blue = rgb("#007dbb")
-#let brand-navy = rgb("#002555")
-#let brand-sky = rgb("#009ce8")
-#let brand-muted = rgb("#e5f2f8")
-#let brand-white = rgb("#ffffff")
-#let page-footer = [
-  #align(center)[
-    #text(fill: rgb("#646c6f"), size: 8.5pt)[#context counter(page).display()]
-  ]
-]
+#import "/templates/starter/theme.typ": *
 #set text(font: "Mari", size: 10.5pt, fill: brand-white)
 #set par(justify: true)
 #set heading(numbering: none)
@@ -39,6 +34,7 @@
 #set page(margin: 0mm, footer: none, fill: brand-navy)
 #let page-w = 148mm
 #let page-h = 210mm
+#let index-letter(body) = block(above: 8pt, below: 4pt, text(weight: "bold", fill: brand-blue, body))
 #place(top + left)[
   #rect(width: page-w, height: page-h, fill: brand-navy, stroke: none)
 ]
@@ -49,7 +45,7 @@
   #box(width: page-w)[
     #align(center)[
       #set text(size: 34pt, weight: "bold")
-      {{COVER_TITLE}}
+      {{ labels.cover_title }}
     ]
   ]
 ]
@@ -57,7 +53,7 @@
   #box(width: page-w)[
     #align(center)[
       #set text(size: 17pt, weight: "bold")
-      {{COVER_SYMPOSIUM}}
+      {{ labels.cover_symposium }}
     ]
   ]
 ]
@@ -65,7 +61,7 @@
   #box(width: page-w)[
     #align(center)[
       #set text(size: 17pt, weight: "bold")
-      {{COVER_DATE}}
+      {{ labels.cover_date }}
     ]
   ]
 ]
@@ -76,7 +72,7 @@
   #box(width: page-w)[
     #align(center)[
       #set text(size: 13pt, weight: "bold")
-      {{COVER_SUBTITLE}}
+      {{ labels.cover_subtitle }}
     ]
   ]
 ]
@@ -102,9 +98,34 @@
 #set par(justify: false, spacing: 2pt)
 #set text(size: 8.5pt)
 #show outline.entry.where(level: 1): set text(weight: "bold")
-#outline(title: [{{TOC_LABEL}}], depth: 2, indent: 1.1em)
+#outline(title: [{{ labels.toc_label }}], depth: 2, indent: 1.1em)
 #pagebreak()
 #set text(size: 10.5pt)
 #set par(justify: true)
 
-{{CONTENT}}
+{% for session in sessions %}
+{% if not loop.first %}
+#pagebreak()
+{% endif %}
+{% if session.file %}
+#include {{ session.file }}
+{% else %}
+{% include "partials/session.typ" %}
+{% endif %}
+{% else %}
+No content for locale "{{ locale }}".
+
+{% endfor %}
+{% if tag_index %}
+{% include "partials/tag_index.typ" %}
+{% endif %}
+{% if author_index %}
+{% with title = labels.author_index_label, entries = author_index %}
+{% include "partials/name_index.typ" %}
+{% endwith %}
+{% endif %}
+{% if affiliation_index %}
+{% with title = labels.affiliation_index_label, entries = affiliation_index %}
+{% include "partials/name_index.typ" %}
+{% endwith %}
+{% endif %}
@@ -0,0 +1,50 @@
+{# One abstract page. Section bodies, take-home and reference are generated
+   Typst markup; every other value is escaped text. #}
+== {{ abstract.title }} <{{ abstract.label }}>
+{% if abstract.untranslated %}
+#text(size: 7pt, fill: rgb("#646c6f"))[{{ labels.untranslated_label }} ({{ abstract.locale }})]
+{% endif %}
+
+#set text(size: 8.5pt)
+{% if abstract.authors %}
+#strong[{{ labels.authors_label }}]: {{ abstract.authors|join(", ") }}
+
+{% endif %}
+{% if abstract.affiliations %}
+{% if abstract.authors %}
+#v(6pt)
+{% endif %}
+#strong[{{ labels.affiliation_label }}]: {{ abstract.affiliations|join("; ") }}
+
+{% endif %}
+{% if abstract.authors or abstract.affiliations %}
+#v(8pt)
+{% endif %}
+#set text(size: 8.5pt)
+{% for section in abstract.sections %}
+{% if section.label %}#strong[{{ section.label }}]: {% endif %}{{ section.body }}
+
+{% if not loop.last %}
+#v(6pt)
+{% endif %}
+{% endfor %}
+#set text(size: 10.5pt)
+{% if abstract.take_home %}
+#v(8pt)
+#set text(size: 8.5pt)
+#strong[{{ labels.take_home_label }}]: {{ abstract.take_home }}
+
+{% endif %}
+{% if abstract.reference %}
+#v(8pt)
+#set text(size: 8.5pt)
+#strong[{{ labels.reference_label }}]: {{ abstract.reference }}
+
+{% endif %}
+{% if abstract.tags %}
+#v(8pt)
+#set par(justify: false)
+#text(size: 6.5pt, fill: rgb("#646c6f"))[#strong[{{ labels.tags_label }}]: {{ abstract.tags|join(" ") }}]
+
+#set par(justify: true)
+{% endif %}
@@ -0,0 +1,12 @@
+{# Author or affiliation index, included with `title` and `entries` set. #}
+#pagebreak()
+#set page(header: none)
+= {{ title }}
+
+{% for entry in entries %}
+{% if index_letters and loop.changed(entry.letter) %}
+#index-letter[{{ entry.letter }}]
+{% endif %}
+- {{ entry.name }}: {% for ref in entry.abstracts %}{% if not loop.first %}; {% endif %}#link(<{{ ref.label }}>)[{{ ref.title }}] (#context counter(page).at(<{{ ref.label }}>).at(0)){% endfor +%}
+{% endfor %}
+
@@ -0,0 +1,21 @@
+{# One session: a full-page title followed by its abstracts. Rendered into its
+   own include file, so it imports what it uses from the book. #}
+#import "/templates/starter/theme.typ": brand-blue, page-footer
+#set page(footer: none, header: none)
+#set page(fill: brand-blue)
+#show heading.where(level: 1): it => block(above: 0pt, below: 0pt)[
+  #align(center)[
+    #v(70pt)
+    #text(size: 28pt, weight: "bold", font: "Mari", fill: white)[#it.body]
+  ]
+]
+= {{ session.title|upper }}
+
+#pagebreak()
+#set page(fill: none, footer: page-footer, header: [#align(right)[#image("/templates/starter/images/Logo_dark.jpg", height: 6mm)]])
+{% for abstract in session.abstracts %}
+{% include "partials/abstract.typ" %}
+{% if not loop.last %}
+#pagebreak()
+{% endif %}
+{% endfor %}
@@ -0,0 +1,17 @@
+{# Tag index: each tag links to its abstracts with page numbers; optional
+   group letter headings. #}
+#pagebreak()
+#set page(header: none)
+#show heading.where(level: 1): it => block(above: 10pt, below: 10pt)[
+  #set text(size: 13pt, weight: "bold", font: "Mari")
+  #text(fill: brand-blue)[#it.body]
+]
+= {{ labels.tag_index_label }}
+
+{% for entry in tag_index %}
+{% if index_letters and loop.changed(entry.letter) %}
+#index-letter[{{ entry.letter }}]
+{% endif %}
+- {{ entry.tag }}: {% for ref in entry.abstracts %}{% if not loop.first %}; {% endif %}#link(<{{ ref.label }}>)[{{ ref.title }}] (#context counter(page).at(<{{ ref.label }}>).at(0)){% endfor +%}
+{% endfor %}
+
@@ -0,0 +1,12 @@
+// Colours and page furniture shared by book.typ and the session include files
+// (`typst/sessions_<locale>/`), which do not see the book's own bindings.
+#let brand-blue = rgb("#007dbb")
+#let brand-navy = rgb("#002555")
+#let brand-sky = rgb("#009ce8")
+#let brand-muted = rgb("#e5f2f8")
+#let brand-white = rgb("#ffffff")
+#let page-footer = [
+  #align(center)[
+    #text(fill: rgb("#646c6f"), size: 8.5pt)[#context counter(page).display()]
+  ]
+]
@@ -0,0 +1,146 @@
+mod common;
+
+use std::fs;
+
+fn strings(value: &serde_json::Value, field: &str) -> Vec<String> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e[field].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn indexes_and_sessions_collate_per_booklet_locale() {
+    let dir = "target/test-book-indexes";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/indexes.xlsx", dir);
+    common::write_workbook(
+        &xlsx,
+        &[
+            &[
+                "id",
+                "title",
+                "authors",
+                "abstract",
+                "keywords",
+                "take home",
+                "reference",
+                "locale",
+            ],
+            &[
+                "a1",
+                "Zoner",
+                "Åse Holm, Øst Hospital",
+                "Tekst",
+                "Ærlighed, zone",
+                "",
+                "",
+                "da",
+            ],
+            &[
+                "a2",
+                "Åben",
+                "Bo Ek, Aarhus Universitet",
+                "Tekst",
+                "Øvelse, Éclair",
+                "",
+                "",
+                "da",
+            ],
+            &[
+                "a3",
+                "Øre",
+                "Carl Ørum, Vest Skole",
+                "Tekst",
+                "åbenhed, 3D print",
+                "",
+                "",
+                "da",
+            ],
+        ],
+        &["Øst", "a1", "a2", "Vest", "a3"],
+    );
+    let config = format!("{}/config.toml", dir);
+    fs::write(
+        &config,
+        "[ordering]\nsession_order = \"title\"\n\n[content]\nuntranslated = \"include\"\n\n\
+         [index]\nauthors = true\naffiliations = true\nletters = true\n",
+    )
+    .unwrap();
+
+    let out = format!("{}/out", dir);
+    common::build(
+        &xlsx,
+        &out,
+        &[
+            "--locales",
+            "da,en",
+            "--config",
+            &config,
+            "--data-json",
+            "--no-pdf",
+        ],
+    );
+    let read = |l: &str| -> serde_json::Value {
+        serde_json::from_str(&fs::read_to_string(format!("{}/typst/data_{}.json", out, l)).unwrap())
+            .unwrap()
+    };
+
+    let da = read("da");
+    assert_eq!(strings(&da["sessions"], "title"), vec!["Vest", "Øst"]);
+    assert_eq!(
+        strings(&da["tag_index"], "tag"),
+        vec![
+            "#3d_print",
+            "#éclair",
+            "#zone",
+            "#ærlighed",
+            "#øvelse",
+            "#åbenhed"
+        ]
+    );
+    assert_eq!(
+        strings(&da["tag_index"], "letter"),
+        vec!["0-9", "E", "Z", "Æ", "Ø", "Å"]
+    );
+    assert_eq!(
+        strings(&da["author_index"], "name"),
+        vec!["Ek, Bo", "Holm, Åse", "Ørum, Carl"]
+    );
+    assert_eq!(
+        strings(&da["affiliation_index"], "name"),
+        vec!["Aarhus Universitet", "Vest Skole", "Øst Hospital"]
+    );
+
+    // English folds the same letters onto a and o
+    let en = read("en");
+    assert_eq!(strings(&en["sessions"], "title"), vec!["Øst", "Vest"]);
+    assert_eq!(
+        strings(&en["tag_index"], "tag"),
+        vec![
+            "#3d_print",
+            "#åbenhed",
+            "#ærlighed",
+            "#éclair",
+            "#øvelse",
+            "#zone"
+        ]
+    );
+    assert_eq!(strings(&en["author_index"], "letter"), vec!["E", "H", "O"]);
+
+    let typ = fs::read_to_string(format!("{}/typst/book_da.typ", out)).unwrap();
+    assert!(typ.contains("#index-letter[Æ]\n- \\#ærlighed: "), "{}", typ);
+    assert!(
+        typ.contains("= Forfatterindeks\n\n#index-letter[E]\n- Ek, Bo: "),
+        "{}",
+        typ
+    );
+    assert!(typ.contains("= Institutionsindeks"), "{}", typ);
+    let errors = typst_syntax::parse(&typ).errors_and_warnings().0;
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let _ = fs::remove_dir_all(dir);
+}
@@ -0,0 +1,136 @@
+use std::fs;
+
+mod common;
+use common::output::book_text;
+
+// One English and one Danish abstract; the tests change the Danish text.
+const HEADER: &[&str] = &["id", "title", "authors", "locale", "abstract"];
+const ENGLISH: &[&str] = &["e1", "English talk", "Ann Lee", "en", "English text"];
+const SESSIONS: &[&str] = &["Session", "e1", "d1"];
+
+// Both locales; returns stdout.
+fn build(xlsx: &str, out: &str, extra: &[&str]) -> String {
+    let mut args = vec!["--locales", "en,da"];
+    args.extend(extra);
+    common::build(xlsx, out, &args)
+}
+
+#[test]
+fn unchanged_rebuild_is_skipped() {
+    let dir = "target/test-build-cache";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/abstracts.xlsx", dir);
+    let out = format!("{}/out", dir);
+    common::write_workbook(
+        &xlsx,
+        &[
+            HEADER,
+            ENGLISH,
+            &["d1", "Dansk oplæg", "Bo Ek", "da", "Dansk tekst"],
+        ],
+        SESSIONS,
+    );
+
+    let first = build(&xlsx, &out, &["--no-pdf"]);
+    assert!(!first.contains("up to date"), "{}", first);
+    let second = build(&xlsx, &out, &["--no-pdf"]);
+    assert!(second.contains("up to date"), "{}", second);
+    // decided before the workbook is read
+    assert!(!second.contains("Parsing abstracts"), "{}", second);
+    assert!(book_text(&out, "da").contains("Dansk tekst"));
+
+    // a changed workbook, option or --no-cache builds again
+    common::write_workbook(
+        &xlsx,
+        &[
+            HEADER,
+            ENGLISH,
+            &["d1", "Dansk oplæg", "Bo Ek", "da", "Ny dansk tekst"],
+        ],
+        SESSIONS,
+    );
+    let changed = build(&xlsx, &out, &["--no-pdf"]);
+    assert!(!changed.contains("up to date"), "{}", changed);
+    assert!(book_text(&out, "da").contains("Ny dansk tekst"));
+    let other = build(&xlsx, &out, &["--no-pdf", "--no-linkify"]);
+    assert!(!other.contains("up to date"), "{}", other);
+    let forced = build(&xlsx, &out, &["--no-pdf", "--no-linkify", "--no-cache"]);
+    assert!(!forced.contains("up to date"), "{}", forced);
+}
+
+// Stands in for typst: logs the book it compiles and writes a dummy PDF.
+#[cfg(unix)]
+fn fake_typst(dir: &str) -> String {
+    use std::os::unix::fs::PermissionsExt;
+    let path = format!("{}/typst", dir);
+    fs::write(
+        &path,
+        format!(
+            "#!/bin/sh\n\
+             [ \"$1\" = \"--version\" ] && {{ echo 'typst 0.0.0'; exit 0; }}\n\
+             for a in \"$@\"; do case \"$a\" in *.typ) file=\"$a\";; *.pdf) out=\"$a\";; esac; done\n\
+             echo \"$(basename \"$file\")\" >> {dir}/compiled.log\n\
+             echo '%PDF-1.7' > \"$out\"\n"
+        ),
+    )
+    .unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+#[cfg(unix)]
+#[test]
+fn only_changed_locales_are_recompiled() {
+    let dir = "target/test-build-cache-pdf";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/abstracts.xlsx", dir);
+    let out = format!("{}/out", dir);
+    let typst = fake_typst(dir);
+    let log = format!("{}/compiled.log", dir);
+    let compiled = || -> Vec<String> {
+        let mut books: Vec<String> = fs::read_to_string(&log)
+            .unwrap_or_default()
+            .lines()
+            .map(String::from)
+            .collect();
+        books.sort();
+        books
+    };
+
+    common::write_workbook(
+        &xlsx,
+        &[
+            HEADER,
+            ENGLISH,
+            &["d1", "Dansk oplæg", "Bo Ek", "da", "Dansk tekst"],
+        ],
+        SESSIONS,
+    );
+    build(&xlsx, &out, &["--typst-bin", &typst, "--jobs", "2"]);
+    assert_eq!(compiled(), vec!["book_da.typ", "book_en.typ"]);
+
+    // only the Danish booklet prints d1
+    fs::remove_file(&log).unwrap();
+    common::write_workbook(
+        &xlsx,
+        &[
+            HEADER,
+            ENGLISH,
+            &["d1", "Dansk oplæg", "Bo Ek", "da", "Ny dansk tekst"],
+        ],
+        SESSIONS,
+    );
+    let stdout = build(&xlsx, &out, &["--typst-bin", &typst, "--jobs", "2"]);
+    assert_eq!(compiled(), vec!["book_da.typ"]);
+    assert!(
+        stdout.contains("Unchanged typst sources for en"),
+        "{}",
+        stdout
+    );
+    for locale in ["en", "da"] {
+        let pdf = format!("{}/symposium-2026_{}.pdf", out, locale);
+        assert!(fs::read(pdf).unwrap().starts_with(b"%PDF"));
+    }
+}
@@ -1,3 +1,66 @@
+#[allow(dead_code)]
 pub mod fixtures {
     include!("../fixtures/generate_fixture.rs");
 }
+
+#[allow(dead_code)]
+pub mod output {
+    use std::fs;
+    use std::path::Path;
+
+    // `typst/book_<locale>.typ` followed by its session include files.
+    pub fn book_files(out: &str, locale: &str) -> Vec<String> {
+        let typst = Path::new(out).join("typst");
+        let mut files = vec![typst.join(format!("book_{}.typ", locale))];
+        if let Ok(entries) = fs::read_dir(typst.join(format!("sessions_{}", locale))) {
+            let mut sessions: Vec<_> = entries.map(|e| e.unwrap().path()).collect();
+            sessions.sort();
+            files.extend(sessions);
+        }
+        files
+            .iter()
+            .map(|p| fs::read_to_string(p).unwrap())
+            .collect()
+    }
+
+    pub fn book_text(out: &str, locale: &str) -> String {
+        book_files(out, locale).join("\n")
+    }
+}
+
+// An `abstracts` sheet from `rows` (header first) and a `sessions` sheet with
+// `sessions` down column A: session titles, each followed by its abstract ids.
+#[allow(dead_code)]
+pub fn write_workbook(path: &str, rows: &[&[&str]], sessions: &[&str]) {
+    let mut book = umya_spreadsheet::new_file();
+    let _ = book.new_sheet("abstracts");
+    let sheet = book.get_sheet_by_name_mut("abstracts").unwrap();
+    for (r, row) in rows.iter().enumerate() {
+        for (c, value) in row.iter().enumerate() {
+            sheet
+                .get_cell_mut((c as u32 + 1, r as u32 + 1))
+                .set_value(*value);
+        }
+    }
+    let _ = book.new_sheet("sessions");
+    let s = book.get_sheet_by_name_mut("sessions").unwrap();
+    for (i, value) in sessions.iter().enumerate() {
+        s.get_cell_mut((1, i as u32 + 1)).set_value(*value);
+    }
+    umya_spreadsheet::writer::xlsx::write(&book, std::path::Path::new(path)).unwrap();
+}
+
+// `build --input <xlsx> --output <out>` plus `extra`; the build must succeed.
+// Returns its stdout.
+#[allow(dead_code)]
+pub fn build(xlsx: &str, out: &str, extra: &[&str]) -> String {
+    let output = assert_cmd::cargo::cargo_bin_cmd!("symposium-booklet")
+        .args(["build", "--input", xlsx, "--output", out])
+        .args(extra)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    String::from_utf8_lossy(&output).into_owned()
+}
@@ -0,0 +1,148 @@
+mod common;
+
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+
+fn build(dir: &str, docx: &[&str]) -> String {
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/abstracts.xlsx", dir);
+    common::write_workbook(
+        &xlsx,
+        &[
+            &["id", "title", "authors", "locale", "abstract", "keywords"],
+            &[
+                "a-1",
+                "Safer <wards>",
+                "Ann Lee",
+                "en",
+                "Background: Falls & fractures. Results: Fewer falls.",
+                "patient safety",
+            ],
+            &["a2", "Second talk", "Cy Dahl", "en", "Plain text", ""],
+            &["a3", "Posters", "Bo Ek", "en", "On the wall", ""],
+        ],
+        &["Morning", "a-1", "a2", "Afternoon", "a3"],
+    );
+    let out = format!("{}/out", dir);
+    let mut extra = vec!["--locales", "en", "--no-pdf"];
+    extra.extend(docx);
+    common::build(&xlsx, &out, &extra);
+    out
+}
+
+// `word/document.xml`, after checking that every part is well-formed XML.
+fn document(path: &str) -> String {
+    let mut archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+    let mut document = String::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).unwrap();
+        let mut text = String::new();
+        file.read_to_string(&mut text).unwrap();
+        let mut reader = Reader::from_str(&text);
+        loop {
+            match reader.read_event() {
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(e) => panic!("{} is not well-formed: {}", file.name(), e),
+            }
+        }
+        if file.name() == "word/document.xml" {
+            document = text;
+        }
+    }
+    document
+}
+
+#[test]
+fn docx_uses_heading_styles_bold_labels_and_id_bookmarks() {
+    let out = build("target/test-docx", &["--format", "docx"]);
+    let xml = document(&format!("{}/docx/symposium-2026_en.docx", out));
+
+    // sessions and abstracts are Word headings, in programme order
+    let morning = xml
+        .find(
+            "<w:pStyle w:val=\"Heading1\"/></w:pPr><w:r><w:t xml:space=\"preserve\">Morning</w:t>",
+        )
+        .unwrap();
+    let first = xml.find("Safer &lt;wards&gt;").unwrap();
+    let afternoon = xml.find(">Afternoon<").unwrap();
+    assert!(morning < first && first < afternoon, "{}", xml);
+    assert!(
+        xml.contains(
+            "<w:pStyle w:val=\"Heading2\"/></w:pPr><w:bookmarkStart w:id=\"1\" w:name=\"abs_a_1\"/>"
+        ),
+        "{}",
+        xml
+    );
+    assert!(xml.contains("<w:bookmarkEnd w:id=\"1\"/>"), "{}", xml);
+    // the source row for tracing comments back
+    assert!(xml.contains("a-1 · abstracts row 2"), "{}", xml);
+
+    // sections from the workbook text, labels in bold
+    assert!(
+        xml.contains(
+            "<w:r><w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">Background: </w:t></w:r>"
+        ),
+        "{}",
+        xml
+    );
+    assert!(
+        xml.contains("<w:r><w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">Results: </w:t></w:r>"),
+        "{}",
+        xml
+    );
+    assert!(xml.contains("Falls &amp; fractures."), "{}", xml);
+    assert!(
+        xml.contains("<w:t xml:space=\"preserve\">Authors: </w:t>"),
+        "{}",
+        xml
+    );
+}
+
+#[test]
+fn docx_session_writes_one_document_per_session() {
+    let out = build(
+        "target/test-docx-session",
+        &["--format", "docx", "--docx-split", "session"],
+    );
+    let morning = document(&format!("{}/docx/en/01-morning.docx", out));
+    assert!(morning.contains("Second talk"), "{}", morning);
+    assert!(!morning.contains("Posters"), "{}", morning);
+    let afternoon = document(&format!("{}/docx/en/02-afternoon.docx", out));
+    assert!(afternoon.contains("Posters"), "{}", afternoon);
+    // bookmark ids restart in each document
+    assert!(
+        afternoon.contains("<w:bookmarkStart w:id=\"1\" w:name=\"abs_a3\"/>"),
+        "{}",
+        afternoon
+    );
+    assert!(!Path::new(&format!("{}/docx/symposium-2026_en.docx", out)).exists());
+}
+
+#[test]
+fn docx_is_a_format_of_its_own() {
+    let out = build("target/test-docx-format", &["--format", "docx"]);
+    document(&format!("{}/docx/symposium-2026_en.docx", out));
+    assert!(!Path::new(&format!("{}/typst", out)).exists());
+}
+
+#[test]
+fn docx_follows_the_booklet_order_and_tags() {
+    let config = "target/test-docx-booklet.toml";
+    fs::write(config, "[ordering]\nsession_order = \"title\"\n").unwrap();
+    let out = build(
+        "target/test-docx-booklet",
+        &["--format", "docx", "--config", config],
+    );
+    let xml = document(&format!("{}/docx/symposium-2026_en.docx", out));
+    assert!(
+        xml.find(">Afternoon<").unwrap() < xml.find(">Morning<").unwrap(),
+        "{}",
+        xml
+    );
+    assert!(xml.contains("#patient_safety"), "{}", xml);
+}
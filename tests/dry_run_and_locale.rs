@@ -49,6 +49,7 @@ fn write_markdown_plan_includes_locale_and_paths() {
             reference: None,
             literature: None,
             locale: "en".to_string(),
+            ..Default::default()
         },
     );
     let session = Session {
@@ -61,15 +62,15 @@ fn write_markdown_plan_includes_locale_and_paths() {
         }],
     };
     let mut plan = symposium_booklet::io::plan::Plan::default();
-    write_markdown_plan(&abstracts, &vec![session], "outdir", &mut plan).unwrap();
+    write_markdown_plan(&abstracts, &[session], "outdir", &mut plan).unwrap();
 
     // find a WriteFile action with summary containing locale
     let mut found = false;
     for a in plan.actions.iter() {
-        if let PlanAction::WriteFile { path: _p, summary } = a {
-            if summary.contains("locale:en") {
-                found = true;
-            }
+        if let PlanAction::WriteFile { path: _p, summary } = a
+            && summary.contains("locale:en")
+        {
+            found = true;
         }
     }
     assert!(
@@ -0,0 +1,220 @@
+mod common;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Read;
+
+// Entries of the archive in order, with the compression of each.
+fn read_epub(path: &str) -> Vec<(String, zip::CompressionMethod, Vec<u8>)> {
+    let mut archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+    (0..archive.len())
+        .map(|i| {
+            let mut file = archive.by_index(i).unwrap();
+            let mut data = Vec::new();
+            file.read_to_end(&mut data).unwrap();
+            (file.name().to_string(), file.compression(), data)
+        })
+        .collect()
+}
+
+fn assert_well_formed(name: &str, text: &str) {
+    let mut reader = Reader::from_str(text);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(e) => panic!("{} is not well-formed: {}\n{}", name, e, text),
+        }
+    }
+}
+
+fn build(dir: &str, config: Option<&str>) -> String {
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/abstracts.xlsx", dir);
+    common::write_workbook(
+        &xlsx,
+        &[
+            &["id", "title", "authors", "locale", "abstract", "keywords"],
+            &[
+                "a1",
+                "Safer <wards> & beds",
+                "Ann Lee",
+                "en",
+                "See https://example.org/x",
+                "patient safety, 2026 data",
+            ],
+            &[
+                "a2",
+                "Second talk",
+                "Cy Dahl",
+                "en",
+                "Plain text",
+                "patient safety",
+            ],
+            &["a3", "Posters", "Bo Ek", "en", "On the wall", ""],
+        ],
+        &["Morning", "a1", "a2", "Afternoon", "a3"],
+    );
+    let out = format!("{}/out", dir);
+    // like `common::build`, with a fixed modification time
+    let mut cmd = cargo_bin_cmd!("symposium-booklet");
+    cmd.env("SOURCE_DATE_EPOCH", "1773360000").args([
+        "build",
+        "--input",
+        &xlsx,
+        "--output",
+        &out,
+        "--locales",
+        "en",
+        "--no-pdf",
+        "--format",
+        "epub",
+    ]);
+    if let Some(config) = config {
+        let path = format!("{}/config.toml", dir);
+        fs::write(&path, config).unwrap();
+        cmd.args(["--config", &path]);
+    }
+    cmd.assert().success();
+    format!("{}/symposium-2026_en.epub", out)
+}
+
+#[test]
+fn epub_is_a_valid_package_with_outline_and_index_links() {
+    let epub = build("target/test-epub", None);
+    let entries = read_epub(&epub);
+
+    // OCF: `mimetype` first and uncompressed
+    let (name, method, data) = &entries[0];
+    assert_eq!(name, "mimetype");
+    assert_eq!(*method, zip::CompressionMethod::Stored);
+    assert_eq!(data, b"application/epub+zip");
+    let files: BTreeMap<&str, String> = entries
+        .iter()
+        .map(|(name, _, data)| (name.as_str(), String::from_utf8_lossy(data).into_owned()))
+        .collect();
+    assert!(files["META-INF/container.xml"].contains("full-path=\"OEBPS/content.opf\""));
+    for (name, text) in &files {
+        if name.ends_with(".xhtml") || name.ends_with(".opf") || name.ends_with(".xml") {
+            assert_well_formed(name, text);
+        }
+    }
+
+    let opf = &files["OEBPS/content.opf"];
+    assert!(
+        opf.contains("<meta property=\"dcterms:modified\">2026-03-13T00:00:00Z</meta>"),
+        "{}",
+        opf
+    );
+    assert!(opf.contains("<dc:language>en</dc:language>"), "{}", opf);
+    assert!(opf.contains("properties=\"nav\""), "{}", opf);
+    assert!(opf.contains("properties=\"cover-image\""), "{}", opf);
+    assert!(files.contains_key("OEBPS/images/cover.jpg"));
+    // every manifest item is in the package
+    let href = Regex::new(r#"<item id="[^"]+" href="([^"]+)""#).unwrap();
+    for cap in href.captures_iter(opf) {
+        assert!(
+            files.contains_key(format!("OEBPS/{}", &cap[1]).as_str()),
+            "{} missing",
+            &cap[1]
+        );
+    }
+    let spine: Vec<&str> = Regex::new(r#"<itemref idref="([^"]+)"/>"#)
+        .unwrap()
+        .captures_iter(opf)
+        .map(|c| c.get(1).unwrap().as_str())
+        .collect();
+    assert_eq!(
+        spine,
+        ["title-page", "nav", "session-1", "session-2", "tag-index"]
+    );
+
+    // the outline: sessions, then their abstracts, then the index
+    let nav = &files["OEBPS/nav.xhtml"];
+    let morning = nav.find(">Morning<").unwrap();
+    let first = nav.find("Safer &lt;wards&gt; &amp; beds").unwrap();
+    let afternoon = nav.find(">Afternoon<").unwrap();
+    assert!(morning < first && first < afternoon, "{}", nav);
+    assert!(
+        nav.contains("<a href=\"session-01.xhtml#abs-a1\">"),
+        "{}",
+        nav
+    );
+    assert!(
+        nav.contains("<a href=\"tags.xhtml\">Tag index</a>"),
+        "{}",
+        nav
+    );
+
+    // index entries link to the abstracts and the abstracts back to the index
+    let session = &files["OEBPS/session-01.xhtml"];
+    assert!(session.contains("id=\"abs-a1\""), "{}", session);
+    assert!(
+        session.contains("<a href=\"tags.xhtml#tag-patient-safety\">#patient_safety</a>"),
+        "{}",
+        session
+    );
+    let tags = &files["OEBPS/tags.xhtml"];
+    assert!(tags.contains("id=\"tag-patient-safety\""), "{}", tags);
+    // ids never start with a digit
+    assert!(tags.contains("id=\"tag-2026-data\""), "{}", tags);
+    let link = Regex::new(r#"href="([a-z0-9-]+\.xhtml)#([^"]+)""#).unwrap();
+    for page in [
+        "OEBPS/nav.xhtml",
+        "OEBPS/tags.xhtml",
+        "OEBPS/session-01.xhtml",
+    ] {
+        for cap in link.captures_iter(&files[page]) {
+            let target = &files[format!("OEBPS/{}", &cap[1]).as_str()];
+            assert!(
+                target.contains(&format!("id=\"{}\"", &cap[2])),
+                "{} links to missing {}#{}",
+                page,
+                &cap[1],
+                &cap[2]
+            );
+        }
+    }
+
+    // fonts only on request
+    assert!(!files.keys().any(|name| name.contains("fonts/")));
+    assert!(!files["OEBPS/style.css"].contains("@font-face"));
+}
+
+#[test]
+fn epub_embeds_fonts_and_author_index_when_configured() {
+    let epub = build(
+        "target/test-epub-fonts",
+        Some("[epub]\nembed_fonts = true\n\n[index]\nauthors = true\n"),
+    );
+    let entries = read_epub(&epub);
+    let names: Vec<&str> = entries.iter().map(|(n, _, _)| n.as_str()).collect();
+    assert!(names.contains(&"OEBPS/fonts/Mari-Book.otf"), "{:?}", names);
+    assert!(names.contains(&"OEBPS/authors.xhtml"), "{:?}", names);
+    let opf = entries
+        .iter()
+        .find(|(n, _, _)| n == "OEBPS/content.opf")
+        .map(|(_, _, d)| String::from_utf8_lossy(d).into_owned())
+        .unwrap();
+    assert!(
+        opf.contains("href=\"fonts/Mari-Book.otf\" media-type=\"font/otf\""),
+        "{}",
+        opf
+    );
+    let authors = entries
+        .iter()
+        .find(|(n, _, _)| n == "OEBPS/authors.xhtml")
+        .map(|(_, _, d)| String::from_utf8_lossy(d).into_owned())
+        .unwrap();
+    assert!(authors.contains("id=\"author-lee-ann\""), "{}", authors);
+    assert!(
+        authors.contains("<a href=\"session-01.xhtml#abs-a1\">"),
+        "{}",
+        authors
+    );
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use std::collections::HashMap;
+use std::fs;
+use symposium_booklet::config::{EventConfig, SessionSlot};
+use symposium_booklet::io::frab::render_frab;
+use symposium_booklet::model::{Abstract, ItemRef, Session};
+
+fn abstract_with(id: &str, title: &str, authors: &[&str]) -> Abstract {
+    Abstract {
+        id: id.to_string(),
+        title: title.to_string(),
+        authors: authors.iter().map(|a| a.to_string()).collect(),
+        affiliation: None,
+        center: None,
+        contact_email: None,
+        abstract_text: "Results & <discussion>".to_string(),
+        abstract_sections: Vec::new(),
+        keywords: vec![],
+        take_home: None,
+        reference: None,
+        literature: None,
+        locale: "da".to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn frab_schedule_places_sessions_in_rooms_and_escapes_text() {
+    let mut abstracts = HashMap::new();
+    abstracts.insert(
+        "a1".to_string(),
+        abstract_with("a1", "First", &["Ann", "Bo"]),
+    );
+    abstracts.insert("a2".to_string(), abstract_with("a2", "Second", &["Ann"]));
+    abstracts.insert("a3".to_string(), abstract_with("a3", "Third", &["Cy"]));
+    let sessions = vec![
+        Session {
+            id: "Plenum".to_string(),
+            title: "Plenum".to_string(),
+            order: 1,
+            items: vec![
+                ItemRef {
+                    id: "a2".to_string(),
+                    order: 2,
+                },
+                ItemRef {
+                    id: "a1".to_string(),
+                    order: 1,
+                },
+            ],
+        },
+        Session {
+            id: "Posters".to_string(),
+            title: "Posters".to_string(),
+            order: 2,
+            items: vec![ItemRef {
+                id: "a3".to_string(),
+                order: 1,
+            }],
+        },
+    ];
+    let mut event = EventConfig::default();
+    event.sessions.insert(
+        "posters".to_string(),
+        SessionSlot {
+            day: Some(2),
+            room: Some("Foyer".to_string()),
+            start: Some("13:30".to_string()),
+            slot_minutes: None,
+        },
+    );
+
+    let xml = render_frab(&abstracts, &sessions, &event).unwrap();
+    assert!(xml.contains("<days>2</days>"));
+    assert!(xml.contains("<end>2026-03-14</end>"));
+    assert!(xml.contains("<room name=\"Foyer\""));
+    assert!(xml.contains("<abstract>Results &amp; &lt;discussion&gt;</abstract>"));
+    assert!(xml.contains("<track>Posters</track>"));
+    assert!(xml.contains("<start>13:30</start>"));
+    // items follow session order, and repeat authors keep their person id
+    let first = xml.find("<title>First</title>").unwrap();
+    let second = xml.find("<title>Second</title>").unwrap();
+    assert!(first < second);
+    assert_eq!(xml.matches("<person id=\"1\">Ann</person>").count(), 2);
+}
+
+#[test]
+fn frab_rejects_impossible_start_dates() {
+    let sessions = vec![Session {
+        id: "Plenum".to_string(),
+        title: "Plenum".to_string(),
+        order: 1,
+        items: Vec::new(),
+    }];
+    for start in [
+        "2026-13-01",
+        "2026-00-10",
+        "2026-02-29",
+        "2026-04-31",
+        "2026-03-00",
+    ] {
+        let event = EventConfig {
+            start: start.to_string(),
+            ..EventConfig::default()
+        };
+        let err = render_frab(&HashMap::new(), &sessions, &event).unwrap_err();
+        assert!(
+            err.to_string().contains("out of range"),
+            "{}: {}",
+            start,
+            err
+        );
+    }
+    // leap day in a leap year is fine
+    let event = EventConfig {
+        start: "2028-02-29".to_string(),
+        ..EventConfig::default()
+    };
+    assert!(render_frab(&HashMap::new(), &sessions, &event).is_ok());
+}
+
+#[test]
+fn frab_guids_do_not_depend_on_the_toolchain() {
+    let mut abstracts = HashMap::new();
+    abstracts.insert("a1".to_string(), abstract_with("a1", "First", &["Ann"]));
+    let sessions = vec![Session {
+        id: "Plenum".to_string(),
+        title: "Plenum".to_string(),
+        order: 1,
+        items: vec![ItemRef {
+            id: "a1".to_string(),
+            order: 1,
+        }],
+    }];
+    let xml = render_frab(&abstracts, &sessions, &EventConfig::default()).unwrap();
+    // 128-bit FNV-1a of "symposium-2026", "event", "a1"
+    assert!(
+        xml.contains("<event guid=\"c992f972-1404-88f7-8b81-b6a8463eef86\""),
+        "{}",
+        xml
+    );
+}
+
+#[test]
+fn frab_abstracts_are_plain_text_with_markup_on() {
+    let dir = "target/test-frab-markup";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/abstracts.xlsx", dir);
+    common::write_workbook(
+        &xlsx,
+        &[
+            &["id", "title", "authors", "locale", "abstract"],
+            &[
+                "a1",
+                "Gases",
+                "Ann Lee",
+                "en",
+                "Results: **All** wards had less H~2~O, 2\\*3 & <more>.",
+            ],
+        ],
+        &["Plenum", "a1"],
+    );
+    let config = format!("{}/config.toml", dir);
+    fs::write(&config, "[text]\nmarkup = true\n").unwrap();
+    let out = format!("{}/out", dir);
+    common::build(
+        &xlsx,
+        &out,
+        &[
+            "--config",
+            &config,
+            "--no-pdf",
+            "--format",
+            "frab",
+            "--locales",
+            "en",
+        ],
+    );
+    let xml = fs::read_to_string(format!("{}/schedule.xml", out)).unwrap();
+    assert!(
+        xml.contains("<abstract>All wards had less H2O, 2*3 &amp; &lt;more&gt;.</abstract>"),
+        "{}",
+        xml
+    );
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+const ROWS: &[&[&str]] = &[
+    &["id", "title", "authors", "locale", "abstract", "keywords"],
+    &[
+        "a1",
+        "Safer <wards>",
+        "Ann Lee; Bo Ek",
+        "en",
+        "See https://example.org/x and more",
+        "patient safety",
+    ],
+    &["a2", "Second talk", "Cy Dahl", "en", "Plain text", ""],
+];
+const SESSIONS: &[&str] = &["Morning", "a1", "a2"];
+
+#[test]
+fn html_writes_a_self_contained_site_per_locale() {
+    let dir = "target/test-html-site";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/abstracts.xlsx", dir);
+    common::write_workbook(&xlsx, ROWS, SESSIONS);
+    let out = format!("{}/out", dir);
+
+    common::build(
+        &xlsx,
+        &out,
+        &["--locales", "en,da", "--no-pdf", "--format", "html"],
+    );
+
+    let site = format!("{}/html", out);
+    let languages = fs::read_to_string(format!("{}/index.html", site)).unwrap();
+    assert!(
+        languages.contains("href=\"en/index.html\""),
+        "{}",
+        languages
+    );
+    assert!(
+        languages.contains("href=\"da/index.html\""),
+        "{}",
+        languages
+    );
+
+    // programme in booklet order, titles escaped, links to the abstract pages
+    let index = fs::read_to_string(format!("{}/en/index.html", site)).unwrap();
+    assert!(index.contains("Morning"), "{}", index);
+    assert!(index.contains("Safer &lt;wards&gt;"), "{}", index);
+    assert!(index.contains("href=\"abstracts/a1.html\""), "{}", index);
+    assert!(
+        index.find("Safer").unwrap() < index.find("Second talk").unwrap(),
+        "{}",
+        index
+    );
+    let danish = fs::read_to_string(format!("{}/da/index.html", site)).unwrap();
+    assert!(danish.contains("Søg"), "{}", danish);
+
+    let page = fs::read_to_string(format!("{}/en/abstracts/a1.html", site)).unwrap();
+    assert!(page.contains("<h1>Safer &lt;wards&gt;</h1>"), "{}", page);
+    assert!(
+        page.contains("<a href=\"https://example.org/x\">"),
+        "{}",
+        page
+    );
+    assert!(
+        page.contains("href=\"../tags.html#patient-safety\""),
+        "{}",
+        page
+    );
+    assert!(page.contains("href=\"../../assets/style.css\""), "{}", page);
+
+    let tags = fs::read_to_string(format!("{}/en/tags.html", site)).unwrap();
+    assert!(tags.contains("id=\"patient-safety\""), "{}", tags);
+    let authors = fs::read_to_string(format!("{}/en/authors.html", site)).unwrap();
+    assert!(authors.contains("Lee, Ann"), "{}", authors);
+    assert!(
+        authors.contains("href=\"abstracts/a2.html\""),
+        "{}",
+        authors
+    );
+
+    // plain text for the client-side search
+    let search: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(format!("{}/en/search.json", site)).unwrap())
+            .unwrap();
+    let first = &search[0];
+    assert_eq!(first["title"], "Safer <wards>");
+    assert_eq!(first["href"], "abstracts/a1.html");
+    assert_eq!(first["session"], "Morning");
+    assert_eq!(first["text"], "See https://example.org/x and more");
+
+    // styles and scripts are local files
+    let css = fs::read_to_string(format!("{}/assets/style.css", site)).unwrap();
+    assert!(css.contains("--rh-"), "{}", css);
+    assert!(Path::new(&format!("{}/assets/search.js", site)).is_file());
+    for html in [&languages, &index, &page, &tags, &authors] {
+        assert!(!html.contains("src=\"http"), "{}", html);
+        assert!(!html.contains("<link href=\"http"), "{}", html);
+    }
+}
+
+#[test]
+fn without_html_no_site_is_written() {
+    let dir = "target/test-html-site-off";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/abstracts.xlsx", dir);
+    common::write_workbook(&xlsx, ROWS, SESSIONS);
+    let out = format!("{}/out", dir);
+    common::build(&xlsx, &out, &["--no-pdf"]);
+    assert!(!Path::new(&format!("{}/html", out)).exists());
+}
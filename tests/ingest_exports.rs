@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::Path;
+use symposium_booklet::io::ingest::{SourceKind, parse_source};
+
+fn write(dir: &str, name: &str, content: &str) -> String {
+    fs::create_dir_all(dir).unwrap();
+    let path = Path::new(dir).join(name);
+    fs::write(&path, content).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn easychair_csv_pair_maps_authors_and_tracks() {
+    let dir = "target/test-ingest-easychair";
+    let _ = fs::remove_dir_all(dir);
+    write(
+        dir,
+        "submission.csv",
+        "#,Track name,Title,Authors,Keywords,Decision,Abstract,Deleted?\n\
+         1,Simulation,Sim in wards,\"Ann Berg and Bo Dahl\",\"sim\nteamwork\",accept,Background: Why. Results: Good.,no\n\
+         2,Simulation,Old,Cy,,reject,Text,yes\n\
+         3,Education,Teaching,Cy Eng,ipe,accept,Plain text,no\n",
+    );
+    write(
+        dir,
+        "author.csv",
+        "Submission #,First name,Last name,Email,Affiliation,Corresponding?\n\
+         1,Ann,Berg,ann@example.org,Rigshospitalet,\n\
+         1,Bo,Dahl,bo@example.org,Herlev Hospital,yes\n",
+    );
+
+    let (abstracts, sessions) =
+        parse_source(SourceKind::Easychair, dir, None, &Default::default()).unwrap();
+    assert_eq!(abstracts.len(), 2, "deleted submissions are skipped");
+    let a = &abstracts["1"];
+    assert_eq!(a.authors, vec!["Ann Berg", "Bo Dahl"]);
+    assert_eq!(
+        a.affiliation.as_deref(),
+        Some("Rigshospitalet; Herlev Hospital")
+    );
+    assert_eq!(a.contact_email.as_deref(), Some("bo@example.org"));
+    assert_eq!(a.keywords, vec!["sim", "teamwork"]);
+    assert_eq!(a.status.as_deref(), Some("accepted"));
+    assert_eq!(a.abstract_sections.len(), 2);
+    assert_eq!(abstracts["3"].authors, vec!["Cy Eng"]);
+
+    let titles: Vec<&str> = sessions.iter().map(|s| s.title.as_str()).collect();
+    assert_eq!(titles, vec!["Simulation", "Education"]);
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn conftool_csv_resolves_organisation_references() {
+    let dir = "target/test-ingest-conftool";
+    let _ = fs::remove_dir_all(dir);
+    let path = write(
+        dir,
+        "conftool.csv",
+        "paperID;title;authors;organisations;keywords;abstract;track;acceptance_status_name;language\n\
+         17;Title A;\"Jane Doe (1); John Smith (2)\";\"1: Univ A; 2: Univ B\";ipe, sim;Some text;Posters;Accepted;English\n",
+    );
+    let (abstracts, sessions) =
+        parse_source(SourceKind::Conftool, &path, None, &Default::default()).unwrap();
+    let a = &abstracts["17"];
+    assert_eq!(a.authors, vec!["Jane Doe", "John Smith"]);
+    assert_eq!(a.affiliation.as_deref(), Some("Univ A; Univ B"));
+    assert_eq!(a.locale, "en");
+    assert_eq!(a.track.as_deref(), Some("Posters"));
+    assert_eq!(sessions[0].items[0].id, "17");
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn pretalx_json_reads_localized_fields() {
+    let dir = "target/test-ingest-pretalx";
+    let _ = fs::remove_dir_all(dir);
+    let path = write(
+        dir,
+        "submissions.json",
+        r#"{"count": 1, "results": [{
+            "code": "ABC123",
+            "title": "Learning together",
+            "speakers": [{"code": "X1", "name": "Eva Holm"}],
+            "track": {"en": "Education", "da": "Uddannelse"},
+            "state": "accepted",
+            "abstract": "Short abstract",
+            "tags": ["ipe", {"tag": "simulation"}],
+            "content_locale": "en"
+        }]}"#,
+    );
+    let (abstracts, sessions) =
+        parse_source(SourceKind::Pretalx, &path, None, &Default::default()).unwrap();
+    let a = &abstracts["ABC123"];
+    assert_eq!(a.authors, vec!["Eva Holm"]);
+    assert_eq!(a.track.as_deref(), Some("Education"));
+    assert_eq!(a.keywords, vec!["ipe", "simulation"]);
+    assert_eq!(a.status.as_deref(), Some("accepted"));
+    assert_eq!(sessions.len(), 1);
+    let _ = fs::remove_dir_all(dir);
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::fs;
+use symposium_booklet::keywords::{Thesaurus, key, split, tag};
+
+#[test]
+fn splits_and_normalizes_keyword_cells() {
+    assert_eq!(
+        split("Simulation; patient safety,\nTeamwork - debriefing. Sim lab."),
+        vec![
+            "Simulation",
+            "patient safety",
+            "Teamwork",
+            "debriefing",
+            "Sim lab"
+        ]
+    );
+    assert_eq!(split("Sim, sim ,SIM"), vec!["Sim"]);
+    assert_eq!(key("#Patient_Safety"), "patient safety");
+    assert_eq!(key("  patient   safety "), "patient safety");
+    assert_eq!(tag("Patient Safety"), "#patient_safety");
+}
+
+#[test]
+fn thesaurus_rejects_an_alias_under_two_terms() {
+    let dir = "target/test-keywords-conflict";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let path = format!("{}/thesaurus.toml", dir);
+    fs::write(
+        &path,
+        "[simulation]\naliases = [\"sim\"]\n\n[simulator]\naliases = [\"SIM\"]\n",
+    )
+    .unwrap();
+    let err = Thesaurus::load(&path).unwrap_err().to_string();
+    assert!(err.contains("belongs to both"), "{}", err);
+}
+
+fn setup(dir: &str) -> (String, String) {
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/keywords.xlsx", dir);
+    common::write_workbook(
+        &xlsx,
+        &[
+            &[
+                "id",
+                "title",
+                "authors",
+                "abstract",
+                "keywords",
+                "take home",
+                "reference",
+                "locale",
+            ],
+            &[
+                "a1",
+                "One",
+                "Ann Lee",
+                "Text",
+                "sim; Teamwork",
+                "",
+                "",
+                "en",
+            ],
+            &["a2", "Two", "Bo Ek", "Text", "Simulation", "", "", "en"],
+            &[
+                "a3",
+                "Three",
+                "Cy Dahl",
+                "Text",
+                "simulationstræning, sim lab",
+                "",
+                "",
+                "en",
+            ],
+            &["a4", "Four", "Di Fog", "Text", "Sim Lab", "", "", "en"],
+        ],
+        &["Session", "a1", "a2", "a3", "a4"],
+    );
+    let thesaurus = format!("{}/thesaurus.toml", dir);
+    fs::write(
+        &thesaurus,
+        "[Simulation]\naliases = [\"sim\", \"simulation training\"]\nda = \"Simulationstræning\"\n\n\
+         [teamwork]\nda = \"teamsamarbejde\"\n",
+    )
+    .unwrap();
+    let config = format!("{}/config.toml", dir);
+    fs::write(
+        &config,
+        // named relative to the config file
+        "[keywords]\nthesaurus = \"thesaurus.toml\"\n\n[content]\nuntranslated = \"include\"\n",
+    )
+    .unwrap();
+    (xlsx, config)
+}
+
+#[test]
+fn thesaurus_merges_spellings_into_one_tag_per_locale() {
+    let dir = "target/test-keywords-thesaurus";
+    let (xlsx, config) = setup(dir);
+    let out = format!("{}/out", dir);
+    common::build(
+        &xlsx,
+        &out,
+        &[
+            "--locales",
+            "en,da",
+            "--config",
+            &config,
+            "--data-json",
+            "--no-pdf",
+        ],
+    );
+    let read = |l: &str| -> serde_json::Value {
+        serde_json::from_str(&fs::read_to_string(format!("{}/typst/data_{}.json", out, l)).unwrap())
+            .unwrap()
+    };
+    let entries = |data: &serde_json::Value| -> Vec<(String, usize)> {
+        data["tag_index"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| {
+                (
+                    e["tag"].as_str().unwrap().to_string(),
+                    e["abstracts"].as_array().unwrap().len(),
+                )
+            })
+            .collect()
+    };
+
+    let en = read("en");
+    assert_eq!(
+        entries(&en),
+        vec![
+            ("#sim_lab".to_string(), 2),
+            ("#simulation".to_string(), 3),
+            ("#teamwork".to_string(), 1),
+        ]
+    );
+    assert_eq!(
+        en["sessions"][0]["abstracts"][0]["tags"],
+        serde_json::json!(["#simulation", "#teamwork"])
+    );
+    let da = read("da");
+    assert_eq!(
+        entries(&da),
+        vec![
+            ("#sim_lab".to_string(), 2),
+            ("#simulationstræning".to_string(), 3),
+            ("#teamsamarbejde".to_string(), 1),
+        ]
+    );
+}
+
+#[test]
+fn validate_reports_keywords_missing_from_the_thesaurus() {
+    let dir = "target/test-keywords-validate";
+    let (xlsx, config) = setup(dir);
+    let mut cmd = cargo_bin_cmd!("symposium-booklet");
+    cmd.args(["validate", &xlsx, "--config", &config]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8_lossy(&output);
+    assert!(
+        stdout.contains("Keyword not in thesaurus: sim lab (2 abstracts)"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("not in thesaurus: sim ("), "{}", stdout);
+    assert!(!stdout.contains("Teamwork"), "{}", stdout);
+}
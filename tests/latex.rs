@@ -0,0 +1,113 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+// Builds the LaTeX books in `dir/out`, with `config` as `dir/config.toml`.
+fn build(dir: &str, config: Option<&str>) -> String {
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/abstracts.xlsx", dir);
+    common::write_workbook(
+        &xlsx,
+        &[
+            &["id", "title", "authors", "locale", "abstract", "keywords"],
+            &[
+                "a1",
+                "50% of {beds} & $costs",
+                "Ann Lee",
+                "en",
+                "C:\\temp #1 ~ x_y ^2 \"quoted\" https://example.org/a%20b#top",
+                "patient safety, a|b",
+            ],
+            &["a2", "Second talk", "Cy Dahl", "en", "Plain text", ""],
+        ],
+        &["Morning", "a1", "Afternoon", "a2"],
+    );
+    let out = format!("{}/out", dir);
+    let mut extra = vec!["--locales", "en", "--no-pdf", "--format", "latex"];
+    let path = format!("{}/config.toml", dir);
+    if let Some(config) = config {
+        fs::write(&path, config).unwrap();
+        extra.extend(["--config", &path]);
+    }
+    common::build(&xlsx, &out, &extra);
+    out
+}
+
+#[test]
+fn latex_escapes_text_and_indexes_keywords_and_authors() {
+    let out = build("target/test-latex", None);
+    let tex = fs::read_to_string(format!("{}/latex/book_en.tex", out)).unwrap();
+
+    assert!(tex.starts_with("% book_en.tex"), "{}", tex);
+    assert!(
+        tex.contains("\\documentclass[a5paper,11pt]{book}"),
+        "{}",
+        tex
+    );
+    assert!(tex.contains("\\usepackage[english]{babel}"), "{}", tex);
+
+    // sessions are chapters, abstracts labelled sections in programme order
+    let morning = tex.find("\\chapter{Morning}").unwrap();
+    let first = tex
+        .find("\\section{50\\% of \\{beds\\} \\& \\$costs}\\label{abs-a1}")
+        .unwrap();
+    let afternoon = tex.find("\\chapter{Afternoon}").unwrap();
+    assert!(morning < first && first < afternoon, "{}", tex);
+
+    // submitted text cannot run commands
+    assert!(
+        tex.contains(
+            "C:\\textbackslash{}temp \\#1 \\textasciitilde{} x\\_y \\textasciicircum{}2 \\textquotedbl{}quoted\\textquotedbl{}"
+        ),
+        "{}",
+        tex
+    );
+    assert!(
+        tex.contains("\\href{https://example.org/a\\%20b\\#top}{"),
+        "{}",
+        tex
+    );
+
+    // index entries sorted by the collated position, makeindex specials quoted
+    assert!(tex.contains("\\index[tags]{0000@\\#a\"|b}"), "{}", tex);
+    assert!(
+        tex.contains("\\index[tags]{0001@\\#patient\\_safety}"),
+        "{}",
+        tex
+    );
+    assert!(tex.contains("\\index[authors]{0001@Lee, Ann}"), "{}", tex);
+    assert!(tex.contains("\\printindex[tags]"), "{}", tex);
+    assert!(tex.contains("\\printindex[authors]"), "{}", tex);
+
+    // only the formats asked for: no Typst files, so no PDFs either
+    assert!(!Path::new(&format!("{}/typst", out)).exists());
+}
+
+#[test]
+fn latex_copies_class_file_and_preamble() {
+    // named relative to the config file, not to where the build runs
+    let class = "target/test-latex-partner.cls";
+    fs::write(class, "\\LoadClass{book}\n").unwrap();
+    let preamble = "target/test-latex-preamble.tex";
+    fs::write(preamble, "\\newcommand\\partner{}\n").unwrap();
+    let out = build(
+        "target/test-latex-class",
+        Some(
+            "[latex]\nclass = \"../test-latex-partner.cls\"\nclass_options = \"a4paper\"\npreamble = \"../test-latex-preamble.tex\"\n",
+        ),
+    );
+    let tex = fs::read_to_string(format!("{}/latex/book_en.tex", out)).unwrap();
+    assert!(
+        tex.contains("\\documentclass[a4paper]{test-latex-partner}"),
+        "{}",
+        tex
+    );
+    assert!(tex.contains("\\input{preamble}"), "{}", tex);
+    assert_eq!(
+        fs::read_to_string(format!("{}/latex/test-latex-partner.cls", out)).unwrap(),
+        "\\LoadClass{book}\n"
+    );
+    assert!(Path::new(&format!("{}/latex/preamble.tex", out)).exists());
+}
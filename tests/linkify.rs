@@ -0,0 +1,117 @@
+use std::fs;
+use symposium_booklet::typst::links::linkify;
+use symposium_booklet::typst::markup::{Inline, Target, text};
+
+mod common;
+use common::output::book_text;
+
+fn targets(items: &[Inline]) -> Vec<String> {
+    items
+        .iter()
+        .filter_map(|i| match i {
+            Inline::Link {
+                target: Target::Url(u),
+                ..
+            } => Some(u.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn detects_urls_dois_and_emails() {
+    let out = linkify(vec![text(
+        "See (https://example.org/a_(b)/c?x=1). Also www.kp.dk, doi:10.1000/xyz.12 and \
+         10.5555/abc-1; write to jane.doe@uni.dk.",
+    )]);
+    assert_eq!(
+        targets(&out),
+        vec![
+            "https://example.org/a_(b)/c?x=1",
+            "https://www.kp.dk",
+            "https://doi.org/10.1000/xyz.12",
+            "https://doi.org/10.5555/abc-1",
+            "mailto:jane.doe@uni.dk",
+        ]
+    );
+    // surrounding text is kept, and shown URLs get break opportunities
+    assert_eq!(out[0], text("See ("));
+    let Inline::Link { body, .. } = &out[1] else {
+        panic!("{:?}", out[1]);
+    };
+    assert_eq!(
+        body,
+        &vec![text(
+            "https://example.\u{200B}org/\u{200B}a_\u{200B}(b)/\u{200B}c?\u{200B}x=\u{200B}1"
+        )]
+    );
+    assert_eq!(out.last(), Some(&text(".")));
+}
+
+#[test]
+fn plain_text_is_untouched() {
+    let input = vec![text("p<0.05 at 10.5 mg/kg, see @ref or version 2.0")];
+    assert_eq!(linkify(input.clone()), input);
+}
+
+// The English booklet's text.
+fn build(xlsx: &str, out: &str, extra: &[&str]) -> String {
+    let mut args = vec!["--locales", "en", "--no-pdf"];
+    args.extend(extra);
+    common::build(xlsx, out, &args);
+    book_text(out, "en")
+}
+
+#[test]
+fn build_links_text_and_reference_unless_disabled() {
+    let dir = "target/test-linkify";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/links.xlsx", dir);
+    common::write_workbook(
+        &xlsx,
+        &[
+            &[
+                "id",
+                "title",
+                "locale",
+                "abstract",
+                "keywords",
+                "take home",
+                "reference",
+            ],
+            &[
+                "l1",
+                "Linked",
+                "en",
+                "Data at https://example.org/data, contact a@b.dk",
+                "Open data",
+                "Share at www.example.org",
+                "doi:10.1000/182",
+            ],
+        ],
+        &["Session 1", "l1"],
+    );
+
+    let typ = build(&xlsx, &format!("{}/out", dir), &[]);
+    assert!(
+        typ.contains("#link(\"https://example.org/data\")["),
+        "{}",
+        typ
+    );
+    assert!(typ.contains("#link(\"mailto:a@b.dk\")[a\\@"), "{}", typ);
+    assert!(typ.contains("#link(\"https://www.example.org\")[www.\u{200B}example"));
+    assert!(typ.contains("#strong[Reference]: #link(\"https://doi.org/10.1000/182\")"));
+    let (errors, _) = typst_syntax::parse(&typ).errors_and_warnings();
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let typ = build(&xlsx, &format!("{}/print", dir), &["--no-linkify"]);
+    assert!(!typ.contains("#link(\""), "{}", typ);
+    assert!(
+        typ.contains("#strong[Reference]: doi:10.1000/182"),
+        "{}",
+        typ
+    );
+
+    let _ = fs::remove_dir_all(dir);
+}
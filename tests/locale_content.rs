@@ -0,0 +1,112 @@
+use std::fs;
+
+mod common;
+use common::output::book_text;
+
+// Both booklets' text.
+fn build(xlsx: &str, out: &str, extra: &[&str]) -> (String, String) {
+    let mut args = vec!["--locales", "en,da", "--no-pdf"];
+    args.extend(extra);
+    common::build(xlsx, out, &args);
+    let read = |l: &str| book_text(out, l);
+    (read("en"), read("da"))
+}
+
+#[test]
+fn booklets_pick_their_language_variant() {
+    let dir = "target/test-locale-content";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/variants.xlsx", dir);
+    common::write_workbook(
+        &xlsx,
+        &[
+            &[
+                "id",
+                "title",
+                "authors",
+                "locale",
+                "abstract",
+                "keywords",
+                "take home",
+                "reference",
+                "title_en",
+                "abstract_en",
+            ],
+            &[
+                "d1",
+                "Dansk titel",
+                "Ann Berg",
+                "da",
+                "Dansk tekst",
+                "",
+                "",
+                "",
+                "English title",
+                "English text",
+            ],
+            &[
+                "d2",
+                "Kun dansk",
+                "Bo Ek",
+                "da",
+                "Kun dansk tekst",
+                "",
+                "",
+                "",
+                "",
+                "",
+            ],
+            &[
+                "e1",
+                "Only English",
+                "Cy Dahl",
+                "en",
+                "English only",
+                "",
+                "",
+                "",
+                "",
+                "",
+            ],
+        ],
+        &["Session 1", "d1", "d2", "e1"],
+    );
+
+    // default: each booklet holds only text in its own language
+    let out = format!("{}/strict", dir);
+    let (en, da) = build(&xlsx, &out, &[]);
+    assert!(en.contains("== English title") && en.contains("English text"));
+    assert!(en.contains("== Only English"));
+    assert!(!en.contains("Kun dansk") && !en.contains("Dansk"), "{}", en);
+    assert!(da.contains("== Dansk titel") && da.contains("== Kun dansk"));
+    assert!(!da.contains("English"), "{}", da);
+    let md = fs::read_to_string(format!("{}/session-1/0001-dansk-titel.md", out)).unwrap();
+    assert!(
+        md.contains("variants:\n  \"en\":\n    title: \"English title\""),
+        "{}",
+        md
+    );
+
+    // untranslated abstracts marked; the Danish booklet also takes English text
+    let config = format!("{}/content.toml", dir);
+    fs::write(
+        &config,
+        "[content]\nuntranslated = \"mark\"\nsources = { da = [\"da\", \"en\"] }\n",
+    )
+    .unwrap();
+    let out = format!("{}/marked", dir);
+    let (en, da) = build(&xlsx, &out, &["--config", &config]);
+    assert!(
+        en.contains(
+            "== Kun dansk <abs-d2>\n#text(size: 7pt, fill: rgb(\"#646c6f\"))[Not translated (da)]"
+        ),
+        "{}",
+        en
+    );
+    assert!(!en.contains("Dansk titel"));
+    assert!(da.contains("== Dansk titel") && da.contains("== Only English"));
+    assert!(!da.contains("Ikke oversat"), "{}", da);
+
+    let _ = fs::remove_dir_all(dir);
+}
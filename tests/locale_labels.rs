@@ -0,0 +1,84 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::fs;
+
+mod common;
+use common::fixtures::make_fixture;
+
+#[test]
+fn labels_follow_locale_chain_and_validate_reports_coverage() {
+    let dir = "target/test-locale-labels";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(format!("{}/tpl/locales", dir)).unwrap();
+    let xlsx = format!("{}/fixture.xlsx", dir);
+    make_fixture(&xlsx).unwrap();
+    fs::write(
+        format!("{}/tpl/book.typ", dir),
+        "{{ labels.greeting }}|{{ labels.cover.title }}|{{ labels.cover.date }}|{{ labels.toc_label }}\n",
+    )
+    .unwrap();
+    fs::write(
+        format!("{}/tpl/locales/en.toml", dir),
+        "greeting = \"Hello\"\n[cover]\ntitle = \"Programme\"\ndate = \"13 March\"\n",
+    )
+    .unwrap();
+    fs::write(
+        format!("{}/tpl/locales/da.toml", dir),
+        "greeting = \"Hej\"\nextra = \"ubrugt\"\n[cover]\ntitle = \"Program\"\n",
+    )
+    .unwrap();
+
+    let out = format!("{}/out", dir);
+    let mut cmd = cargo_bin_cmd!("symposium-booklet");
+    cmd.args([
+        "build",
+        "--input",
+        &xlsx,
+        "--output",
+        &out,
+        "--locales",
+        "da-DK,sv",
+        "--template",
+        &format!("{}/tpl", dir),
+        "--no-pdf",
+    ]);
+    cmd.assert().success();
+    // da-DK -> da -> en, nested tables merge key by key, built-in defaults last
+    let typ = fs::read_to_string(format!("{}/typst/book_da-DK.typ", out)).unwrap();
+    assert_eq!(typ, "Hej|Program|13 March|Table of contents\n");
+    let typ = fs::read_to_string(format!("{}/typst/book_sv.typ", out)).unwrap();
+    assert_eq!(typ, "Hello|Programme|13 March|Table of contents\n");
+
+    let mut cmd = cargo_bin_cmd!("symposium-booklet");
+    cmd.args([
+        "validate",
+        &xlsx,
+        "--locales",
+        "en,da,sv",
+        "--template",
+        &format!("{}/tpl", dir),
+    ]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8(output).unwrap();
+    assert!(
+        stdout.contains("Locale en: missing labels toc_label"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Locale da: missing labels cover.date, toc_label"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Locale da: labels not used by the template: extra"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Locale sv: no locale file") && stdout.contains("(falls back to en)"),
+        "{}",
+        stdout
+    );
+
+    let _ = fs::remove_dir_all(dir);
+}
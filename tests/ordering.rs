@@ -0,0 +1,101 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use symposium_booklet::collate;
+use symposium_booklet::config::{OrderingConfig, SortMode};
+use symposium_booklet::model::{Abstract, ItemRef, Session};
+use symposium_booklet::ordering::apply_ordering;
+
+fn abs(id: &str, title: &str, author: &str) -> Abstract {
+    Abstract {
+        id: id.to_string(),
+        title: title.to_string(),
+        authors: vec![author.to_string()],
+        locale: "da".to_string(),
+        ..Default::default()
+    }
+}
+
+fn session(id: &str, ids: &[&str]) -> Session {
+    Session {
+        id: id.to_string(),
+        title: id.to_string(),
+        order: 1,
+        items: ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| ItemRef {
+                id: id.to_string(),
+                order: i as u32 + 1,
+            })
+            .collect(),
+    }
+}
+
+fn ids(session: &Session) -> Vec<&str> {
+    session.items.iter().map(|i| i.id.as_str()).collect()
+}
+
+#[test]
+fn danish_letters_collate_after_z() {
+    let mut words = vec!["Åben", "Ærlig", "Zebra", "Øre", "Ande", "Éclair"];
+    words.sort_by(|a, b| collate::compare(a, b, "da"));
+    assert_eq!(
+        words,
+        vec!["Ande", "Éclair", "Zebra", "Ærlig", "Øre", "Åben"]
+    );
+    assert_eq!(collate::compare("Øre", "Ore", "en"), Ordering::Greater);
+    assert_eq!(collate::compare("Åben", "Zebra", "en"), Ordering::Less);
+}
+
+#[test]
+fn swedish_alphabet_and_index_letters() {
+    let mut words = vec!["Öl", "Ärlig", "Åsa", "Zon", "Æble"];
+    words.sort_by(|a, b| collate::compare(a, b, "sv-SE"));
+    assert_eq!(words, vec!["Zon", "Åsa", "Æble", "Ärlig", "Öl"]);
+    assert_eq!(collate::initial("Ærlig", "da"), "Æ");
+    assert_eq!(collate::initial("Ærlig", "en"), "A");
+    assert_eq!(collate::initial("#éclair", "da"), "E");
+    assert_eq!(collate::initial("3D", "da"), "0-9");
+    assert_eq!(collate::initial("—", "da"), "#");
+}
+
+#[test]
+fn sessions_follow_configured_modes() {
+    let mut abstracts = HashMap::new();
+    for a in [
+        abs("a10", "Åben dialog", "Mette Østergaard, sygeplejerske"),
+        abs("a2", "Ærlig feedback", "Jens Andersen"),
+        abs("a3", "Zoneterapi", "Anna Åberg"),
+    ] {
+        abstracts.insert(a.id.clone(), a);
+    }
+    let all = ["a10", "a2", "a3"];
+    let mut sessions = vec![
+        session("by-title", &all),
+        session("by-author", &all),
+        session("by-id", &all),
+        session("hand-picked", &all),
+        session("as-sheet", &all),
+    ];
+    let config = OrderingConfig {
+        default: SortMode::Sheet,
+        locale: "da".to_string(),
+        sessions: HashMap::from([
+            ("by-title".to_string(), SortMode::Title),
+            ("by-author".to_string(), SortMode::Author),
+            ("by-id".to_string(), SortMode::Id),
+            ("hand-picked".to_string(), SortMode::Explicit),
+        ]),
+        explicit: HashMap::from([("hand-picked".to_string(), vec!["a3".to_string()])]),
+        ..Default::default()
+    };
+    apply_ordering(&abstracts, &mut sessions, &config);
+
+    assert_eq!(ids(&sessions[0]), vec!["a3", "a2", "a10"]);
+    assert_eq!(ids(&sessions[1]), vec!["a2", "a10", "a3"]);
+    assert_eq!(ids(&sessions[2]), vec!["a2", "a3", "a10"]);
+    assert_eq!(ids(&sessions[3]), vec!["a3", "a10", "a2"]);
+    assert_eq!(ids(&sessions[4]), all.to_vec());
+    let orders: Vec<u32> = sessions[0].items.iter().map(|i| i.order).collect();
+    assert_eq!(orders, vec![1, 2, 3]);
+}
@@ -0,0 +1,109 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::fixtures::make_fixture;
+
+fn fixture(dir: &str) -> String {
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/fixture.xlsx", dir);
+    make_fixture(&xlsx).unwrap();
+    xlsx
+}
+
+#[test]
+fn missing_typst_is_an_error_unless_no_pdf() {
+    let dir = "target/test-pdf-backend-missing";
+    let xlsx = fixture(dir);
+    let out = format!("{}/out", dir);
+    let mut cmd = cargo_bin_cmd!("symposium-booklet");
+    cmd.args([
+        "build",
+        "--input",
+        &xlsx,
+        "--output",
+        &out,
+        "--locales",
+        "en",
+        "--typst-bin",
+        "/nonexistent/typst",
+    ]);
+    let output = cmd.assert().failure().get_output().stderr.clone();
+    let stderr = String::from_utf8_lossy(&output);
+    assert!(stderr.contains("/nonexistent/typst"), "{}", stderr);
+    assert!(stderr.contains("--no-pdf"), "{}", stderr);
+    // the typst files are still there to compile by hand
+    assert!(Path::new(&out).join("typst/book_en.typ").exists());
+
+    let mut cmd = cargo_bin_cmd!("symposium-booklet");
+    cmd.args([
+        "build",
+        "--input",
+        &xlsx,
+        "--output",
+        &out,
+        "--locales",
+        "en",
+        "--typst-bin",
+        "/nonexistent/typst",
+        "--no-pdf",
+    ]);
+    cmd.assert().success();
+    assert!(Path::new(&out).join("typst/book_en.typ").exists());
+    assert!(!Path::new(&out).join("symposium-2026_en.pdf").exists());
+}
+
+#[cfg(feature = "embedded-typst")]
+#[test]
+fn embedded_compiler_writes_pdf_and_previews() {
+    let dir = "target/test-pdf-backend-embedded";
+    let xlsx = fixture(dir);
+    let out = format!("{}/out", dir);
+    let mut cmd = cargo_bin_cmd!("symposium-booklet");
+    cmd.args([
+        "build",
+        "--input",
+        &xlsx,
+        "--output",
+        &out,
+        "--locales",
+        "en",
+        "--preview",
+        "png,svg",
+    ]);
+    cmd.assert().success();
+    let pdf = fs::read(format!("{}/symposium-2026_en.pdf", out)).unwrap();
+    assert!(pdf.starts_with(b"%PDF"));
+    let previews: Vec<String> = fs::read_dir(format!("{}/preview/en", out))
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    assert!(
+        previews
+            .iter()
+            .any(|p| p.starts_with("page-1") && p.ends_with(".png"))
+    );
+    assert_eq!(
+        previews.iter().filter(|p| p.ends_with(".png")).count(),
+        previews.iter().filter(|p| p.ends_with(".svg")).count()
+    );
+}
+
+#[cfg(feature = "embedded-typst")]
+#[test]
+fn embedded_compiler_builds_outside_the_working_directory() {
+    let dir = "target/test-pdf-backend-outside";
+    let xlsx = fixture(dir);
+    let out = std::env::temp_dir().join("symposium-booklet-test-pdf-outside");
+    let _ = fs::remove_dir_all(&out);
+    let mut cmd = cargo_bin_cmd!("symposium-booklet");
+    cmd.args(["build", "--input", &xlsx, "--locales", "en", "--no-cache"])
+        .arg("--output")
+        .arg(&out);
+    cmd.assert().success();
+    let pdf = fs::read(out.join("symposium-2026_en.pdf")).unwrap();
+    assert!(pdf.starts_with(b"%PDF"));
+    let _ = fs::remove_dir_all(&out);
+}
@@ -0,0 +1,300 @@
+mod common;
+
+use anyhow::Result;
+use assert_cmd::cargo::cargo_bin_cmd;
+use clap::Parser;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use symposium_booklet::cli::{Cli, Commands};
+use symposium_booklet::config::TextConfig;
+use symposium_booklet::io::ingest::Ingestor;
+use symposium_booklet::io::markdown::{write_markdown, write_markdown_plan};
+use symposium_booklet::io::plan::{Plan, PlanAction};
+use symposium_booklet::model::{Abstract, ItemRef, Session};
+use symposium_booklet::render::{Registry, RenderContext, Renderer};
+
+// Abstracts from somewhere other than a workbook.
+struct Fixed;
+
+impl Ingestor for Fixed {
+    fn ingest(
+        &self,
+        _input: &str,
+        _grouping: Option<&str>,
+        _text: &TextConfig,
+    ) -> Result<(HashMap<String, Abstract>, Vec<Session>)> {
+        let mut abstracts = HashMap::new();
+        for (id, title) in [("a1", "First"), ("a2", "Second")] {
+            abstracts.insert(
+                id.to_string(),
+                Abstract {
+                    id: id.to_string(),
+                    title: title.to_string(),
+                    authors: vec!["Ann Lee".to_string()],
+                    abstract_text: "Text".to_string(),
+                    locale: "en".to_string(),
+                    ..Default::default()
+                },
+            );
+        }
+        let session = Session {
+            id: "s1".to_string(),
+            title: "Morning".to_string(),
+            order: 1,
+            items: vec![
+                ItemRef {
+                    id: "a2".to_string(),
+                    order: 1,
+                },
+                ItemRef {
+                    id: "a1".to_string(),
+                    order: 2,
+                },
+            ],
+        };
+        Ok((abstracts, vec![session]))
+    }
+}
+
+// A plain-text outline per locale from the shared book data.
+struct Outline;
+
+impl Renderer for Outline {
+    fn render(&self, ctx: &RenderContext) -> Result<()> {
+        for book in &ctx.booklet()?.editions {
+            let mut text = String::new();
+            for session in &book.sessions {
+                text.push_str(&format!("{}\n", session.title));
+                for abs in &session.abstracts {
+                    text.push_str(&format!("  {}\n", abs.title));
+                }
+            }
+            fs::write(self.outputs(ctx, &book.locale).remove(0), text)?;
+        }
+        Ok(())
+    }
+
+    fn plan(&self, ctx: &RenderContext, plan: &mut Plan) -> Result<()> {
+        plan.push(PlanAction::WriteFile {
+            path: self.outputs(ctx, "en").remove(0),
+            summary: "outline".to_string(),
+        });
+        Ok(())
+    }
+
+    fn outputs(&self, ctx: &RenderContext, locale: &str) -> Vec<PathBuf> {
+        vec![Path::new(ctx.outdir()).join(format!("outline_{}.txt", locale))]
+    }
+}
+
+#[test]
+fn library_builds_with_own_ingestor_and_renderer() {
+    let out = "target/test-renderers";
+    let _ = fs::remove_dir_all(out);
+    let cli = Cli::parse_from([
+        "symposium-booklet",
+        "build",
+        "--input",
+        "unused",
+        "--output",
+        out,
+        "--locales",
+        "en",
+        "--no-pdf",
+        "--no-cache",
+        "--format",
+        "outline,typst",
+    ]);
+    let Commands::Build(opts) = cli.command else {
+        panic!("not a build");
+    };
+    let mut registry = Registry::default();
+    registry.register("outline", Outline);
+    symposium_booklet::io::run_build_with(&opts, &Fixed, &registry).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(format!("{}/outline_en.txt", out)).unwrap(),
+        "Morning\n  Second\n  First\n"
+    );
+    assert!(Path::new(&format!("{}/typst/book_en.typ", out)).exists());
+
+    // formats the registry does not know are refused
+    let cli = Cli::parse_from([
+        "symposium-booklet",
+        "build",
+        "--input",
+        "unused",
+        "--output",
+        out,
+        "--format",
+        "outline",
+    ]);
+    let Commands::Build(opts) = cli.command else {
+        panic!("not a build");
+    };
+    let err = symposium_booklet::io::run_build_with(&opts, &Fixed, &Registry::default())
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("unknown output format: outline"), "{}", err);
+}
+
+#[test]
+fn library_watches_with_own_ingestor_and_renderer() {
+    let out = "target/test-renderers-watch";
+    let _ = fs::remove_dir_all(out);
+    let cli = Cli::parse_from([
+        "symposium-booklet",
+        "watch",
+        "--input",
+        "unused",
+        "--output",
+        out,
+        "--locales",
+        "en",
+        "--no-pdf",
+        "--format",
+        "outline",
+        "--max-builds",
+        "1",
+    ]);
+    let Commands::Watch(opts) = cli.command else {
+        panic!("not a watch");
+    };
+    let mut registry = Registry::default();
+    registry.register("outline", Outline);
+    symposium_booklet::io::watch::run_watch_with(&opts, &Fixed, &registry).unwrap();
+    assert!(Path::new(&format!("{}/outline_en.txt", out)).exists());
+}
+
+#[test]
+fn formats_come_from_config_without_format_flag() {
+    let dir = "target/test-renderers-config";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/abstracts.xlsx", dir);
+    common::write_workbook(
+        &xlsx,
+        &[
+            &["id", "title", "authors", "locale", "abstract"],
+            &["a1", "Talk", "Ann Lee", "en", "Text"],
+        ],
+        &["Morning", "a1"],
+    );
+    let config = format!("{}/config.toml", dir);
+    fs::write(&config, "[output]\nformats = [\"latex\"]\n").unwrap();
+
+    let out = format!("{}/out", dir);
+    common::build(
+        &xlsx,
+        &out,
+        &["--locales", "en", "--no-pdf", "--config", &config],
+    );
+    assert!(Path::new(&format!("{}/latex/book_en.tex", out)).exists());
+    assert!(!Path::new(&format!("{}/typst", out)).exists());
+}
+
+#[test]
+fn markdown_plan_matches_the_written_files() {
+    let out = "target/test-renderers-markdown";
+    let _ = fs::remove_dir_all(out);
+    let mut abstracts = HashMap::new();
+    for id in ["a1", "a2"] {
+        abstracts.insert(
+            id.to_string(),
+            Abstract {
+                id: id.to_string(),
+                title: "Same title".to_string(),
+                locale: "en".to_string(),
+                ..Default::default()
+            },
+        );
+    }
+    // two sessions with one title share a directory
+    let sessions: Vec<Session> = ["a1", "a2"]
+        .iter()
+        .enumerate()
+        .map(|(i, id)| Session {
+            id: format!("s{}", i + 1),
+            title: "Posters".to_string(),
+            order: i as u32 + 1,
+            items: vec![ItemRef {
+                id: id.to_string(),
+                order: 1,
+            }],
+        })
+        .collect();
+
+    let mut plan = Plan::default();
+    write_markdown_plan(&abstracts, &sessions, out, &mut plan).unwrap();
+    let mut planned: Vec<PathBuf> = plan
+        .actions
+        .into_iter()
+        .filter_map(|a| match a {
+            PlanAction::WriteFile { path, .. } => Some(path),
+            _ => None,
+        })
+        .collect();
+    planned.sort();
+
+    write_markdown(&abstracts, &sessions, out, &Default::default()).unwrap();
+    let mut written: Vec<PathBuf> = fs::read_dir(format!("{}/posters", out))
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .collect();
+    written.sort();
+
+    assert_eq!(planned, written);
+    assert_eq!(
+        written,
+        [
+            PathBuf::from(format!("{}/posters/0001-same-title-1.md", out)),
+            PathBuf::from(format!("{}/posters/0001-same-title.md", out)),
+        ]
+    );
+}
+
+#[test]
+fn long_session_titles_keep_their_abstracts() {
+    let out = "target/test-renderers-long-title";
+    let _ = fs::remove_dir_all(out);
+    let mut abstracts = HashMap::new();
+    abstracts.insert(
+        "a1".to_string(),
+        Abstract {
+            id: "a1".to_string(),
+            title: "Talk".to_string(),
+            locale: "en".to_string(),
+            ..Default::default()
+        },
+    );
+    let title = "Simulation based training in interprofessional emergency teams and beyond";
+    let sessions = vec![Session {
+        id: "s1".to_string(),
+        title: title.to_string(),
+        order: 1,
+        items: vec![ItemRef {
+            id: "a1".to_string(),
+            order: 1,
+        }],
+    }];
+    write_markdown(&abstracts, &sessions, out, &Default::default()).unwrap();
+
+    // the manifest names the session directory
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(format!("{}/manifest.json", out)).unwrap())
+            .unwrap();
+    let slug = manifest["sessions"][0]["slug"].as_str().unwrap();
+    assert!(
+        Path::new(out).join(slug).join("0001-talk.md").exists(),
+        "{}",
+        slug
+    );
+
+    cargo_bin_cmd!("symposium-booklet")
+        .args(["emit-typst", "--output", out, "--locales", "en", "--no-pdf"])
+        .assert()
+        .success();
+    let book = common::output::book_text(out, "en");
+    assert!(book.contains("Talk"), "{}", book);
+}
@@ -0,0 +1,100 @@
+use std::fs;
+use symposium_booklet::richtext::{Block, Inline, parse, to_markdown, underscore_warning};
+
+mod common;
+use common::output::book_text;
+
+fn t(s: &str) -> Inline {
+    Inline::Text(s.to_string())
+}
+
+#[test]
+fn parses_inline_markup_and_lists() {
+    let (blocks, warnings) =
+        parse("Levels of CO~2~ and m^2^ were *lower* in **all** wards:\n- ward A\n- ward *B*");
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(
+        blocks,
+        vec![
+            Block::Paragraph(vec![
+                t("Levels of CO"),
+                Inline::Sub(vec![t("2")]),
+                t(" and m"),
+                Inline::Sup(vec![t("2")]),
+                t(" were "),
+                Inline::Emph(vec![t("lower")]),
+                t(" in "),
+                Inline::Strong(vec![t("all")]),
+                t(" wards:"),
+            ]),
+            Block::List(vec![
+                vec![t("ward A")],
+                vec![t("ward "), Inline::Emph(vec![t("B")])],
+            ]),
+        ]
+    );
+    assert_eq!(parse(&to_markdown(&blocks)).0, blocks);
+}
+
+#[test]
+fn stray_and_unknown_markup_stays_text_with_warnings() {
+    let (blocks, warnings) = parse("p<0.05 *significant and ~ 5 min, 2\\*3 <b>bold</b>");
+    assert_eq!(
+        blocks,
+        vec![Block::Paragraph(vec![t(
+            "p<0.05 *significant and ~ 5 min, 2*3 <b>bold</b>"
+        )])]
+    );
+    assert!(warnings.iter().any(|w| w.contains("HTML tag")));
+    assert!(warnings.iter().any(|w| w.contains("unmatched `*`")));
+
+    // escaped literals survive a round trip through the canonical form
+    assert_eq!(parse(&to_markdown(&blocks)).0, blocks);
+
+    // Markdown's `__bold__` underlines; typed text is warned about it
+    let warning = underscore_warning("a __key__ point").unwrap();
+    assert!(warning.contains("`__key__`"), "{}", warning);
+    assert!(underscore_warning("snake_case and \\__init__").is_none());
+}
+
+#[test]
+fn build_renders_markup_when_enabled() {
+    let dir = "target/test-richtext";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/fixture.xlsx", dir);
+    common::write_workbook(
+        &xlsx,
+        &[
+            &["id", "title", "locale", "abstract"],
+            &[
+                "r1",
+                "Rich one",
+                "en",
+                "H~2~O is *wet*:\n- **very**\n- x^2^",
+            ],
+        ],
+        &["Session 1", "r1"],
+    );
+    let config = format!("{}/booklet.toml", dir);
+    fs::write(&config, "[text]\nmarkup = true\n").unwrap();
+
+    let out = format!("{}/out", dir);
+    common::build(
+        &xlsx,
+        &out,
+        &["--locales", "en", "--config", &config, "--no-pdf"],
+    );
+
+    let md = fs::read_to_string(format!("{}/session-1/0001-rich-one.md", out)).unwrap();
+    assert!(md.contains("markup: true"));
+    assert!(md.contains("H~2~O is *wet*:\n\n- **very**\n- x^2^"));
+
+    let typ = book_text(&out, "en");
+    assert!(typ.contains("H#sub[2]O is #emph[wet]:"));
+    assert!(typ.contains("- #strong[very]\n- x#super[2]\n"));
+    let (errors, _) = typst_syntax::parse(&typ).errors_and_warnings();
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let _ = fs::remove_dir_all(dir);
+}
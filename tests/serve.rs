@@ -0,0 +1,159 @@
+mod common;
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{Receiver, channel};
+use std::thread;
+use std::time::Duration;
+
+// Saved under another name and renamed, so the server never reads half a file.
+fn write_workbook(path: &str, title: &str) {
+    let tmp = format!("{}.tmp", path);
+    common::write_workbook(
+        &tmp,
+        &[
+            &["id", "title", "authors", "locale", "abstract", "status"],
+            &["s1", title, "Ann Lee", "en", "Some <text>", "accepted"],
+            &["s2", "Pulled talk", "Bo Ek", "en", "Gone", "withdrawn"],
+        ],
+        &["Morning", "s1", "s2"],
+    );
+    fs::rename(&tmp, path).unwrap();
+}
+
+// Stops the server when the test ends, passed or not.
+struct Server(Child);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+// The server with its stdout lines on a channel.
+fn serve(xlsx: &str, out: &str) -> (Server, Receiver<String>) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_symposium-booklet"))
+        .args([
+            "serve",
+            "--input",
+            xlsx,
+            "--output",
+            out,
+            "--locales",
+            "en",
+            "--no-pdf",
+            "--port",
+            "0",
+            "--debounce",
+            "100",
+            "--poll",
+            "20",
+        ])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stdout = child.stdout.take().unwrap();
+    let (tx, rx) = channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    (Server(child), rx)
+}
+
+fn wait_for_line(rx: &Receiver<String>, needle: &str) -> String {
+    loop {
+        let line = rx
+            .recv_timeout(Duration::from_secs(60))
+            .unwrap_or_else(|_| panic!("timed out waiting for {}", needle));
+        if line.contains(needle) {
+            return line;
+        }
+    }
+}
+
+fn get(addr: &str, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "GET {} HTTP/1.1\r\nHost: {}\r\n\r\n", path, addr).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+// The page once it shows `needle`; it is updated just after the build is logged.
+fn wait_for_page(addr: &str, needle: &str) -> String {
+    for _ in 0..500 {
+        let page = get(addr, "/");
+        if page.contains(needle) {
+            return page;
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    panic!("page never showed {}", needle);
+}
+
+#[test]
+fn serves_the_programme_and_reloads_after_a_rebuild() {
+    let dir = "target/test-serve";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/abstracts.xlsx", dir);
+    let out = format!("{}/out", dir);
+    write_workbook(&xlsx, "First title");
+
+    let (_server, rx) = serve(&xlsx, &out);
+    let line = wait_for_line(&rx, "Serving the preview at http://");
+    let addr = line
+        .split("http://")
+        .nth(1)
+        .and_then(|rest| rest.split('/').next())
+        .unwrap()
+        .to_string();
+    wait_for_line(&rx, "Build ok");
+
+    let page = wait_for_page(&addr, "Build 1 ok");
+    assert!(page.starts_with("HTTP/1.1 200 OK"), "{}", page);
+    assert!(page.contains("<h2>Morning</h2>"), "{}", page);
+    assert!(page.contains("First title"), "{}", page);
+    assert!(page.contains("Some &lt;text&gt;"), "{}", page);
+    assert!(!page.contains("Pulled talk"), "{}", page);
+    assert!(
+        page.contains("references abstract s2 with status &#x27;withdrawn&#x27;"),
+        "{}",
+        page
+    );
+    assert!(
+        page.contains("new EventSource(\"/events?since=1\")"),
+        "{}",
+        page
+    );
+    assert!(get(&addr, "/files/..%2Fabstracts.xlsx").starts_with("HTTP/1.1 404"));
+
+    // the event stream announces the next build
+    let mut events = TcpStream::connect(&addr).unwrap();
+    write!(
+        events,
+        "GET /events?since=1 HTTP/1.1\r\nHost: {}\r\n\r\n",
+        addr
+    )
+    .unwrap();
+    let mut events = BufReader::new(events);
+    let mut line = String::new();
+    events.read_line(&mut line).unwrap();
+    assert!(line.starts_with("HTTP/1.1 200"), "{}", line);
+    write_workbook(&xlsx, "Second title");
+    loop {
+        line.clear();
+        assert!(events.read_line(&mut line).unwrap() > 0);
+        if line.starts_with("event: reload") {
+            break;
+        }
+    }
+    assert!(wait_for_page(&addr, "Build 2 ok").contains("Second title"));
+}
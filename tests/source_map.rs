@@ -0,0 +1,132 @@
+mod common;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::fs;
+use symposium_booklet::typst::sourcemap::parse_short;
+
+#[test]
+fn parses_short_typst_diagnostics() {
+    let (diags, other) = parse_short(
+        "out/typst/book_en.typ:12:4: error: unclosed delimiter\n\
+         out/typst/book_en.typ:3:0: warning: unknown font family: x\n\
+         something else\n",
+    );
+    assert_eq!(diags.len(), 2);
+    assert_eq!(diags[0].file, "out/typst/book_en.typ");
+    assert_eq!((diags[0].line, diags[0].column), (12, 4));
+    assert!(diags[0].error);
+    assert_eq!(diags[0].message, "unclosed delimiter");
+    assert!(!diags[1].error);
+    assert_eq!(other, vec!["something else"]);
+}
+
+// Stands in for typst: reports an error on the first line holding BROKEN in the
+// book or its session files, and one on line 1 of the book, in
+// `--diagnostic-format short`.
+#[cfg(unix)]
+fn fake_typst(dir: &str) -> String {
+    use std::os::unix::fs::PermissionsExt;
+    let path = format!("{}/typst", dir);
+    fs::write(
+        &path,
+        "#!/bin/sh\n\
+         [ \"$1\" = \"--version\" ] && { echo 'typst 0.0.0'; exit 0; }\n\
+         for a in \"$@\"; do case \"$a\" in *.typ) file=\"$a\";; esac; done\n\
+         hit=$(grep -rn --include='*.typ' BROKEN \"$(dirname \"$file\")\" | head -n 1)\n\
+         echo \"$(echo \"$hit\" | cut -d: -f1,2):2: error: unclosed delimiter\" >&2\n\
+         echo \"$file:1:0: error: unknown variable: foo\" >&2\n\
+         exit 1\n",
+    )
+    .unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+#[cfg(unix)]
+#[test]
+fn typst_errors_point_at_the_workbook_cell() {
+    let dir = "target/test-source-map";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/abstracts.xlsx", dir);
+    // Row 1 is empty and the header is row 2, so abstracts start at row 3.
+    common::write_workbook(
+        &xlsx,
+        &[
+            &[],
+            &[
+                "ID",
+                "Titel",
+                "Forfatter",
+                "locale",
+                "Resumé",
+                "title_en",
+                "abstract_en",
+            ],
+            &["b1", "Fint", "Ann Berg", "da", "Helt fin tekst", "", ""],
+            &[
+                "b2",
+                "Fejl",
+                "Bo Ek",
+                "da",
+                "Tekst med BROKEN i",
+                "Broken",
+                "Text with BROKEN in it",
+            ],
+        ],
+        &["Session", "b1", "b2"],
+    );
+    let typst = fake_typst(dir);
+    let out = format!("{}/out", dir);
+
+    let run = |locale: &str| -> String {
+        let mut cmd = cargo_bin_cmd!("symposium-booklet");
+        cmd.args([
+            "build",
+            "--input",
+            &xlsx,
+            "--output",
+            &out,
+            "--locales",
+            locale,
+            "--typst-bin",
+            &typst,
+        ]);
+        let output = cmd.assert().failure().get_output().stderr.clone();
+        String::from_utf8_lossy(&output).into_owned()
+    };
+
+    let stderr = run("da");
+    assert!(stderr.contains("typst failed for locale da"), "{}", stderr);
+    assert!(
+        stderr.contains("abstract b2 (row 4, column 'Resumé'): unclosed delimiter"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("book_da.typ:1:0: unknown variable: foo"),
+        "{}",
+        stderr
+    );
+    let md = fs::read_to_string(format!("{}/session/0002-fejl.md", out)).unwrap();
+    assert!(
+        md.contains("source:\n  sheet: \"abstracts\"\n  row: 4\n"),
+        "{}",
+        md
+    );
+    assert!(md.contains("    abstract: \"Resumé\"\n"), "{}", md);
+    let map: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(format!("{}/typst/sourcemap_da.json", out)).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(map["entries"].as_array().unwrap().len(), 2);
+    assert_eq!(map["entries"][1]["file"], "sessions_da/01-session.typ");
+
+    // the English booklet prints the variant, so its column is named
+    let stderr = run("en");
+    assert!(
+        stderr.contains("abstract b2 (row 4, column 'abstract_en'): unclosed delimiter"),
+        "{}",
+        stderr
+    );
+}
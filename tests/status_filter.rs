@@ -0,0 +1,57 @@
+use symposium_booklet::filter::{apply_status_filter, normalize_status, normalize_statuses};
+use symposium_booklet::io::excel::parse_abstracts_from_rows;
+use symposium_booklet::model::{ItemRef, Session};
+use symposium_booklet::validation::status_warnings;
+
+fn row(cells: &[&str]) -> Vec<String> {
+    cells.iter().map(|c| c.to_string()).collect()
+}
+
+#[test]
+fn danish_and_english_statuses_normalize() {
+    assert_eq!(normalize_status("Accepteret"), "accepted");
+    assert_eq!(normalize_status("accept"), "accepted");
+    assert_eq!(normalize_status("Afvist"), "rejected");
+    assert_eq!(normalize_status("Ikke optaget"), "rejected");
+    assert_eq!(normalize_status("Trukket tilbage"), "withdrawn");
+    assert_eq!(normalize_status("Afventer"), "pending");
+    assert_eq!(normalize_status("Waitlist"), "waitlist");
+}
+
+#[test]
+fn status_column_filters_abstracts_and_flags_grouping_refs() {
+    let rows = vec![
+        row(&["id", "title", "abstract", "Status"]),
+        row(&["a1", "Kept", "Text", "Accepteret"]),
+        row(&["a2", "Dropped", "Text", "Afvist"]),
+        row(&["a3", "No status", "Text", ""]),
+    ];
+    let mut abstracts = parse_abstracts_from_rows(&rows, 0).unwrap();
+    normalize_statuses(&mut abstracts);
+    assert_eq!(abstracts["a2"].status.as_deref(), Some("rejected"));
+    assert_eq!(abstracts["a3"].status, None);
+
+    let mut sessions = vec![Session {
+        id: "s1".to_string(),
+        title: "Session 1".to_string(),
+        order: 1,
+        items: ["a1", "a2", "a3"]
+            .iter()
+            .enumerate()
+            .map(|(i, id)| ItemRef {
+                id: id.to_string(),
+                order: i as u32 + 1,
+            })
+            .collect(),
+    }];
+    let include = vec!["accepted".to_string()];
+
+    let warnings = status_warnings(&abstracts, &sessions, &include);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("a2"));
+
+    let excluded = apply_status_filter(&mut abstracts, &mut sessions, &include);
+    assert_eq!(excluded, 1);
+    let ids: Vec<&str> = sessions[0].items.iter().map(|i| i.id.as_str()).collect();
+    assert_eq!(ids, vec!["a1", "a3"]);
+}
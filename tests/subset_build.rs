@@ -0,0 +1,92 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::collections::HashMap;
+use std::fs;
+use symposium_booklet::filter::Subset;
+use symposium_booklet::model::{Abstract, ItemRef, Session};
+mod common;
+use common::fixtures::make_fixture;
+
+fn abs(id: &str, keywords: &[&str]) -> Abstract {
+    Abstract {
+        id: id.to_string(),
+        title: format!("Title {}", id),
+        keywords: keywords.iter().map(|k| k.to_string()).collect(),
+        locale: "da".to_string(),
+        ..Default::default()
+    }
+}
+
+fn session(title: &str, ids: &[&str]) -> Session {
+    Session {
+        id: title.to_string(),
+        title: title.to_string(),
+        order: 1,
+        items: ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| ItemRef {
+                id: id.to_string(),
+                order: i as u32 + 1,
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn subset_filters_by_session_slug_and_keyword() {
+    let mut abstracts: HashMap<String, Abstract> = HashMap::new();
+    for a in [
+        abs("a1", &["Simulation, teamwork"]),
+        abs("a2", &["ipe"]),
+        abs("a3", &["Patient safety"]),
+    ] {
+        abstracts.insert(a.id.clone(), a);
+    }
+    let mut sessions = vec![
+        session("Sim og læring", &["a1", "a2"]),
+        session("Posters", &["a3"]),
+    ];
+    let subset = Subset {
+        sessions: vec!["sim-og-laering".to_string()],
+        ids: vec![],
+        keywords: vec!["#teamwork".to_string()],
+    };
+    subset.apply(&mut abstracts, &mut sessions).unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].items.len(), 1);
+    assert!(abstracts.contains_key("a1") && abstracts.len() == 1);
+    assert_eq!(subset.label(), "session-sim-og-laering_keyword-teamwork");
+
+    let none = Subset {
+        ids: vec!["zz".to_string()],
+        ..Default::default()
+    };
+    assert!(none.apply(&mut abstracts, &mut sessions).is_err());
+}
+
+#[test]
+fn partial_dry_run_names_pdf_after_filter() {
+    let fixture_dir = "target/test-fixtures-subset";
+    let _ = fs::create_dir_all(fixture_dir);
+    let xlsx_path = format!("{}/fixture.xlsx", fixture_dir);
+    make_fixture(&xlsx_path).expect("create fixture");
+
+    let mut cmd = cargo_bin_cmd!("symposium-booklet");
+    cmd.args([
+        "build",
+        "--input",
+        &xlsx_path,
+        "--output",
+        "target/test-subset-out",
+        "--only-id",
+        "f1",
+        "--locales",
+        "en",
+        "--dry-run",
+    ]);
+    let assert = cmd.assert().success();
+    let outstr = String::from_utf8(assert.get_output().stdout.clone()).unwrap_or_default();
+    assert!(outstr.contains("symposium-2026_en_partial_ids-f1.pdf"));
+
+    let _ = fs::remove_dir_all(fixture_dir);
+}
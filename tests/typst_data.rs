@@ -0,0 +1,69 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::fs;
+
+mod common;
+use common::fixtures::make_fixture;
+
+fn build(xlsx: &str, out: &str, extra: &[&str]) {
+    let mut cmd = cargo_bin_cmd!("symposium-booklet");
+    cmd.args([
+        "build",
+        "--input",
+        xlsx,
+        "--output",
+        out,
+        "--locales",
+        "en",
+        "--no-pdf",
+    ]);
+    cmd.args(extra);
+    cmd.assert().success();
+}
+
+#[test]
+fn data_json_holds_book_for_native_templates() {
+    let dir = "target/test-data-json";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/fixture.xlsx", dir);
+    make_fixture(&xlsx).unwrap();
+
+    // not written unless asked for
+    let out = format!("{}/plain", dir);
+    build(&xlsx, &out, &[]);
+    assert!(!std::path::Path::new(&format!("{}/typst/data_en.json", out)).exists());
+
+    let out = format!("{}/native", dir);
+    build(
+        &xlsx,
+        &out,
+        &["--data-json", "--template", "templates/native"],
+    );
+    let raw = fs::read_to_string(format!("{}/typst/data_en.json", out)).unwrap();
+    let data: serde_json::Value = serde_json::from_str(&raw).unwrap();
+    assert_eq!(data["schema_version"], 1);
+    assert_eq!(data["locale"], "en");
+    assert_eq!(data["labels"]["toc_label"], "Table of contents");
+    let session = &data["sessions"][0];
+    assert_eq!(session["title"], "Session 1");
+    let abs = &session["abstracts"][0];
+    assert_eq!(abs["id"], "f1");
+    assert_eq!(abs["label"], "abs-f1");
+    assert_eq!(abs["title"], "Fixture One");
+    assert_eq!(
+        abs["sections"][0]["body"],
+        serde_json::json!([{ "paragraph": [{ "text": "Text one" }] }])
+    );
+    assert!(data["tag_index"].is_array());
+
+    // the native entry only points Typst at the data file
+    let typ = fs::read_to_string(format!("{}/typst/book_en.typ", out)).unwrap();
+    assert!(typ.contains("#book(json(\"data_en.json\"))"), "{}", typ);
+    let errors = typst_syntax::parse(&typ).errors_and_warnings().0;
+    assert!(errors.is_empty(), "{:?}", errors);
+    let lib = fs::read_to_string("templates/native/lib.typ").unwrap();
+    let errors = typst_syntax::parse(&lib).errors_and_warnings().0;
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let _ = fs::remove_dir_all(dir);
+}
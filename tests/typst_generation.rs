@@ -4,6 +4,7 @@ use std::process::Command as StdCommand;
 
 mod common;
 use common::fixtures::make_fixture;
+use common::output::book_files;
 
 #[test]
 fn typst_output_compiles_when_typst_available() {
@@ -46,3 +47,40 @@ fn typst_output_compiles_when_typst_available() {
     let _ = fs::remove_dir_all(fixture_dir);
     let _ = fs::remove_dir_all(out);
 }
+
+#[test]
+fn generated_typst_parses_without_errors() {
+    let fixture_dir = "target/test-fixtures-typst-parse";
+    let _ = fs::create_dir_all(fixture_dir);
+    let xlsx_path = format!("{}/fixture.xlsx", fixture_dir);
+    make_fixture(&xlsx_path).expect("create fixture");
+
+    let out = "target/test-typst-parse";
+    let _ = fs::remove_dir_all(out);
+
+    let mut cmd = cargo_bin_cmd!("symposium-booklet");
+    cmd.args([
+        "build",
+        "--input",
+        &xlsx_path,
+        "--output",
+        out,
+        "--locales",
+        "en",
+        "--no-pdf",
+    ]);
+    cmd.assert().success();
+
+    let sources = book_files(out, "en");
+    assert_eq!(sources.len(), 2);
+    for source in &sources {
+        let root = typst_syntax::parse(source);
+        let (errors, _) = root.errors_and_warnings();
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+    assert!(sources[0].contains("#include \"sessions_en/01-session-1.typ\""));
+    assert!(sources[1].contains("== Fixture One <abs-f1>"));
+
+    let _ = fs::remove_dir_all(fixture_dir);
+    let _ = fs::remove_dir_all(out);
+}
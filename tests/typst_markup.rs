@@ -0,0 +1,232 @@
+use symposium_booklet::typst::markup::{Block, Document, Inline, Target, escape_line, text};
+use typst_syntax::{SyntaxKind, SyntaxNode};
+
+// Deterministic xorshift so failures reproduce.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn text(&mut self, max_len: usize) -> String {
+        const PIECES: &[&str] = &[
+            "a",
+            "Z",
+            "ø",
+            "Å",
+            "1",
+            "0",
+            " ",
+            " ",
+            "\n",
+            "\n\n",
+            "\t",
+            "\\",
+            "#",
+            "$",
+            "@",
+            "`",
+            "<",
+            ">",
+            "_",
+            "*",
+            "[",
+            "]",
+            "{",
+            "}",
+            "~",
+            "/",
+            "//",
+            "/*",
+            "*/",
+            "-",
+            "--",
+            "-?",
+            "+",
+            "=",
+            "==",
+            ".",
+            "1.",
+            "(",
+            ")",
+            ":",
+            ";",
+            "'",
+            "\"",
+            "...",
+            "http://x.dk",
+            "https://doi.org/1",
+            "<abs-1>",
+            "@ref",
+            "p<0.05",
+            "\\u{41}",
+            "\r\n",
+            "&",
+            "%",
+        ];
+        let len = self.below(max_len) + 1;
+        (0..len).map(|_| PIECES[self.below(PIECES.len())]).collect()
+    }
+}
+
+fn parse(source: &str) -> SyntaxNode {
+    let root = typst_syntax::parse(source);
+    assert!(
+        root.errors_and_warnings().0.is_empty(),
+        "Typst parse errors {:?} in:\n{}",
+        root.errors_and_warnings().0,
+        source
+    );
+    root
+}
+
+// Flatten plain-text markup back to its text; panics on any node that would
+// format the text instead of printing it.
+fn plain_text(node: &SyntaxNode, out: &mut String) {
+    match node.kind() {
+        SyntaxKind::Markup => {
+            for child in node.children() {
+                plain_text(child, out);
+            }
+        }
+        SyntaxKind::Text | SyntaxKind::Shorthand | SyntaxKind::SmartQuote => {
+            out.push_str(node.leaf_text())
+        }
+        SyntaxKind::Escape => out.push_str(&node.leaf_text()[1..]),
+        SyntaxKind::Space | SyntaxKind::Parbreak => out.push(' '),
+        kind => panic!("unexpected {:?} node {:?}", kind, node.leaf_text()),
+    }
+}
+
+fn count(node: &SyntaxNode, kind: SyntaxKind) -> usize {
+    let own = usize::from(node.kind() == kind);
+    own + node.children().map(|c| count(c, kind)).sum::<usize>()
+}
+
+fn normalize(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[test]
+fn known_troublemakers_stay_plain_text() {
+    for input in [
+        "p<0.05 *significant*",
+        "- a line that looks like a list",
+        "= not a heading\n+ not an item\n1. not numbered\n/ term: no",
+        "costs $5 // not a comment /* nor this */",
+        "mail@example.com and `code` and ~tilde",
+        "2020-2021, -5 degrees, soft-?hyphen --- dash",
+    ] {
+        let mut doc = Document::new();
+        doc.push(Block::Paragraph(vec![text(input)]));
+        let mut out = String::new();
+        plain_text(&parse(&doc.render()), &mut out);
+        assert_eq!(normalize(&out), normalize(input));
+    }
+}
+
+#[test]
+fn fuzzed_paragraph_text_round_trips() {
+    let mut rng = Rng(0x5eed_1234_abcd_0001);
+    for _ in 0..3000 {
+        let input = rng.text(24);
+        let mut doc = Document::new();
+        doc.push(Block::Paragraph(vec![text(&input)]));
+        let rendered = doc.render();
+        let mut out = String::new();
+        plain_text(&parse(&rendered), &mut out);
+        assert_eq!(
+            normalize(&out),
+            normalize(&input.replace('\r', "")),
+            "rendered: {:?}",
+            rendered
+        );
+
+        let line = escape_line(&input);
+        let mut out = String::new();
+        plain_text(&parse(&line), &mut out);
+        assert_eq!(normalize(&out), normalize(&input.replace('\r', "")));
+    }
+}
+
+// Headings come from the templates (`== {{ abstract.title }} <label>`), which
+// print values through `escape_line`; blocks come from the document tree.
+#[test]
+fn fuzzed_documents_keep_their_structure() {
+    let mut rng = Rng(0x0dd_ba11_cafe_f00d);
+    for _ in 0..500 {
+        let mut rendered = String::new();
+        let (mut headings, mut items) = (0, 0);
+        for _ in 0..rng.below(6) + 1 {
+            let mut doc = Document::new();
+            match rng.below(4) {
+                0 => {
+                    headings += 1;
+                    rendered.push_str(&format!(
+                        "{} {} <abs-{}>\n\n",
+                        "=".repeat(rng.below(3) + 1),
+                        escape_line(&rng.text(8)),
+                        headings
+                    ));
+                    continue;
+                }
+                1 => {
+                    let n = rng.below(3) + 1;
+                    items += n;
+                    let list = (0..n)
+                        .map(|_| {
+                            vec![
+                                text(rng.text(6)),
+                                Inline::Strong(vec![text(rng.text(4))]),
+                                text(rng.text(4)),
+                                Inline::Link {
+                                    target: Target::Url(rng.text(6)),
+                                    body: vec![text(rng.text(4))],
+                                },
+                            ]
+                        })
+                        .collect();
+                    doc.push(Block::List(list));
+                }
+                2 => doc.push(Block::Paragraph(vec![
+                    Inline::Strong(vec![text(rng.text(4))]),
+                    text(rng.text(12)),
+                    Inline::Call {
+                        func: "text(size: 6.5pt)".to_string(),
+                        body: vec![text(rng.text(6))],
+                    },
+                    text(rng.text(6)),
+                ])),
+                _ => doc.push(Block::Paragraph(vec![text(rng.text(16))])),
+            }
+            rendered.push_str(&doc.render());
+            rendered.push_str("\n\n");
+        }
+        let root = parse(&rendered);
+        assert_eq!(count(&root, SyntaxKind::Heading), headings, "{}", rendered);
+        assert_eq!(count(&root, SyntaxKind::Label), headings, "{}", rendered);
+        assert_eq!(count(&root, SyntaxKind::ListItem), items, "{}", rendered);
+        for kind in [
+            SyntaxKind::Strong,
+            SyntaxKind::Emph,
+            SyntaxKind::Raw,
+            SyntaxKind::Equation,
+            SyntaxKind::Ref,
+            SyntaxKind::EnumItem,
+            SyntaxKind::TermItem,
+            SyntaxKind::LineComment,
+            SyntaxKind::BlockComment,
+            SyntaxKind::Link,
+        ] {
+            assert_eq!(count(&root, kind), 0, "{:?} in:\n{}", kind, rendered);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::fs;
+
+mod common;
+use common::fixtures::make_fixture;
+
+fn build(xlsx: &str, out: &str, template: &str) -> assert_cmd::assert::Assert {
+    let mut cmd = cargo_bin_cmd!("symposium-booklet");
+    cmd.args([
+        "build",
+        "--input",
+        xlsx,
+        "--output",
+        out,
+        "--locales",
+        "en",
+        "--template",
+        template,
+        "--no-pdf",
+    ]);
+    cmd.assert()
+}
+
+#[test]
+fn custom_template_renders_book_data_with_escaping() {
+    let dir = "target/test-templates";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(format!("{}/tpl/parts", dir)).unwrap();
+    let xlsx = format!("{}/fixture.xlsx", dir);
+    make_fixture(&xlsx).unwrap();
+    fs::write(
+        format!("{}/tpl/book.typ", dir),
+        "#set page(width: 100mm)\n{% for session in sessions %}\n{% include \"parts/s.typ\" %}\n{% endfor %}\n{{ \"#not code\" }} {{ \"ok\"|safe }} {{ labels.toc_label }}\n",
+    )
+    .unwrap();
+    fs::write(
+        format!("{}/tpl/parts/s.typ", dir),
+        "= {{ session.title }}\n{% for abstract in session.abstracts %}\n== {{ abstract.title }} <{{ abstract.label }}>\n{{ abstract.sections[0].body }}\n{% endfor %}\n",
+    )
+    .unwrap();
+
+    let out = format!("{}/out", dir);
+    build(&xlsx, &out, &format!("{}/tpl", dir)).success();
+    let typ = fs::read_to_string(format!("{}/typst/book_en.typ", out)).unwrap();
+    assert_eq!(
+        typ,
+        "#set page(width: 100mm)\n= Session 1\n== Fixture One <abs-f1>\nText one\n\\#not code ok Table of contents\n"
+    );
+
+    // the entry file itself may be named
+    let out = format!("{}/out-file", dir);
+    build(&xlsx, &out, &format!("{}/tpl/book.typ", dir)).success();
+
+    // template errors name the file
+    fs::write(format!("{}/tpl/book.typ", dir), "{% for x in %}").unwrap();
+    let out = format!("{}/out-bad", dir);
+    build(&xlsx, &out, &format!("{}/tpl", dir))
+        .failure()
+        .stderr(predicates::str::contains("tpl/book.typ"));
+
+    let _ = fs::remove_dir_all(dir);
+}
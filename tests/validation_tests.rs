@@ -1,4 +1,6 @@
 use symposium_booklet::io::excel::{find_header_row, parse_abstracts_from_rows};
+mod common;
+use common::fixtures::make_fixture;
 
 #[test]
 fn header_detection_finds_header() {
@@ -70,6 +72,7 @@ fn slug_collision_appends_suffix() {
             reference: None,
             literature: None,
             locale: "en".to_string(),
+            ..Default::default()
         },
     );
     let session = symposium_booklet::model::Session {
@@ -82,7 +85,13 @@ fn slug_collision_appends_suffix() {
         }],
     };
 
-    symposium_booklet::io::markdown::write_markdown(&abstracts, &vec![session], out).unwrap();
+    symposium_booklet::io::markdown::write_markdown(
+        &abstracts,
+        &[session],
+        out,
+        &Default::default(),
+    )
+    .unwrap();
 
     // expect original and a suffixed file exist
     assert!(session_dir.join("0001-duplicate.md").exists());
@@ -90,3 +99,14 @@ fn slug_collision_appends_suffix() {
 
     let _ = remove_dir_all(out);
 }
+
+#[test]
+#[allow(deprecated)]
+fn validate_input_still_checks_a_workbook() {
+    let dir = "target/test-validate-input";
+    let _ = std::fs::create_dir_all(dir);
+    let xlsx = format!("{}/fixture.xlsx", dir);
+    make_fixture(&xlsx).expect("create fixture");
+    symposium_booklet::validation::validate_input(&xlsx).unwrap();
+    assert!(symposium_booklet::validation::validate_input("target/missing.xlsx").is_err());
+}
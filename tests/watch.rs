@@ -0,0 +1,141 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+mod common;
+use common::output::book_text;
+
+fn write_workbook(path: &str, title: &str) {
+    // write next to the workbook and rename, like a spreadsheet program
+    let tmp = format!("{}.tmp", path);
+    common::write_workbook(
+        &tmp,
+        &[
+            &["id", "title", "authors", "locale", "abstract"],
+            &["w1", title, "Ann Lee", "en", "Some text"],
+        ],
+        &["Session", "w1"],
+    );
+    fs::rename(&tmp, path).unwrap();
+}
+
+fn watch(xlsx: &str, out: &str, extra: &[&str]) -> Child {
+    Command::new(env!("CARGO_BIN_EXE_symposium-booklet"))
+        .args([
+            "watch",
+            "--input",
+            xlsx,
+            "--output",
+            out,
+            "--locales",
+            "en",
+            "--debounce",
+            "100",
+            "--poll",
+            "20",
+            "--max-builds",
+            "2",
+        ])
+        .args(extra)
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap()
+}
+
+fn wait_for(what: &str, done: impl Fn() -> bool) {
+    let start = Instant::now();
+    while !done() {
+        assert!(
+            start.elapsed() < Duration::from_secs(60),
+            "timed out: {}",
+            what
+        );
+        sleep(Duration::from_millis(20));
+    }
+}
+
+fn finish(child: Child) -> String {
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn rebuilds_after_the_workbook_changes() {
+    let dir = "target/test-watch";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/abstracts.xlsx", dir);
+    let out = format!("{}/out", dir);
+    write_workbook(&xlsx, "First title");
+
+    let child = watch(&xlsx, &out, &["--no-pdf"]);
+    let book = format!("{}/typst/book_en.typ", out);
+    wait_for("first build", || Path::new(&book).exists());
+    write_workbook(&xlsx, "Second title");
+    let stdout = finish(child);
+
+    assert!(stdout.contains("Changed: "), "{}", stdout);
+    assert!(stdout.contains("abstracts.xlsx"), "{}", stdout);
+    assert!(!stdout.contains(".tmp"), "{}", stdout);
+    assert!(book_text(&out, "en").contains("Second title"));
+}
+
+// Stands in for typst: fails on a title containing BROKEN, else writes a PDF
+// holding the title.
+#[cfg(unix)]
+fn fake_typst(dir: &str) -> String {
+    use std::os::unix::fs::PermissionsExt;
+    let path = format!("{}/typst", dir);
+    fs::write(
+        &path,
+        "#!/bin/sh\n\
+         [ \"$1\" = \"--version\" ] && { echo 'typst 0.0.0'; exit 0; }\n\
+         for a in \"$@\"; do case \"$a\" in *.typ) file=\"$a\";; *.pdf) out=\"$a\";; esac; done\n\
+         hit=$(grep -rn --include='*.typ' BROKEN \"$(dirname \"$file\")\" | head -n 1)\n\
+         if [ -n \"$hit\" ]; then\n\
+         echo \"$(echo \"$hit\" | cut -d: -f1,2):3: error: unclosed delimiter\" >&2; exit 1\n\
+         fi\n\
+         { echo '%PDF-1.7'; grep -rh '^== ' \"$(dirname \"$file\")\"; } > \"$out\"\n",
+    )
+    .unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+#[cfg(unix)]
+#[test]
+fn failed_rebuild_keeps_the_last_good_pdf() {
+    let dir = "target/test-watch-last-good";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/abstracts.xlsx", dir);
+    let out = format!("{}/out", dir);
+    let typst = fake_typst(dir);
+    write_workbook(&xlsx, "Good title");
+
+    let child = watch(&xlsx, &out, &["--typst-bin", &typst]);
+    let pdf = format!("{}/symposium-2026_en.pdf", out);
+    wait_for("first build", || Path::new(&pdf).exists());
+    write_workbook(&xlsx, "BROKEN title");
+    let stdout = finish(child);
+
+    assert!(stdout.contains("Build ok"), "{}", stdout);
+    assert!(stdout.contains("Build failed"), "{}", stdout);
+    assert!(
+        stdout.contains("abstract w1 (row 2, column 'title'): unclosed delimiter"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Kept the last good PDF(s): symposium-2026_en.pdf"),
+        "{}",
+        stdout
+    );
+    let kept = fs::read_to_string(&pdf).unwrap();
+    assert!(kept.contains("Good title"), "{}", kept);
+    // the failing sources stay for inspection
+    assert!(book_text(&out, "en").contains("BROKEN title"));
+}
@@ -0,0 +1,110 @@
+use std::fs;
+use std::path::Path;
+use symposium_booklet::io::xlsx_rich::rich_cells;
+use umya_spreadsheet::{
+    Font, RichText, TextElement, VerticalAlignmentRunValues, VerticalTextAlignment,
+};
+
+mod common;
+use common::output::book_text;
+
+fn run(text: &str, style: impl Fn(&mut Font)) -> TextElement {
+    let mut el = TextElement::default();
+    el.set_text(text);
+    style(el.get_run_properties_mut());
+    el
+}
+
+fn script(value: VerticalAlignmentRunValues) -> impl Fn(&mut Font) {
+    move |f: &mut Font| {
+        let mut align = VerticalTextAlignment::default();
+        align.set_val(value.clone());
+        f.set_vertical_text_alignment(align);
+    }
+}
+
+// Abstracts sheet whose used range starts at B2, so calamine's row/column offset
+// matters when matching cells (hence not `common::write_workbook`).
+fn write_workbook(path: &str) {
+    let mut book = umya_spreadsheet::new_file();
+    let _ = book.new_sheet("abstracts");
+    let sheet = book.get_sheet_by_name_mut("abstracts").unwrap();
+    for (col, head) in ["id", "title", "locale", "abstract"].iter().enumerate() {
+        sheet.get_cell_mut((col as u32 + 2, 2)).set_value(*head);
+    }
+    sheet.get_cell_mut((2, 3)).set_value("x1");
+    sheet.get_cell_mut((3, 3)).set_value("Rich cells");
+    sheet.get_cell_mut((4, 3)).set_value("en");
+
+    let mut rich = RichText::default();
+    rich.add_rich_text_elements(run("Growth of ", |_| {}));
+    rich.add_rich_text_elements(run("E. coli", |f| {
+        f.set_italic(true);
+    }));
+    rich.add_rich_text_elements(run(" in H", |_| {}));
+    rich.add_rich_text_elements(run("2", script(VerticalAlignmentRunValues::Subscript)));
+    rich.add_rich_text_elements(run("O at 10", |_| {}));
+    rich.add_rich_text_elements(run("5", script(VerticalAlignmentRunValues::Superscript)));
+    rich.add_rich_text_elements(run(" cells, ", |_| {}));
+    rich.add_rich_text_elements(run("fast ", |f| {
+        f.set_bold(true);
+    }));
+    rich.add_rich_text_elements(run("and ", |_| {}));
+    rich.add_rich_text_elements(run("noted", |f| {
+        f.set_underline("single");
+    }));
+    rich.add_rich_text_elements(run(" (*plain*).", |_| {}));
+    sheet.get_cell_mut((5, 3)).set_rich_text(rich);
+    // no formatting runs: typed markup characters are text
+    for (col, value) in ["x2", "Plain cell", "en", "Dose 2*3*4 mg, ~5 ml at x^2^"]
+        .iter()
+        .enumerate()
+    {
+        sheet.get_cell_mut((col as u32 + 2, 4)).set_value(*value);
+    }
+
+    let _ = book.new_sheet("sessions");
+    let s = book.get_sheet_by_name_mut("sessions").unwrap();
+    s.get_cell_mut((1, 1)).set_value("Session 1");
+    s.get_cell_mut((1, 2)).set_value("x1");
+    s.get_cell_mut((1, 3)).set_value("x2");
+    umya_spreadsheet::writer::xlsx::write(&book, Path::new(path)).unwrap();
+}
+
+const MARKUP: &str =
+    "Growth of *E. coli* in H~2~O at 10^5^ cells, **fast** and __noted__ (\\*plain\\*).";
+
+#[test]
+fn formatted_runs_become_markup() {
+    let dir = "target/test-xlsx-rich";
+    fs::create_dir_all(dir).unwrap();
+    let xlsx = format!("{}/rich.xlsx", dir);
+    write_workbook(&xlsx);
+
+    let cells = rich_cells(&xlsx, "abstracts").unwrap();
+    assert_eq!(cells.len(), 1, "{:?}", cells);
+    assert_eq!(cells[&(2, 4)], MARKUP);
+
+    let config = format!("{}/booklet.toml", dir);
+    fs::write(&config, "[text]\nrich_text = true\n").unwrap();
+    let out = format!("{}/out", dir);
+    common::build(
+        &xlsx,
+        &out,
+        &["--locales", "en", "--config", &config, "--no-pdf"],
+    );
+
+    let md = fs::read_to_string(format!("{}/session-1/0001-rich-cells.md", out)).unwrap();
+    assert!(md.contains(MARKUP), "{}", md);
+    let typ = book_text(&out, "en");
+    assert!(typ.contains(
+        "Growth of #emph[E. coli] in H#sub[2]O at 10#super[5] cells, #strong[fast] and #underline[noted] (\\*plain\\*)."
+    ), "{}", typ);
+    assert!(
+        typ.contains("Dose 2\\*3\\*4 mg, \\~5 ml at x^2^"),
+        "{}",
+        typ
+    );
+
+    let _ = fs::remove_dir_all(dir);
+}